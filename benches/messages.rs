@@ -0,0 +1,85 @@
+//! Measures the cost of sending work to the UI thread: time per message, and how
+//! many heap allocations the calling thread makes per message.
+//!
+//! Run with `cargo bench`. Like the tests, this needs a display.
+
+extern crate sdl2_mt;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const MESSAGES: usize = 100000;
+
+/// Sends `MESSAGES` messages with `send`, waits until they have all run, and reports the results.
+/// The allocation count includes the UI thread, which allocates little once it is warmed up.
+fn bench<F: FnMut(&sdl2_mt::Sdl2Mt, usize)>(name: &str, sdlh: &sdl2_mt::Sdl2Mt, mut send: F) {
+    sdlh.flush().unwrap();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for i in 0..MESSAGES {
+        send(sdlh, i);
+    }
+    sdlh.flush().unwrap();
+
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    println!("{:<24} {:>8.0} ns/message {:>6.2} allocations/message",
+             name,
+             elapsed.as_secs_f64() * 1e9 / MESSAGES as f64,
+             allocations as f64 / MESSAGES as f64);
+}
+
+fn main() {
+    let sdlh = sdl2_mt::init();
+    let window = sdlh.create_simple_window("sdl2_mt benchmark", 64, 64).unwrap();
+
+    bench("run_on_ui_thread", &sdlh, |sdlh, i| {
+        sdlh.run_on_ui_thread(Box::new(move |_sdl, windows| {
+            let _ = windows.get(&window).map(|_| i);
+        })).unwrap();
+    });
+
+    bench("run_on_ui_thread_once", &sdlh, |sdlh, i| {
+        sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+            let _ = windows.get(&window).map(|_| i);
+        }).unwrap();
+    });
+
+    let mut submitter = sdlh.submitter(1024).unwrap();
+    bench("Submitter::submit", &sdlh, |_sdlh, i| {
+        submitter.submit(move |_sdl, windows| {
+            let _ = windows.get(&window).map(|_| i);
+        }).unwrap();
+    });
+    drop(submitter);
+
+    bench("present", &sdlh, |sdlh, _| {
+        sdlh.present(window).unwrap();
+    });
+
+    bench("call", &sdlh, |sdlh, i| {
+        sdlh.call(move |_sdl, _windows| i).unwrap();
+    });
+
+    sdlh.exit().unwrap();
+}
@@ -0,0 +1,335 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use pixels::PixelFormatEnum;
+use render::WindowCanvas;
+use state::UiState;
+use timers::{TimerAction, TimerId};
+use trace;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// The format of RGBA bytes in memory, which SDL names by the order of the channels in a
+/// 32-bit number.
+#[cfg(target_endian = "little")]
+const RGBA_FORMAT: PixelFormatEnum = PixelFormatEnum::ABGR8888;
+#[cfg(target_endian = "big")]
+const RGBA_FORMAT: PixelFormatEnum = PixelFormatEnum::RGBA8888;
+
+/// One full-size image of an `Animation`.
+#[derive(Clone)]
+pub struct AnimationFrame {
+    /// 4 bytes per pixel, red, green, blue and alpha, row by row without padding
+    pub rgba: Vec<u8>,
+    /// how long the frame is shown
+    pub delay: Duration,
+}
+
+/// A sequence of images of the same size, played with `Sdl2Mt::play_animation`.
+/// With the `animated-images` feature, animations can be decoded from GIF and APNG files.
+#[derive(Clone)]
+pub struct Animation {
+    pub width: u32,
+    pub height: u32,
+    pub frames: Vec<AnimationFrame>,
+}
+
+impl Animation {
+    /// An animation without frames, to be filled with `push_frame`.
+    pub fn new(width: u32, height: u32) -> Animation {
+        Animation {
+            width,
+            height,
+            frames: vec![],
+        }
+    }
+
+    /// Adds a frame. Returns false, without adding it, if `rgba` doesn't have the size
+    /// of the animation.
+    pub fn push_frame(&mut self, rgba: Vec<u8>, delay: Duration) -> bool {
+        if rgba.len() != self.width as usize * self.height as usize * 4 {
+            return false;
+        }
+        self.frames.push(AnimationFrame { rgba, delay });
+        true
+    }
+
+    /// The time one pass through all frames takes.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.delay).sum()
+    }
+}
+
+/// Refers to an animation started with `play_animation`. Handles can be cloned and sent
+/// to other threads freely.
+#[derive(Clone, Debug)]
+pub struct AnimationHandle {
+    stopped: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+impl AnimationHandle {
+    /// Stops the animation at the frame it is showing.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the animation has stopped, because it was stopped, it played to its end
+    /// without looping, or its window was closed.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::SeqCst)
+    }
+}
+
+/// An animation being played on the UI thread.
+struct Playback {
+    window_id: WindowId,
+    animation: Arc<Animation>,
+    looping: bool,
+    index: usize,
+    /// when the current frame was due, so that delays don't add up rounding errors
+    shown_at: Instant,
+    handle: AnimationHandle,
+}
+
+fn draw_animation_frame(canvas: &mut WindowCanvas, animation: &Animation, frame: &AnimationFrame) -> Result<(), String> {
+    let creator = canvas.texture_creator();
+    let mut texture = creator.create_texture_static(RGBA_FORMAT, animation.width, animation.height).map_err(|error| error.to_string())?;
+    texture.update(None, &frame.rgba, animation.width as usize * 4).map_err(|error| error.to_string())?;
+    canvas.clear();
+    canvas.copy(&texture, None, None)
+}
+
+impl UiState {
+    /// Shows the current frame of the animation, and waits for the next one on a timer.
+    fn show_animation_frame(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, mut playback: Playback) {
+        if playback.handle.stopped.load(Ordering::SeqCst) {
+            playback.handle.finished.store(true, Ordering::SeqCst);
+            return;
+        }
+        let delay = {
+            let frame = &playback.animation.frames[playback.index];
+            match canvases.get_mut(&playback.window_id) {
+                Some(canvas) => {
+                    if let Err(error) = draw_animation_frame(canvas, &playback.animation, frame) {
+                        trace::sdl_error("play_animation", error);
+                    }
+                },
+                None => {
+                    playback.handle.finished.store(true, Ordering::SeqCst);
+                    return;
+                },
+            }
+            frame.delay
+        };
+        self.present(canvases, playback.window_id);
+
+        playback.index += 1;
+        if playback.index == playback.animation.frames.len() {
+            if !playback.looping {
+                playback.handle.finished.store(true, Ordering::SeqCst);
+                return;
+            }
+            playback.index = 0;
+        }
+
+        // if the UI thread fell behind, frames are shown late rather than skipped
+        let now = self.clock.now();
+        playback.shown_at += delay;
+        if playback.shown_at < now {
+            playback.shown_at = now;
+        }
+        let deadline = playback.shown_at;
+        let mut playback = Some(playback);
+        self.timers.add(TimerId::next(), deadline, None, TimerAction::Builtin(Box::new(move |_sdl, windows, state| {
+            if let Some(playback) = playback.take() {
+                state.show_animation_frame(windows, playback);
+            }
+        })));
+    }
+}
+
+impl Sdl2Mt {
+    /// Plays an animation in the window, stretched over its whole canvas, presenting each
+    /// frame when it is due. Without `looping`, the last frame stays up once the animation
+    /// has played through. Playing another animation in the same window doesn't stop this
+    /// one; stop it with the returned handle first.
+    ///
+    /// Frame timing is driven by timers on the UI thread, so no extra thread is involved.
+    /// Animations without frames finish right away.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn play_animation(&self, window_id: WindowId, animation: Arc<Animation>, looping: bool) -> Result<AnimationHandle, Sdl2MtError> {
+        let handle = AnimationHandle {
+            stopped: Arc::new(AtomicBool::new(false)),
+            finished: Arc::new(AtomicBool::new(animation.frames.is_empty())),
+        };
+        if animation.frames.is_empty() {
+            return Ok(handle);
+        }
+
+        let playback_handle = handle.clone();
+        self.run_builtin(move |_sdl, windows, state| {
+            let playback = Playback {
+                window_id,
+                animation,
+                looping,
+                index: 0,
+                shown_at: state.clock.now(),
+                handle: playback_handle,
+            };
+            state.show_animation_frame(windows, playback);
+        })?;
+        Ok(handle)
+    }
+}
+
+#[cfg(feature = "animated-images")]
+mod decode {
+    use super::Animation;
+
+    use gif;
+    use png;
+
+    use std::time::Duration;
+
+    /// Browsers show GIF frames with shorter delays than this for 100ms, and so do we, since
+    /// many GIFs rely on it.
+    const MIN_GIF_DELAY: Duration = Duration::from_millis(20);
+
+    /// Draws `rgba`, a `width` wide image, onto `canvas` at `(left, top)`. With `blend`, the
+    /// image is drawn over what is there, otherwise it replaces it.
+    fn compose(canvas: &mut [u8], canvas_width: u32, rgba: &[u8], left: u32, top: u32, width: u32, blend: bool) {
+        let canvas_height = canvas.len() as u32 / (canvas_width * 4);
+        for (row, line) in rgba.chunks(width as usize * 4).enumerate() {
+            let y = top + row as u32;
+            if y >= canvas_height {
+                break;
+            }
+            for (column, pixel) in line.chunks(4).enumerate() {
+                let x = left + column as u32;
+                if x >= canvas_width {
+                    break;
+                }
+                let offset = (y * canvas_width + x) as usize * 4;
+                let target = &mut canvas[offset..offset + 4];
+                let alpha = pixel[3] as u32;
+                if !blend || alpha == 255 {
+                    target.copy_from_slice(pixel);
+                } else if alpha > 0 {
+                    let under = target[3] as u32 * (255 - alpha) / 255;
+                    let out = alpha + under;
+                    for channel in 0..3 {
+                        target[channel] = ((pixel[channel] as u32 * alpha + target[channel] as u32 * under) / out) as u8;
+                    }
+                    target[3] = out as u8;
+                }
+            }
+        }
+    }
+
+    /// Makes a rectangle of the canvas transparent.
+    fn clear(canvas: &mut [u8], canvas_width: u32, left: u32, top: u32, width: u32, height: u32) {
+        let blank = vec![0; width as usize * height as usize * 4];
+        compose(canvas, canvas_width, &blank, left, top, width, false);
+    }
+
+    /// Expands 8-bit grayscale, grayscale with alpha, or RGB pixels to RGBA.
+    fn to_rgba(pixels: &[u8], color_type: png::ColorType) -> Result<Vec<u8>, String> {
+        let channels = match color_type {
+            png::ColorType::Grayscale => 1,
+            png::ColorType::GrayscaleAlpha => 2,
+            png::ColorType::Rgb => 3,
+            png::ColorType::Rgba => return Ok(pixels.to_vec()),
+            png::ColorType::Indexed => return Err("APNG: indexed colors were not expanded".to_string()),
+        };
+        let mut rgba = Vec::with_capacity(pixels.len() / channels * 4);
+        for pixel in pixels.chunks(channels) {
+            match channels {
+                1 => rgba.extend_from_slice(&[pixel[0], pixel[0], pixel[0], 255]),
+                2 => rgba.extend_from_slice(&[pixel[0], pixel[0], pixel[0], pixel[1]]),
+                _ => rgba.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 255]),
+            }
+        }
+        Ok(rgba)
+    }
+
+    impl Animation {
+        /// Decodes an animated GIF, or a still one as an animation of one frame. The GIF's
+        /// own loop count is ignored; `play_animation` decides whether to loop.
+        pub fn from_gif(data: &[u8]) -> Result<Animation, String> {
+            let mut options = gif::DecodeOptions::new();
+            options.set_color_output(gif::ColorOutput::RGBA);
+            let mut decoder = options.read_info(data).map_err(|error| format!("GIF: {}", error))?;
+            let (width, height) = (decoder.width() as u32, decoder.height() as u32);
+
+            let mut animation = Animation::new(width, height);
+            let mut canvas = vec![0; width as usize * height as usize * 4];
+            while let Some(frame) = decoder.read_next_frame().map_err(|error| format!("GIF: {}", error))? {
+                let (left, top) = (frame.left as u32, frame.top as u32);
+                let (frame_width, frame_height) = (frame.width as u32, frame.height as u32);
+                let previous = canvas.clone();
+                compose(&mut canvas, width, &frame.buffer, left, top, frame_width, true);
+
+                let delay = Duration::from_millis(frame.delay as u64 * 10);
+                let delay = if delay < MIN_GIF_DELAY { Duration::from_millis(100) } else { delay };
+                animation.frames.push(super::AnimationFrame { rgba: canvas.clone(), delay });
+
+                match frame.dispose {
+                    gif::DisposalMethod::Background => clear(&mut canvas, width, left, top, frame_width, frame_height),
+                    gif::DisposalMethod::Previous => canvas = previous,
+                    gif::DisposalMethod::Any | gif::DisposalMethod::Keep => {},
+                }
+            }
+            Ok(animation)
+        }
+
+        /// Decodes an animated PNG, or a still one as an animation of one frame. The APNG's
+        /// own loop count is ignored; `play_animation` decides whether to loop.
+        pub fn from_apng(data: &[u8]) -> Result<Animation, String> {
+            let mut decoder = png::Decoder::new(data);
+            decoder.set_transformations(png::Transformations::normalize_to_color8());
+            let mut reader = decoder.read_info().map_err(|error| format!("APNG: {}", error))?;
+            let (width, height, frame_count) = {
+                let info = reader.info();
+                (info.width, info.height, info.animation_control.map_or(1, |control| control.num_frames))
+            };
+            let animated = reader.info().animation_control.is_some();
+
+            let mut animation = Animation::new(width, height);
+            let mut canvas = vec![0; width as usize * height as usize * 4];
+            let mut buffer = vec![0; reader.output_buffer_size()];
+            while (animation.frames.len() as u32) < frame_count {
+                let output = reader.next_frame(&mut buffer).map_err(|error| format!("APNG: {}", error))?;
+                let pixels = to_rgba(&buffer[..output.line_size * output.height as usize], output.color_type)?;
+                let control = match reader.info().frame_control {
+                    Some(control) => control,
+                    // a still image, or the default image of an APNG that isn't one of its frames
+                    None if !animated => {
+                        animation.frames.push(super::AnimationFrame { rgba: pixels, delay: Duration::from_millis(100) });
+                        break;
+                    },
+                    None => continue,
+                };
+
+                let previous = canvas.clone();
+                let blend = control.blend_op == png::BlendOp::Over;
+                compose(&mut canvas, width, &pixels, control.x_offset, control.y_offset, control.width, blend);
+
+                let denominator = if control.delay_den == 0 { 100 } else { control.delay_den as u64 };
+                let delay = Duration::from_nanos(control.delay_num as u64 * 1_000_000_000 / denominator);
+                animation.frames.push(super::AnimationFrame { rgba: canvas.clone(), delay });
+
+                match control.dispose_op {
+                    png::DisposeOp::Background => clear(&mut canvas, width, control.x_offset, control.y_offset, control.width, control.height),
+                    png::DisposeOp::Previous => canvas = previous,
+                    png::DisposeOp::None => {},
+                }
+            }
+            Ok(animation)
+        }
+    }
+}
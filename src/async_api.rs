@@ -0,0 +1,287 @@
+//! Futures for UI thread work, for applications built around an async executor.
+//!
+//! The futures only rely on `std::future` and wakers, and the event stream on the
+//! `Stream` trait from `futures-core`, so they work with any executor: tokio,
+//! async-std and smol alike.
+//!
+//! The work behind a future is submitted right away, under the queue's `Backpressure`
+//! policy. With `Backpressure::Block`, a full queue blocks the executor thread that creates
+//! the future until there is room; use `Backpressure::Error` to have the future resolve
+//! to `Sdl2MtError::QueueFull` instead.
+
+use Priority;
+use Sdl2Message::Builtin;
+use Sdl2Mt;
+use Sdl2MtError;
+use SdlCreateWindow;
+use SdlHandleEvent;
+use dispatch::{Forwarded, SendEvent};
+use event::Event;
+use render::WindowCanvas;
+use Sdl;
+use WindowId;
+
+use futures_core::Stream;
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+struct Slot<T> {
+    value: Option<Result<T, Sdl2MtError>>,
+    waker: Option<Waker>,
+}
+
+/// A future that resolves once a piece of work submitted to the UI thread has run,
+/// with the value it returned.
+pub struct UiFuture<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+/// The UI thread's end of a `UiFuture`. If it is dropped without completing, e.g. because
+/// the UI thread exited before running the work, the future resolves to `UiThreadExited`.
+struct Completer<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+fn pair<T>() -> (UiFuture<T>, Completer<T>) {
+    let slot = Arc::new(Mutex::new(Slot { value: None, waker: None }));
+    (UiFuture { slot: slot.clone() }, Completer { slot })
+}
+
+impl<T> Completer<T> {
+    fn complete(&self, value: Result<T, Sdl2MtError>) {
+        let waker = {
+            let mut slot = self.slot.lock().unwrap();
+            if slot.value.is_none() {
+                slot.value = Some(value);
+            }
+            slot.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        self.complete(Err(Sdl2MtError::UiThreadExited));
+    }
+}
+
+impl<T> UiFuture<T> {
+    /// Resolves the future to the error that kept its work from being submitted, in place
+    /// of the `UiThreadExited` left behind by the completer that was dropped with the work.
+    fn submit_failed(&self, error: Sdl2MtError) {
+        self.slot.lock().unwrap().value = Some(Err(error));
+    }
+}
+
+impl<T> Future for UiFuture<T> {
+    type Output = Result<T, Sdl2MtError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap();
+        match slot.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Executes a function on the UI thread, returning a future that resolves to the
+    /// function's return value once it has run.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    pub fn run_on_ui_thread_async<T, F>(&self, function: F) -> UiFuture<T>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) -> T + Send + 'static
+    {
+        let (future, completer) = pair();
+        let mut work = Some((function, completer));
+        let sent = self.run_on_ui_thread(Box::new(move |sdl, windows| {
+            if let Some((function, completer)) = work.take() {
+                completer.complete(Ok(function(sdl, windows)));
+            }
+        }));
+        if let Err(error) = sent {
+            future.submit_failed(error);
+        }
+        future
+    }
+
+    /// The asynchronous version of `handle_ui_events`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    pub fn handle_ui_events_async(&self, mut event_handler: Box<SdlHandleEvent>) -> UiFuture<()> {
+        let caller = self.handle_id();
+        let (future, completer) = pair();
+        let mut completer = Some(completer);
+        let sent = self.send_message(Priority::High, Builtin(Box::new(move |sdl, windows, state| {
+            state.handle_events(sdl, windows, caller, &mut *event_handler);
+            if let Some(completer) = completer.take() {
+                completer.complete(Ok(()));
+            }
+        })));
+        if let Err(error) = sent {
+            future.submit_failed(error);
+        }
+        future
+    }
+
+    /// The asynchronous version of `flush`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    pub fn flush_async(&self) -> UiFuture<()> {
+        self.run_on_ui_thread_async(|_sdl, _windows| ())
+    }
+
+    /// The asynchronous version of `create_window`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    pub fn create_window_async(&self, mut window_creator: Box<SdlCreateWindow>) -> UiFuture<Option<WindowId>> {
+        self.run_on_ui_thread_async(move |sdl, windows| {
+            let mut video = match sdl.video() {
+                Ok(video) => video,
+                Err(_) => return None,
+            };
+
+            window_creator(sdl, &mut video).map(|canvas| {
+                let id = WindowId::from_raw(canvas.window().id());
+                windows.insert(id, canvas);
+                id
+            })
+        })
+    }
+
+    /// The asynchronous version of `create_simple_window`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    ///
+    /// If SDL can't make the window, the future resolves to `None`, and the failed SDL call
+    /// is reported through `errors()`.
+    pub fn create_simple_window_async<IntoString: Into<String>>(&self, name: IntoString, width: u32, height: u32) -> UiFuture<Option<WindowId>> {
+        self.create_window_async(self.shared.window_profile.window_creator(name.into(), width, height, None))
+    }
+}
+
+/// Events that were forwarded to an `EventStream` but not yet taken out of it.
+struct StreamQueue {
+    events: VecDeque<SendEvent>,
+    waker: Option<Waker>,
+    /// set when the UI thread stops forwarding, e.g. because it exited
+    closed: bool,
+    /// set when the stream is dropped, so that forwarding stops
+    dropped: bool,
+}
+
+/// A `Stream` of the events seen by the UI thread, created with `event_stream()`.
+pub struct EventStream {
+    queue: Arc<Mutex<StreamQueue>>,
+}
+
+/// The UI thread's end of an `EventStream`. Dropping it ends the stream.
+struct StreamFeeder {
+    queue: Arc<Mutex<StreamQueue>>,
+}
+
+/// Events are kept for `handle_ui_events` instead once this many are waiting in a stream.
+const STREAM_CAPACITY: usize = 2000;
+
+impl StreamFeeder {
+    fn feed(&self, event: &Event) -> Forwarded {
+        let waker = {
+            let mut queue = self.queue.lock().unwrap();
+            if queue.dropped {
+                return Forwarded::Disconnected;
+            }
+            if queue.events.len() >= STREAM_CAPACITY {
+                return Forwarded::Unhandled;
+            }
+            queue.events.push_back(SendEvent::new(event));
+            queue.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Forwarded::Handled
+    }
+}
+
+impl Drop for StreamFeeder {
+    fn drop(&mut self) {
+        let waker = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.closed = true;
+            queue.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.queue.lock().unwrap().dropped = true;
+    }
+}
+
+impl Stream for EventStream {
+    type Item = SendEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SendEvent>> {
+        let mut queue = self.queue.lock().unwrap();
+        match queue.events.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None if queue.closed => Poll::Ready(None),
+            None => {
+                queue.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Returns a `Stream` of every new event, as soon as the UI thread sees it, so async
+    /// applications can `while let Some(event) = stream.next().await` instead of calling
+    /// `handle_ui_events` in a loop. Events that go to the stream are considered handled.
+    /// The stream ends when the UI thread exits.
+    ///
+    /// See `forward_events` for how events are pumped while they are being forwarded.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    pub fn event_stream(&self) -> EventStream {
+        let queue = Arc::new(Mutex::new(StreamQueue {
+            events: VecDeque::new(),
+            waker: None,
+            closed: false,
+            dropped: false,
+        }));
+
+        let feeder = StreamFeeder { queue: queue.clone() };
+        // if the send fails, the feeder is dropped right away and the stream ends
+        let _ = self.run_builtin(move |_sdl, _windows, state| {
+            state.add_event_forwarder(Box::new(move |event| feeder.feed(event)));
+        });
+
+        EventStream { queue }
+    }
+}
@@ -0,0 +1,100 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use event::{Event, WindowEvent};
+use render::WindowCanvas;
+use state::UiState;
+
+use std::collections::HashMap;
+
+pub type SdlAutoPause = dyn FnMut(bool) + Send;
+
+/// When the UI thread stops rendering by itself, set with `Sdl2Mt::set_auto_pause`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AutoPause {
+    /// keep rendering, which is the default
+    #[default]
+    Off,
+    /// pause while every window is minimized or hidden
+    WhenMinimized,
+    /// pause while no window has the keyboard focus, which includes being minimized
+    WhenUnfocused,
+}
+
+
+/// The auto pause setting, and whether it currently pauses rendering.
+#[derive(Default)]
+pub struct AutoPauseState {
+    pub mode: AutoPause,
+    pub paused: bool,
+    /// callbacks registered with `on_auto_pause`
+    callbacks: Vec<Box<SdlAutoPause>>,
+}
+
+impl UiState {
+    /// Keeps track of which windows are minimized or focused, and pauses or resumes
+    /// rendering when that changes.
+    pub fn observe_visibility(&mut self, canvases: &HashMap<WindowId, WindowCanvas>, event: &Event) {
+        let (window_id, win_event) = match *event {
+            Event::Window { window_id, ref win_event, .. } => (WindowId::from_raw(window_id), win_event),
+            _ => return,
+        };
+        match *win_event {
+            WindowEvent::Minimized | WindowEvent::Hidden => self.window(window_id).minimized = true,
+            WindowEvent::Restored | WindowEvent::Maximized | WindowEvent::Shown => self.window(window_id).minimized = false,
+            WindowEvent::FocusGained => self.window(window_id).focused = true,
+            WindowEvent::FocusLost => self.window(window_id).focused = false,
+            // closing a window can leave only minimized ones
+            WindowEvent::Close => {},
+            _ => return,
+        }
+        self.update_auto_pause(canvases);
+    }
+
+    fn update_auto_pause(&mut self, canvases: &HashMap<WindowId, WindowCanvas>) {
+        let windows = &self.windows;
+        let paused = !canvases.is_empty() && match self.auto_pause.mode {
+            AutoPause::Off => false,
+            AutoPause::WhenMinimized => canvases.keys().all(|id| windows.get(id).is_some_and(|window| window.minimized)),
+            AutoPause::WhenUnfocused => canvases.keys().all(|id| windows.get(id).is_none_or(|window| !window.focused)),
+        };
+        if paused == self.auto_pause.paused {
+            return;
+        }
+
+        self.auto_pause.paused = paused;
+        for callback in &mut self.auto_pause.callbacks {
+            callback(paused);
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Stops rendering while the application's windows can't be seen, or aren't being used,
+    /// to save power: `present` drops frames, and scenes are neither updated nor rendered.
+    /// Rendering resumes as soon as a window is restored or focused again. Callbacks
+    /// registered with `on_auto_pause` can pause the application's own work too.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_auto_pause(&self, mode: AutoPause) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            state.auto_pause.mode = mode;
+            state.update_auto_pause(windows);
+        })
+    }
+
+    /// Registers a callback that runs on the UI thread with `true` when auto pause stops
+    /// rendering, and with `false` when it resumes.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn on_auto_pause(&self, callback: Box<SdlAutoPause>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| state.auto_pause.callbacks.push(callback))
+    }
+
+    /// Returns whether auto pause has currently stopped rendering.
+    ///
+    /// This function executes synchronously.
+    pub fn is_auto_paused(&self) -> Result<bool, Sdl2MtError> {
+        self.call_builtin(|_sdl, _windows, state| state.auto_pause.paused)
+    }
+}
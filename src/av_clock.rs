@@ -0,0 +1,233 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use audio::{AudioFormatNum, AudioQueue};
+use render::WindowCanvas;
+use state::UiState;
+use timers::{TimerAction, TimerId};
+
+use std::collections::HashMap;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How late a frame may be presented before `present_frame_at` drops it instead.
+pub const DEFAULT_MAX_FRAME_LATENESS: Duration = Duration::from_millis(100);
+
+/// Frames due within this much of the clock are presented right away, because waiting
+/// for a timer takes about as long.
+const PRESENT_SLACK: Duration = Duration::from_millis(2);
+
+struct ClockState {
+    /// the position of the clock at `base_at`
+    base: Duration,
+    base_at: Instant,
+    running: bool,
+    /// the position of the first byte queued since the last `flush_audio`
+    audio_origin: Duration,
+    /// bytes queued with `queue_audio` since the last `flush_audio`
+    audio_queued: u64,
+    max_lateness: Duration,
+    dropped_frames: u64,
+}
+
+impl ClockState {
+    fn now(&self) -> Duration {
+        if self.running {
+            self.base + self.base_at.elapsed()
+        } else {
+            self.base
+        }
+    }
+
+    fn set(&mut self, position: Duration) {
+        self.base = position;
+        self.base_at = Instant::now();
+    }
+}
+
+/// A presentation clock for video players, shared between the thread that decodes and the
+/// UI thread. While audio is queued through it, it follows the position of the audio that
+/// has been played, so that frames presented with `Sdl2Mt::present_frame_at` stay in sync
+/// with the sound. Without audio, it runs on the wall clock.
+///
+/// Clocks can be cloned and sent to other threads freely; clones share the same position.
+#[derive(Clone)]
+pub struct AvClock(Arc<Mutex<ClockState>>);
+
+fn bytes_to_duration(bytes: u64, bytes_per_second: u64) -> Duration {
+    if bytes_per_second == 0 {
+        return Duration::from_secs(0);
+    }
+    let nanos = bytes as u128 * 1_000_000_000 / bytes_per_second as u128;
+    Duration::new((nanos / 1_000_000_000) as u64, (nanos % 1_000_000_000) as u32)
+}
+
+impl AvClock {
+    /// A running clock, starting at zero.
+    pub fn new() -> AvClock {
+        AvClock(Arc::new(Mutex::new(ClockState {
+            base: Duration::from_secs(0),
+            base_at: Instant::now(),
+            running: true,
+            audio_origin: Duration::from_secs(0),
+            audio_queued: 0,
+            max_lateness: DEFAULT_MAX_FRAME_LATENESS,
+            dropped_frames: 0,
+        })))
+    }
+
+    /// The current presentation time, to compare with the timestamps of decoded frames.
+    pub fn now(&self) -> Duration {
+        self.0.lock().unwrap().now()
+    }
+
+    /// Stops the clock, e.g. when playback is paused. Pause the audio queue as well.
+    pub fn pause(&self) {
+        let mut state = self.0.lock().unwrap();
+        let now = state.now();
+        state.set(now);
+        state.running = false;
+    }
+
+    /// Starts the clock again after `pause`.
+    pub fn resume(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.base_at = Instant::now();
+        state.running = true;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        !self.0.lock().unwrap().running
+    }
+
+    /// Jumps to `position` without audio, e.g. when seeking in a video without sound.
+    pub fn seek(&self, position: Duration) {
+        self.0.lock().unwrap().set(position);
+    }
+
+    /// Queues audio samples, and moves the clock to the position of the audio played so far.
+    /// All audio of the stream must be queued through the clock for it to keep track.
+    /// Returns false if SDL couldn't queue the samples.
+    pub fn queue_audio<T: AudioFormatNum>(&self, queue: &AudioQueue<T>, samples: &[T]) -> bool {
+        if !queue.queue(samples) {
+            return false;
+        }
+        self.0.lock().unwrap().audio_queued += mem::size_of_val(samples) as u64;
+        self.sync_audio(queue);
+        true
+    }
+
+    /// Moves the clock to the position of the audio played so far. `queue_audio` does this
+    /// as well, but players that queue audio in large chunks should call it once per frame.
+    ///
+    /// SDL doesn't say how much of the audio device's own buffer has been played, so the
+    /// whole buffer is counted as not played yet.
+    pub fn sync_audio<T: AudioFormatNum>(&self, queue: &AudioQueue<T>) {
+        let spec = queue.spec();
+        let bytes_per_second = spec.freq.max(0) as u64 * spec.channels as u64 * mem::size_of::<T>() as u64;
+        let pending = queue.size() as u64 + spec.size as u64;
+
+        let mut state = self.0.lock().unwrap();
+        let played = state.audio_queued.saturating_sub(pending);
+        let position = state.audio_origin + bytes_to_duration(played, bytes_per_second);
+        state.set(position);
+    }
+
+    /// Throws away the queued audio and moves the clock to `position`, where the audio
+    /// queued next starts, e.g. when seeking.
+    pub fn flush_audio<T: AudioFormatNum>(&self, queue: &AudioQueue<T>, position: Duration) {
+        queue.clear();
+        let mut state = self.0.lock().unwrap();
+        state.audio_origin = position;
+        state.audio_queued = 0;
+        state.set(position);
+    }
+
+    /// Sets how late a frame may be before `present_frame_at` drops it. The default is
+    /// `DEFAULT_MAX_FRAME_LATENESS`.
+    pub fn set_max_frame_lateness(&self, lateness: Duration) {
+        self.0.lock().unwrap().max_lateness = lateness;
+    }
+
+    /// How many frames `present_frame_at` has dropped because they were too late.
+    pub fn dropped_frames(&self) -> u64 {
+        self.0.lock().unwrap().dropped_frames
+    }
+
+    /// How long until `pts`, or None if the frame is too late and should be dropped.
+    fn until(&self, pts: Duration) -> Option<Duration> {
+        let mut state = self.0.lock().unwrap();
+        let now = state.now();
+        if pts >= now {
+            Some(pts - now)
+        } else if now - pts > state.max_lateness {
+            state.dropped_frames += 1;
+            None
+        } else {
+            Some(Duration::from_secs(0))
+        }
+    }
+}
+
+impl Default for AvClock {
+    fn default() -> AvClock {
+        AvClock::new()
+    }
+}
+
+/// A frame waiting for its presentation time.
+struct ScheduledFrame {
+    window_id: WindowId,
+    clock: AvClock,
+    pts: Duration,
+    render: Box<dyn FnMut(&mut WindowCanvas) + Send>,
+}
+
+impl UiState {
+    /// Renders and presents the frame if it is due, or waits for it on a timer. The clock
+    /// is checked again when the timer runs, since it follows the audio, not the timers.
+    fn schedule_frame(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, mut frame: ScheduledFrame) {
+        let wait = match frame.clock.until(frame.pts) {
+            Some(wait) => wait,
+            None => return,
+        };
+        if wait <= PRESENT_SLACK {
+            if let Some(canvas) = canvases.get_mut(&frame.window_id) {
+                (frame.render)(canvas);
+            }
+            self.present(canvases, frame.window_id);
+            return;
+        }
+
+        let mut frame = Some(frame);
+        let deadline = self.clock.now() + wait;
+        self.timers.add(TimerId::next(), deadline, None, TimerAction::Builtin(Box::new(move |_sdl, windows, state| {
+            if let Some(frame) = frame.take() {
+                state.schedule_frame(windows, frame);
+            }
+        })));
+    }
+}
+
+impl Sdl2Mt {
+    /// Renders a video frame with `render` and presents it once `clock` reaches `pts`, the
+    /// frame's presentation timestamp. Frames that are already due are presented as soon as
+    /// the UI thread gets to them, and frames later than the clock's maximum lateness are
+    /// dropped, so that video catches up with the audio instead of drifting away from it.
+    ///
+    /// Frames should be submitted in order, a few frames ahead of the clock.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn present_frame_at<F>(&self, window_id: WindowId, clock: &AvClock, pts: Duration, render: F) -> Result<(), Sdl2MtError>
+        where F: FnMut(&mut WindowCanvas) + Send + 'static
+    {
+        let frame = ScheduledFrame {
+            window_id,
+            clock: clock.clone(),
+            pts,
+            render: Box::new(render),
+        };
+        self.run_builtin(move |_sdl, windows, state| state.schedule_frame(windows, frame))
+    }
+}
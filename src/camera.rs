@@ -0,0 +1,97 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use rect::{Point, Rect};
+use trace;
+
+/// A pan and zoom transform from world coordinates to window coordinates:
+/// `window = (world - offset) * scale`. Set per window with `Sdl2Mt::set_camera`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Camera {
+    /// the world position shown at the top left corner of the window
+    pub offset: (f64, f64),
+    /// how many window pixels a world unit takes up
+    pub scale: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Camera {
+        Camera {
+            offset: (0.0, 0.0),
+            scale: 1.0,
+        }
+    }
+}
+
+impl Camera {
+    pub fn new(offset: (f64, f64), scale: f64) -> Camera {
+        Camera {
+            offset,
+            scale,
+        }
+    }
+
+    pub fn to_window(&self, x: f64, y: f64) -> (f64, f64) {
+        ((x - self.offset.0) * self.scale, (y - self.offset.1) * self.scale)
+    }
+
+    /// The world position under a window position, e.g. the mouse.
+    pub fn to_world(&self, x: f64, y: f64) -> (f64, f64) {
+        (x / self.scale + self.offset.0, y / self.scale + self.offset.1)
+    }
+
+    pub fn transform_point(&self, point: Point) -> Point {
+        let (x, y) = self.to_window(point.x() as f64, point.y() as f64);
+        Point::new(x.round() as i32, y.round() as i32)
+    }
+
+    /// Moves and scales a rectangle; it stays at least a pixel wide and high.
+    pub fn transform_rect(&self, rect: Rect) -> Rect {
+        let (x, y) = self.to_window(rect.x() as f64, rect.y() as f64);
+        let width = (rect.width() as f64 * self.scale).round().max(1.0);
+        let height = (rect.height() as f64 * self.scale).round().max(1.0);
+        Rect::new(x.round() as i32, y.round() as i32, width as u32, height as u32)
+    }
+
+    /// The camera zoomed by `factor` around a window position, which keeps showing the
+    /// same world position, like zooming with the mouse wheel.
+    pub fn zoom_at(&self, x: f64, y: f64, factor: f64) -> Camera {
+        let (world_x, world_y) = self.to_world(x, y);
+        let scale = self.scale * factor;
+        Camera::new((world_x - x / scale, world_y - y / scale), scale)
+    }
+
+    /// The camera moved by a distance in window pixels, like dragging with the mouse.
+    pub fn pan_by(&self, dx: f64, dy: f64) -> Camera {
+        Camera::new((self.offset.0 - dx / self.scale, self.offset.1 - dy / self.scale), self.scale)
+    }
+}
+
+impl Sdl2Mt {
+    /// Sets the window's camera. The drawing methods, like `draw_lines` and `fill_rects`,
+    /// take world coordinates and draw them through the camera, and scenes are told about
+    /// it through `Scene::camera_changed`, so that viewers can zoom and pan by changing the
+    /// camera instead of recomputing their geometry.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_camera(&self, window_id: WindowId, offset: (f64, f64), scale: f64) -> Result<(), Sdl2MtError> {
+        let camera = Camera::new(offset, scale);
+        self.run_builtin(move |_sdl, windows, state| {
+            if !windows.contains_key(&window_id) {
+                return trace::window_not_found(window_id);
+            }
+            let settings = state.window(window_id);
+            settings.camera = camera;
+            settings.scenes.camera_changed(&camera);
+        })
+    }
+
+    /// Returns the window's camera, the default one if it was never set.
+    ///
+    /// This function executes synchronously.
+    pub fn camera(&self, window_id: WindowId) -> Result<Camera, Sdl2MtError> {
+        self.call_builtin(move |_sdl, _windows, state| {
+            state.windows.get(&window_id).map_or(Camera::default(), |settings| settings.camera)
+        })
+    }
+}
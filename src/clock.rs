@@ -0,0 +1,123 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use Shared;
+use queue::MessageQueue;
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The time that timers, debounced and throttled lambdas are scheduled against.
+#[derive(Clone)]
+pub enum Clock {
+    Real,
+    Test(TestClock),
+}
+
+impl Clock {
+    pub fn now(&self) -> Instant {
+        match *self {
+            Clock::Real => Instant::now(),
+            Clock::Test(ref clock) => clock.now(),
+        }
+    }
+
+    /// How long the UI thread may sleep while waiting for the next timer. Virtual time
+    /// only moves when a test advances it, which wakes the UI thread up.
+    pub fn wait_deadline(&self, next_timer: Option<Instant>) -> Option<Instant> {
+        match *self {
+            Clock::Real => next_timer,
+            Clock::Test(_) => None,
+        }
+    }
+}
+
+/// A virtual clock for tests, passed to `Sdl2MtBuilder::test_clock`. Time stands still
+/// until the test moves it forward, so timers and rate limited lambdas fire exactly when
+/// the test says so, without real sleeps.
+///
+/// Clones share the same time.
+#[derive(Clone)]
+pub struct TestClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+    /// the queue of the UI thread that schedules against this clock, once it has been built
+    ui_queue: Arc<Mutex<Option<Arc<MessageQueue>>>>,
+}
+
+impl TestClock {
+    pub fn new() -> TestClock {
+        TestClock {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::from_secs(0))),
+            ui_queue: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Called by `Sdl2MtBuilder::build`, so that advancing the clock wakes the UI thread.
+    pub fn attach(&self, queue: Arc<MessageQueue>) {
+        *self.ui_queue.lock().unwrap() = Some(queue);
+    }
+
+    pub fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    /// How far the clock has been advanced since it was created.
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+
+    /// Moves the clock forward, and wakes the UI thread up if it is waiting, so that timers
+    /// that became due run soon after, on the UI thread's own time. Use `Sdl2Mt::advance_clock`
+    /// to run them before returning.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+        if let Some(ref queue) = *self.ui_queue.lock().unwrap() {
+            queue.wakeup.wake();
+        }
+    }
+}
+
+impl fmt::Debug for TestClock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TestClock")
+            .field("start", &self.start)
+            .field("elapsed", &self.elapsed())
+            .finish()
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> TestClock {
+        TestClock::new()
+    }
+}
+
+impl Shared {
+    fn test_clock(&self) -> &TestClock {
+        match self.clock {
+            Clock::Test(ref clock) => clock,
+            Clock::Real => panic!("sdl2_mt: the UI thread was not started with Sdl2MtBuilder::test_clock"),
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Moves the test clock forward and runs every timer that became due, earliest first,
+    /// before returning. Like with real time, a repeating timer runs at most once per call,
+    /// so advance by one interval at a time to see every run.
+    ///
+    /// This function executes synchronously.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the UI thread was not started with `Sdl2MtBuilder::test_clock`.
+    pub fn advance_clock(&self, duration: Duration) -> Result<(), Sdl2MtError> {
+        let clock = self.shared.test_clock().clone();
+        self.call_builtin(move |sdl, windows, state| {
+            clock.advance(duration);
+            state.run_due_timers(sdl, windows);
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use Sdl2Message::Lambda;
+use Sdl2Mt;
+use Sdl2MtError;
+use Shared;
+use SdlLambda;
+
+use std::sync::Arc;
+
+/// Owned by the queued message for a key. If the message is dropped without
+/// running, e.g. because `Backpressure::DropOldest` evicted it or the UI thread
+/// exited, the pending lambda is removed so that later submissions queue a new message.
+struct PendingKey {
+    shared: Arc<Shared>,
+    key: String,
+    taken: bool,
+}
+
+impl PendingKey {
+    fn take(&mut self) -> Option<Box<SdlLambda>> {
+        if self.taken {
+            return None;
+        }
+        // once taken, the key belongs to whichever submission comes next
+        self.taken = true;
+        self.shared.coalesced.lock().unwrap().remove(&self.key)
+    }
+}
+
+impl Drop for PendingKey {
+    fn drop(&mut self) {
+        self.take();
+    }
+}
+
+impl Sdl2Mt {
+    /// Executes a lambda function on the UI thread, replacing any lambda submitted
+    /// with the same key that hasn't started running yet. Only the most recent
+    /// lambda for a key runs, so a busy UI thread doesn't work through dozens of
+    /// stale "redraw" requests that queued up in the meantime.
+    ///
+    /// Lambdas with the same key run in the queue position of the first of them
+    /// that was submitted. When the queue is full and uses `Backpressure::DropOldest`,
+    /// coalesced lambdas are the ones that get dropped to make room.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn run_coalesced<IntoString: Into<String>>(&self, key: IntoString, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        let key = key.into();
+        let replaced = self.shared.coalesced.lock().unwrap().insert(key.clone(), lambda);
+        if replaced.is_some() {
+            // an earlier lambda with this key is still queued, and its message will pick up
+            // the replacement instead. the old lambda is dropped here, after the lock has been
+            // released, since whatever it captured may run arbitrary code when dropped.
+            drop(replaced);
+            return Ok(());
+        }
+
+        let mut pending = PendingKey {
+            shared: self.shared.clone(),
+            key,
+            taken: false,
+        };
+        // if the push fails, dropping the message drops `pending`, so the key
+        // doesn't block later submissions.
+        self.send_coalescable(Lambda(Box::new(move |sdl, windows| {
+            if let Some(mut lambda) = pending.take() {
+                lambda(sdl, windows);
+            }
+        })))
+    }
+}
@@ -0,0 +1,95 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use render::WindowCanvas;
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::Duration;
+
+type Completion = Box<dyn FnOnce() + Send>;
+
+/// Collects completion callbacks so that they can be run on the thread that owns the queue.
+/// See `run_on_ui_thread_with_callback`.
+pub struct CompletionQueue {
+    sender: mpsc::Sender<Completion>,
+    receiver: mpsc::Receiver<Completion>,
+}
+
+impl CompletionQueue {
+    pub fn new() -> CompletionQueue {
+        let (sender, receiver) = mpsc::channel();
+        CompletionQueue { sender, receiver }
+    }
+
+    /// Runs every completion callback that has arrived so far, returning how many ran.
+    pub fn run_pending(&self) -> usize {
+        let mut count = 0;
+        while let Ok(completion) = self.receiver.try_recv() {
+            completion();
+            count += 1;
+        }
+        count
+    }
+
+    /// Waits up to `timeout` for a completion callback to arrive and runs it, followed by
+    /// any others that have arrived. Returns how many ran.
+    pub fn wait_and_run(&self, timeout: Duration) -> usize {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(completion) => {
+                completion();
+                1 + self.run_pending()
+            },
+            Err(_) => 0,
+        }
+    }
+}
+
+impl Default for CompletionQueue {
+    fn default() -> CompletionQueue {
+        CompletionQueue::new()
+    }
+}
+
+/// Where `run_on_ui_thread_with_callback` runs its completion callback.
+pub enum CallbackOn<'a> {
+    /// right after the lambda, on the UI thread
+    UiThread,
+    /// on whichever thread calls `run_pending` or `wait_and_run` on the queue
+    Queue(&'a CompletionQueue),
+}
+
+impl Sdl2Mt {
+    /// Executes a lambda function on the UI thread, then passes its return value to
+    /// `on_complete`. This covers fire-and-confirm workflows without blocking and
+    /// without futures.
+    ///
+    /// If the UI thread exits before running the lambda, `on_complete` is never called.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn run_on_ui_thread_with_callback<T, F, C>(&self, lambda: F, callback_on: CallbackOn, on_complete: C) -> Result<(), Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) -> T + Send + 'static,
+              C: FnOnce(T) + Send + 'static
+    {
+        let queue = match callback_on {
+            CallbackOn::UiThread => None,
+            CallbackOn::Queue(queue) => Some(queue.sender.clone()),
+        };
+
+        let mut work = Some((lambda, on_complete));
+        self.run_on_ui_thread(Box::new(move |sdl, windows| {
+            if let Some((lambda, on_complete)) = work.take() {
+                let result = lambda(sdl, windows);
+                match queue {
+                    None => on_complete(result),
+                    Some(ref queue) => {
+                        // the queue may have been dropped, in which case nobody is waiting
+                        let _ = queue.send(Box::new(move || on_complete(result)));
+                    }
+                }
+            }
+        }))
+    }
+}
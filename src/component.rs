@@ -0,0 +1,51 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use event::Event;
+use render::WindowCanvas;
+use scene::{Scene, SceneAction};
+
+/// A window's state together with the functions that handle its events and draw it.
+struct Component<S, E, R> {
+    state: S,
+    on_event: E,
+    render: R,
+}
+
+impl<S, E, R> Scene for Component<S, E, R>
+    where S: Send,
+          E: FnMut(&mut S, &Event) + Send,
+          R: FnMut(&mut S, &mut WindowCanvas) + Send
+{
+    fn event(&mut self, event: &Event) -> SceneAction {
+        (self.on_event)(&mut self.state, event);
+        SceneAction::None
+    }
+
+    fn render(&mut self, canvas: &mut WindowCanvas) {
+        (self.render)(&mut self.state, canvas);
+    }
+}
+
+impl Sdl2Mt {
+    /// Makes a window a self-contained component: `on_event` sees the window's events, and
+    /// `render` draws it once per frame, both on the UI thread and both with `&mut` access to
+    /// `state`, which lives on the UI thread too. The frame is presented after `render` returns.
+    ///
+    /// The component is a scene on top of the window's scene stack, so it runs at the scene
+    /// frame interval, and `pop_scene` removes it. Like any scene, it also sees events that
+    /// aren't sent to a window, like `Quit`, and events still reach event handlers.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn attach_component<S, E, R>(&self, window_id: WindowId, state: S, on_event: E, render: R) -> Result<(), Sdl2MtError>
+        where S: Send + 'static,
+              E: FnMut(&mut S, &Event) + Send + 'static,
+              R: FnMut(&mut S, &mut WindowCanvas) + Send + 'static
+    {
+        self.push_scene(window_id, Box::new(Component {
+            state,
+            on_event,
+            render,
+        }))
+    }
+}
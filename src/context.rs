@@ -0,0 +1,120 @@
+use Sdl;
+use WindowId;
+use render::WindowCanvas;
+use trace;
+use ui_handle::UiHandle;
+
+use std::collections::HashMap;
+
+/// What a lambda written with `ui!` or `on_event!` gets to work with on the UI thread.
+pub struct UiContext<'a> {
+    pub sdl: &'a mut Sdl,
+    pub windows: &'a mut HashMap<WindowId, WindowCanvas>,
+    /// queues more work for the UI thread without waiting for it
+    pub handle: UiHandle,
+}
+
+impl<'a> UiContext<'a> {
+    /// Panics if called on any thread but the UI thread.
+    pub fn new(sdl: &'a mut Sdl, windows: &'a mut HashMap<WindowId, WindowCanvas>) -> UiContext<'a> {
+        UiContext {
+            sdl,
+            windows,
+            handle: UiHandle::current().expect("a UiContext can only be created on the UI thread"),
+        }
+    }
+
+    /// The window's canvas, if the window is still open.
+    pub fn canvas(&mut self, window_id: WindowId) -> Option<&mut WindowCanvas> {
+        self.windows.get_mut(&window_id)
+    }
+}
+
+/// What the body of a `ui!` lambda may evaluate to: nothing, or a `Result<(), String>`, like
+/// SDL's own, whose error is reported like the crate's failed SDL calls, through `errors()`.
+#[doc(hidden)]
+pub trait UiOutcome {
+    fn report(self, operation: &'static str);
+}
+
+impl UiOutcome for () {
+    fn report(self, _operation: &'static str) {}
+}
+
+impl UiOutcome for Result<(), String> {
+    fn report(self, operation: &'static str) {
+        if let Err(error) = self {
+            trace::sdl_error(operation, error);
+        }
+    }
+}
+
+/// Runs a block on the UI thread, like `run_on_ui_thread`, without spelling out the boxed
+/// lambda and its arguments. The block gets a `UiContext` and may use `?` on SDL's
+/// `Result<_, String>`s if it ends in `Ok(())`; errors are reported through `errors()`.
+///
+/// ```no_run
+/// # #[macro_use] extern crate sdl2_mt;
+/// # use sdl2_mt::pixels::Color;
+/// # fn main() {
+/// # let sdlh = sdl2_mt::init();
+/// # let window = sdlh.create_simple_window("ui!", 100, 100).unwrap();
+/// ui!(sdlh, move |ctx| {
+///     let canvas = ctx.canvas(window).ok_or("no window")?;
+///     canvas.set_draw_color(Color::RGB(0, 0, 0));
+///     canvas.clear();
+///     canvas.present();
+///     Ok(())
+/// }).unwrap();
+/// # }
+/// ```
+///
+/// This macro executes asynchronously, and evaluates to the `Result` of `run_on_ui_thread`.
+#[macro_export]
+macro_rules! ui {
+    ($sdlh:expr, move |$ctx:ident| $body:expr) => {
+        $sdlh.run_on_ui_thread(Box::new(move |sdl, windows| {
+            let $ctx = &mut $crate::UiContext::new(sdl, windows);
+            $crate::UiOutcome::report((|| $body)(), "ui!");
+        }))
+    };
+    ($sdlh:expr, |$ctx:ident| $body:expr) => {
+        $sdlh.run_on_ui_thread(Box::new(|sdl, windows| {
+            let $ctx = &mut $crate::UiContext::new(sdl, windows);
+            $crate::UiOutcome::report((|| $body)(), "ui!");
+        }))
+    };
+}
+
+/// Handles pending events on the UI thread, like `handle_ui_events`, without spelling out
+/// the boxed handler and its arguments. The block gets a `UiContext` and the `&Event`, and
+/// evaluates to whether it handled the event.
+///
+/// ```no_run
+/// # #[macro_use] extern crate sdl2_mt;
+/// # use sdl2_mt::event::Event;
+/// # fn main() {
+/// # let sdlh = sdl2_mt::init();
+/// on_event!(sdlh, move |ctx, event| match *event {
+///     Event::Quit { .. } => true,
+///     _ => false,
+/// }).unwrap();
+/// # }
+/// ```
+///
+/// This macro executes synchronously, and evaluates to the `Result` of `handle_ui_events`.
+#[macro_export]
+macro_rules! on_event {
+    ($sdlh:expr, move |$ctx:ident, $event:ident| $body:expr) => {
+        $sdlh.handle_ui_events(Box::new(move |sdl, windows, $event| {
+            let $ctx = &mut $crate::UiContext::new(sdl, windows);
+            $body
+        }))
+    };
+    ($sdlh:expr, |$ctx:ident, $event:ident| $body:expr) => {
+        $sdlh.handle_ui_events(Box::new(|sdl, windows, $event| {
+            let $ctx = &mut $crate::UiContext::new(sdl, windows);
+            $body
+        }))
+    };
+}
@@ -0,0 +1,169 @@
+use Backpressure;
+use Priority;
+use Sdl2Message;
+use Sdl2MtError;
+use queue::Popped;
+use spy::HandleId;
+use trace::{self, Queued};
+use wakeup::Wakeup;
+
+use crossbeam_channel::{self, Receiver, Sender, TryRecvError, TrySendError};
+use std::sync::Mutex;
+
+/// The queue of messages waiting for the UI thread, built on crossbeam channels.
+///
+/// Unlike the default queue, nothing here takes a lock on the hot path, and senders
+/// blocked on a full queue wait for space and shutdown with a single `select!`.
+pub struct MessageQueue {
+    /// control messages, like `Exit`, which are few and always fit
+    control_tx: Sender<Queued>,
+    control_rx: Receiver<Queued>,
+    high_tx: Sender<Queued>,
+    high_rx: Receiver<Queued>,
+    normal_tx: Sender<Queued>,
+    normal_rx: Receiver<Queued>,
+
+    /// never sent on; dropping the sender disconnects `shutdown_rx`, which wakes
+    /// up everyone waiting on the queue at once.
+    shutdown_tx: Mutex<Option<Sender<()>>>,
+    shutdown_rx: Receiver<()>,
+    backpressure: Backpressure,
+    pub wakeup: Wakeup,
+}
+
+impl MessageQueue {
+    pub fn new(capacity: usize, backpressure: Backpressure) -> MessageQueue {
+        let (control_tx, control_rx) = crossbeam_channel::unbounded();
+        let (high_tx, high_rx) = crossbeam_channel::bounded(capacity);
+        let (normal_tx, normal_rx) = crossbeam_channel::bounded(capacity);
+        let (shutdown_tx, shutdown_rx) = crossbeam_channel::bounded(0);
+        MessageQueue {
+            control_tx,
+            control_rx,
+            high_tx,
+            high_rx,
+            normal_tx,
+            normal_rx,
+            shutdown_tx: Mutex::new(Some(shutdown_tx)),
+            shutdown_rx,
+            backpressure,
+            wakeup: Wakeup::new(),
+        }
+    }
+
+    /// Adds a message to the queue, following the queue's backpressure policy when it is full.
+    ///
+    /// Channels can't drop a message from the middle of the queue, so `Backpressure::DropOldest`
+    /// waits like `Backpressure::Block` with this queue.
+    pub fn push(&self, priority: Priority, origin: HandleId, message: Sdl2Message, _coalescable: bool) -> Result<(), Sdl2MtError> {
+        self.push_with(priority, origin, message, Some(self.backpressure))
+    }
+
+    /// Adds a message to the queue, failing instead of waiting when it is full.
+    pub fn try_push(&self, origin: HandleId, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.push_with(Priority::Normal, origin, message, Some(Backpressure::Error))
+    }
+
+    /// Adds a control message, like `Exit`, ahead of all other messages. Only the crate itself
+    /// sends these, and never more than a few at a time, so they are let in even when the
+    /// queue is full, and never wait.
+    pub fn push_control(&self, origin: HandleId, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.push_with(Priority::High, origin, message, None)
+    }
+
+    /// Adds a message to its lane; `backpressure` is `None` for control messages, which always fit.
+    fn push_with(&self, priority: Priority, origin: HandleId, message: Sdl2Message, backpressure: Option<Backpressure>) -> Result<(), Sdl2MtError> {
+        let result = self.send(priority, Queued::new(priority, origin, message), backpressure);
+        match result {
+            Ok(()) => self.wakeup.wake(),
+            Err(ref error) => trace::rejected(error),
+        }
+        result
+    }
+
+    fn send(&self, priority: Priority, message: Queued, backpressure: Option<Backpressure>) -> Result<(), Sdl2MtError> {
+        if self.is_closed() {
+            return Err(Sdl2MtError::UiThreadExited);
+        }
+
+        let lane = match priority {
+            Priority::High => &self.high_tx,
+            Priority::Normal => &self.normal_tx,
+        };
+        // the queue owns both ends of every channel, so sending can only fail when it is full
+        match backpressure {
+            None => {
+                let _ = self.control_tx.send(message);
+            },
+            Some(Backpressure::Error) => {
+                if let Err(TrySendError::Full(_)) = lane.try_send(message) {
+                    return Err(Sdl2MtError::QueueFull);
+                }
+            },
+            Some(_) => {
+                select! {
+                    send(lane, message) -> _ => {},
+                    recv(self.shutdown_rx) -> _ => return Err(Sdl2MtError::UiThreadExited),
+                }
+            },
+        }
+
+        // the queue may have been closed while the message was being sent, after
+        // `close` already drained it. Nothing would ever run or drop the message then.
+        if self.is_closed() {
+            self.drain();
+        }
+        Ok(())
+    }
+
+    /// Takes the next message off the queue, without waiting.
+    pub fn try_pop(&self) -> Popped {
+        if let Ok(message) = self.control_rx.try_recv() {
+            return Popped::Message(message);
+        }
+        if let Ok(message) = self.high_rx.try_recv() {
+            return Popped::Message(message);
+        }
+        if let Ok(message) = self.normal_rx.try_recv() {
+            return Popped::Message(message);
+        }
+
+        if self.is_closed() {
+            Popped::Closed
+        } else {
+            Popped::Empty
+        }
+    }
+
+    /// Takes every message that is currently waiting off the queue, high priority messages first.
+    pub fn pop_all(&self, batch: &mut Vec<Queued>) {
+        // only what is waiting right now, or busy producers could keep this going forever
+        batch.extend(self.control_rx.try_iter().take(self.control_rx.len()));
+        batch.extend(self.high_rx.try_iter().take(self.high_rx.len()));
+        batch.extend(self.normal_rx.try_iter().take(self.normal_rx.len()));
+    }
+
+    /// The number of messages waiting in the queue.
+    pub fn len(&self) -> usize {
+        self.control_rx.len() + self.high_rx.len() + self.normal_rx.len()
+    }
+
+    /// Refuses any further messages and drops the ones that are still waiting,
+    /// which also drops their reply channels so that no calling thread is left waiting.
+    pub fn close(&self) {
+        self.shutdown_tx.lock().unwrap().take();
+        self.drain();
+    }
+
+    fn is_closed(&self) -> bool {
+        matches!(self.shutdown_rx.try_recv(), Err(TryRecvError::Disconnected))
+    }
+
+    fn drain(&self) {
+        let mut count = 0;
+        while self.control_rx.try_recv().is_ok() { count += 1; }
+        while self.high_rx.try_recv().is_ok() { count += 1; }
+        while self.normal_rx.try_recv().is_ok() { count += 1; }
+        trace::dropped("the UI thread exited", count);
+    }
+}
@@ -0,0 +1,536 @@
+use Priority;
+use Sdl2Message::Inline;
+use Sdl2Mt;
+use Sdl2MtError;
+use SdlHandleEvent;
+use ensure_not_ui_thread;
+use event::Event;
+use inline_fn::InlineFn;
+use render::WindowCanvas;
+use spy::HandleId;
+use state::UiState;
+use sys;
+use wakeup;
+use EventPump;
+use Sdl;
+use WindowId;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Deref;
+use std::ptr;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How many unhandled events are kept by default, which is enough for several
+/// seconds of collection even during fast user input.
+pub const DEFAULT_UNHANDLED_CAPACITY: usize = 2000;
+
+/// How long a handle can go without handling events before it is no longer dealt new
+/// events, and the events it was dealt are open to everyone again.
+const CONSUMER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How events are shared between handles that call `handle_ui_events` at the same time,
+/// e.g. one per thread. Every clone of a handle counts as a separate handle.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EventDispatch {
+    /// Each event goes to whichever handle asks first, so a handle that polls
+    /// often can take all of them. This is the default.
+    #[default]
+    FirstCome,
+    /// New events are dealt out in turn to the handles that have been handling events
+    /// within the last second. Events that a handle leaves unhandled are open to all of them.
+    RoundRobin,
+    /// Events sent to a window go to the handle that claimed it with `claim_window_events`,
+    /// even if it leaves them unhandled. Every other event goes to whichever handle asks first.
+    ByWindow,
+}
+
+
+/// An unhandled event, along with the handle it was dealt to, if any.
+struct Pending {
+    event: Event,
+    /// the window the event was sent to, or `None` for the stream of events sent to no window
+    window: Option<WindowId>,
+    /// where the event falls among the events sent to the same window
+    seq: u64,
+    owner: Option<HandleId>,
+}
+
+/// Keeps each window's events in order over one pass through the events for an event handler.
+///
+/// Every event is numbered among the events sent to the same window as it comes out of the
+/// event pump. The handler is only offered an event if it comes after the last event of that
+/// window it was offered in this pass, and once an event is held back from the handler, the
+/// later events of that window are held back behind it. Unhandled events are replayed oldest
+/// first, ahead of new events.
+///
+/// The order only holds within a pass. An event the handler declines doesn't hold back the
+/// window's later events, so those may be handled in the same pass, and the declined event is
+/// then offered again in a later pass, after them.
+struct InOrder {
+    offered: HashMap<Option<WindowId>, u64>,
+    held: HashSet<Option<WindowId>>,
+}
+
+impl InOrder {
+    fn new() -> InOrder {
+        InOrder {
+            offered: HashMap::new(),
+            held: HashSet::new(),
+        }
+    }
+
+    fn may_offer(&self, window: Option<WindowId>, seq: u64) -> bool {
+        !self.held.contains(&window) && self.offered.get(&window).is_none_or(|&last| seq > last)
+    }
+
+    fn offer(&mut self, window: Option<WindowId>, seq: u64) {
+        self.offered.insert(window, seq);
+    }
+
+    fn hold(&mut self, window: Option<WindowId>) {
+        self.held.insert(window);
+    }
+}
+
+/// An `Event` that can be sent to other threads.
+///
+/// SDL events are plain data, with the exception of `Event::User`, which carries two raw
+/// pointers. Those pointers are set to null when a `SendEvent` is created; the `type_`
+/// and `code` fields of user events are kept.
+#[derive(Clone, PartialEq)]
+pub struct SendEvent(Event);
+
+// the only raw pointers an Event can contain are the ones nulled out in SendEvent::new
+unsafe impl Send for SendEvent {}
+unsafe impl Sync for SendEvent {}
+
+impl SendEvent {
+    pub fn new(event: &Event) -> SendEvent {
+        match *event {
+            Event::User { timestamp, window_id, type_, code, .. } => SendEvent(Event::User {
+                timestamp,
+                window_id,
+                type_,
+                code,
+                data1: ptr::null_mut(),
+                data2: ptr::null_mut(),
+            }),
+            ref event => SendEvent(event.clone()),
+        }
+    }
+
+    pub fn into_event(self) -> Event {
+        self.0
+    }
+}
+
+impl Deref for SendEvent {
+    type Target = Event;
+
+    fn deref(&self) -> &Event {
+        &self.0
+    }
+}
+
+/// The window an event was sent to, if it was sent to one.
+pub fn event_window_id(event: &Event) -> Option<WindowId> {
+    match *event {
+        Event::Window { window_id, .. } |
+        Event::KeyDown { window_id, .. } |
+        Event::KeyUp { window_id, .. } |
+        Event::TextEditing { window_id, .. } |
+        Event::TextInput { window_id, .. } |
+        Event::MouseMotion { window_id, .. } |
+        Event::MouseButtonDown { window_id, .. } |
+        Event::MouseButtonUp { window_id, .. } |
+        Event::MouseWheel { window_id, .. } |
+        Event::User { window_id, .. } => Some(WindowId::from_raw(window_id)),
+        _ => None,
+    }
+}
+
+/// What an event forwarder did with an event.
+pub enum Forwarded {
+    Handled,
+    Unhandled,
+    /// the receiving end is gone, and the forwarder should be removed
+    Disconnected,
+}
+
+/// Forwarders see every event coming out of the event pump before the event handlers do.
+pub type SdlForwardEvent = dyn FnMut(&Event) -> Forwarded + Send;
+
+/// The UI thread's event pump, along with the events that nobody has handled yet.
+pub struct EventQueue {
+    pub pump: EventPump,
+    /// events that no event handler has taken responsibility for yet, oldest first.
+    /// Once there are `unhandled_capacity` of them, the oldest are dropped: if no event
+    /// handler takes an event over the course of several entire seconds, it is then
+    /// unlikely to ever be handled by any event handler.
+    unhandled: VecDeque<Pending>,
+    unhandled_capacity: usize,
+    dispatch: EventDispatch,
+    /// the handles that have been handling events recently, in the order they are dealt new
+    /// events, along with when they last did.
+    consumers: Vec<(HandleId, Instant)>,
+    next_consumer: usize,
+    /// the windows claimed with `claim_window_events`
+    window_owners: HashMap<WindowId, HandleId>,
+    /// the sequence number of the next event sent to each window
+    next_seq: HashMap<Option<WindowId>, u64>,
+    forwarders: Vec<Box<SdlForwardEvent>>,
+    /// the type of the events that wake the UI thread up for new messages
+    pub wakeup_type: u32,
+    /// send forwarders a final `Quit` event if the UI thread panics
+    pub quit_on_panic: bool,
+}
+
+impl EventQueue {
+    pub fn new(pump: EventPump, unhandled_capacity: usize) -> EventQueue {
+        EventQueue {
+            pump,
+            unhandled: VecDeque::with_capacity(unhandled_capacity),
+            unhandled_capacity,
+            dispatch: EventDispatch::default(),
+            consumers: vec![],
+            next_consumer: 0,
+            window_owners: HashMap::new(),
+            next_seq: HashMap::new(),
+            forwarders: vec![],
+            wakeup_type: 0,
+            quit_on_panic: false,
+        }
+    }
+
+    /// The number of events waiting for an event handler.
+    pub fn unhandled_len(&self) -> usize {
+        self.unhandled.len()
+    }
+
+    pub fn forwarders_len(&self) -> usize {
+        self.forwarders.len()
+    }
+
+    /// Keeps an event for the next event handler, dropping the oldest unhandled event if full.
+    fn push_unhandled(&mut self, pending: Pending) {
+        if self.unhandled_capacity == 0 {
+            return;
+        }
+        if self.unhandled.len() >= self.unhandled_capacity {
+            self.unhandled.pop_front();
+        }
+        self.unhandled.push_back(pending);
+    }
+
+    /// Numbers a new event among the events sent to the same window.
+    fn sequence(&mut self, event: &Event) -> (Option<WindowId>, u64) {
+        let window = event_window_id(event);
+        let next = self.next_seq.entry(window).or_insert(0);
+        let seq = *next;
+        *next += 1;
+        (window, seq)
+    }
+
+    /// Notes that a handle is handling events, and forgets the handles that stopped.
+    fn touch_consumer(&mut self, caller: HandleId) {
+        let now = Instant::now();
+        self.consumers.retain(|&(_, seen)| now.duration_since(seen) < CONSUMER_TIMEOUT);
+        match self.consumers.iter_mut().find(|consumer| consumer.0 == caller) {
+            Some(consumer) => consumer.1 = now,
+            None => self.consumers.push((caller, now)),
+        }
+    }
+
+    /// The handle a new event sent to `window` is dealt to, if any.
+    fn assign(&mut self, window: Option<WindowId>) -> Option<HandleId> {
+        match self.dispatch {
+            EventDispatch::FirstCome => None,
+            EventDispatch::RoundRobin => {
+                // while a window's events wait for a handle, its new events go to the same
+                // handle, so that they aren't handled ahead of the waiting ones elsewhere
+                let waiting = self.unhandled.iter().rev()
+                    .filter(|pending| pending.window == window)
+                    .filter_map(|pending| pending.owner)
+                    .next();
+                if let Some(owner) = waiting {
+                    if self.is_consumer(owner) {
+                        return Some(owner);
+                    }
+                }
+                if self.consumers.is_empty() {
+                    return None;
+                }
+                self.next_consumer %= self.consumers.len();
+                let owner = self.consumers[self.next_consumer].0;
+                self.next_consumer += 1;
+                Some(owner)
+            },
+            EventDispatch::ByWindow => {
+                window.and_then(|window_id| self.window_owners.get(&window_id).cloned())
+            },
+        }
+    }
+
+    /// Whether an event dealt to `owner` may be offered to `caller`. Events dealt to
+    /// handles that stopped handling events are open to everyone.
+    fn may_take(&self, owner: Option<HandleId>, caller: Option<HandleId>) -> bool {
+        match owner {
+            None => true,
+            Some(owner) => Some(owner) == caller || !self.is_consumer(owner),
+        }
+    }
+
+    fn is_consumer(&self, handle: HandleId) -> bool {
+        self.consumers.iter().any(|&(consumer, _)| consumer == handle)
+    }
+
+    /// The handle an event stays dealt to after being left unhandled.
+    fn declined(&self, owner: Option<HandleId>) -> Option<HandleId> {
+        match self.dispatch {
+            EventDispatch::ByWindow => owner,
+            EventDispatch::FirstCome | EventDispatch::RoundRobin => None,
+        }
+    }
+
+    /// Offers an event to each forwarder in turn, until one of them handles it.
+    fn forward(&mut self, event: &Event) -> bool {
+        let mut handled = false;
+        let mut index = 0;
+        while !handled && index < self.forwarders.len() {
+            match (self.forwarders[index])(event) {
+                Forwarded::Handled => handled = true,
+                Forwarded::Unhandled => index += 1,
+                Forwarded::Disconnected => drop(self.forwarders.remove(index)),
+            }
+        }
+        handled
+    }
+}
+
+impl UiState {
+    /// Registers an event forwarder. The UI thread pumps events as soon as they arrive,
+    /// so forwarders see them even if `handle_ui_events` is never called.
+    pub fn add_event_forwarder(&mut self, forwarder: Box<SdlForwardEvent>) {
+        self.events.forwarders.push(forwarder);
+    }
+
+    /// Pulls new events out of the event pump and offers them to the forwarders,
+    /// keeping the ones that remain unhandled.
+    pub fn pump_events(&mut self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>) {
+        self.pump_events_with(sdl, windows, None, &mut InOrder::new(), &mut |_, _, _| false);
+    }
+
+    /// Passes every unhandled event that the calling handle may take to an event handler,
+    /// followed by any new events, according to the `EventDispatch` policy. Within the pass,
+    /// each window's events are offered in the order they arrived, as described on `InOrder`.
+    pub fn handle_events(&mut self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>, caller: HandleId, handle_event: &mut SdlHandleEvent) {
+        self.events.touch_consumer(caller);
+        let mut order = InOrder::new();
+        for _ in 0..self.events.unhandled.len() {
+            // we're within the length of the queue, this unwrap is safe.
+            let mut pending = self.events.unhandled.pop_front().unwrap();
+            if !order.may_offer(pending.window, pending.seq) || !self.events.may_take(pending.owner, Some(caller)) {
+                order.hold(pending.window);
+                self.events.unhandled.push_back(pending);
+                continue;
+            }
+            order.offer(pending.window, pending.seq);
+            if handle_event(sdl, windows, &pending.event) {
+                self.latency.record(&pending.event);
+            } else {
+                // place unhandled events back on the end of the queue
+                pending.owner = self.events.declined(pending.owner);
+                self.events.unhandled.push_back(pending);
+            }
+        }
+
+        self.pump_events_with(sdl, windows, Some(caller), &mut order, handle_event);
+    }
+
+    /// Removes every unhandled event that the calling handle may take from the queue,
+    /// without offering them to an event handler. They are considered handled.
+    pub fn take_unhandled_events(&mut self, caller: HandleId) -> Vec<SendEvent> {
+        self.events.touch_consumer(caller);
+        let mut order = InOrder::new();
+        let mut taken = vec![];
+        for _ in 0..self.events.unhandled.len() {
+            // we're within the length of the queue, this unwrap is safe.
+            let pending = self.events.unhandled.pop_front().unwrap();
+            if order.may_offer(pending.window, pending.seq) && self.events.may_take(pending.owner, Some(caller)) {
+                order.offer(pending.window, pending.seq);
+                self.latency.record(&pending.event);
+                taken.push(SendEvent::new(&pending.event));
+            } else {
+                order.hold(pending.window);
+                self.events.unhandled.push_back(pending);
+            }
+        }
+        taken
+    }
+
+    pub fn set_event_dispatch(&mut self, dispatch: EventDispatch) {
+        self.events.dispatch = dispatch;
+        self.events.next_consumer = 0;
+    }
+
+    pub fn claim_window_events(&mut self, window_id: WindowId, owner: HandleId) {
+        self.events.window_owners.insert(window_id, owner);
+    }
+
+    /// Passes every unhandled event and every new event to an event handler at once.
+    /// They are all considered handled.
+    pub fn handle_events_batch<F>(&mut self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>, caller: HandleId, handle_batch: F)
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &[Event])
+    {
+        self.events.touch_consumer(caller);
+        let mut order = InOrder::new();
+        let mut batch = vec![];
+        for _ in 0..self.events.unhandled.len() {
+            // we're within the length of the queue, this unwrap is safe.
+            let pending = self.events.unhandled.pop_front().unwrap();
+            if order.may_offer(pending.window, pending.seq) && self.events.may_take(pending.owner, Some(caller)) {
+                order.offer(pending.window, pending.seq);
+                self.latency.record(&pending.event);
+                batch.push(pending.event);
+            } else {
+                order.hold(pending.window);
+                self.events.unhandled.push_back(pending);
+            }
+        }
+        self.pump_events_with(sdl, windows, Some(caller), &mut order, &mut |_sdl, _windows, event| {
+            batch.push(event.clone());
+            true
+        });
+        handle_batch(sdl, windows, &batch);
+    }
+
+    /// Pulls new events out of the event pump, offering each to the forwarders and then, if
+    /// it was dealt to nobody else, to the event handler running on behalf of `caller`.
+    fn pump_events_with<F>(&mut self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>, caller: Option<HandleId>, order: &mut InOrder, handle_event: &mut F)
+        where F: FnMut(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &Event) -> bool + ?Sized
+    {
+        self.sync_kiosk(sdl, windows);
+        self.sync_tiling(sdl, windows);
+
+        let new_events: Vec<Event> = self.events.pump.poll_iter().collect();
+        for event in new_events {
+            if wakeup::is_wakeup_event(self.events.wakeup_type, &event) {
+                continue;
+            }
+            self.observe_event(windows, &event);
+            let (window, seq) = self.events.sequence(&event);
+            if self.events.forward(&event) {
+                self.latency.record(&event);
+                continue;
+            }
+            let mut owner = self.events.assign(window);
+            if order.may_offer(window, seq) && self.events.may_take(owner, caller) {
+                order.offer(window, seq);
+                if handle_event(sdl, windows, &event) {
+                    self.latency.record(&event);
+                    continue;
+                }
+                owner = self.events.declined(owner);
+            } else {
+                order.hold(window);
+            }
+            // if the event was unhandled, add it to the queue
+            self.events.push_unhandled(Pending { event, window, seq, owner });
+        }
+    }
+}
+
+impl Drop for EventQueue {
+    fn drop(&mut self) {
+        if !self.quit_on_panic || !thread::panicking() {
+            return;
+        }
+
+        // tells everyone receiving events to shut down, just like closing the last window would
+        let timestamp = unsafe { sys::timer::SDL_GetTicks() };
+        let quit = Event::Quit { timestamp };
+        for forwarder in &mut self.forwarders {
+            forwarder(&quit);
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Like `handle_ui_events`, but the event_handler function is called once, with every
+    /// pending event in the order they arrived, instead of once per event. This is cheaper
+    /// when a lot of events pile up between calls, and lets the handler look at the whole
+    /// batch, e.g. to only act on the last resize. All the events count as handled, and the
+    /// slice is empty if there are none. Events dealt to other handles by the
+    /// `EventDispatch` policy are left for them.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// event_handler function has completed.
+    pub fn handle_ui_events_batch<F>(&self, event_handler: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &[Event]) + Send + 'static
+    {
+        ensure_not_ui_thread()?;
+        let caller = self.handle_id();
+        let (tx, rx) = mpsc::channel();
+        let sent = Instant::now();
+        self.send_message(Priority::High, Inline(InlineFn::new(move |sdl, windows, state| {
+            state.handle_events_batch(sdl, windows, caller, event_handler);
+            let _ = tx.send(());
+        })))?;
+        self.recv_reply(rx, sent)
+    }
+
+    /// Removes the events that no event handler has handled yet from the UI thread, and
+    /// returns them, oldest first, so that the caller can route or log them itself. Events
+    /// dealt to other handles by the `EventDispatch` policy are left for them, and the
+    /// raw pointers of user events are set to null, as with `SendEvent`.
+    ///
+    /// New events are not pumped, so this only returns events that were already waiting.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// events have been taken.
+    pub fn take_unhandled_events(&self) -> Result<Vec<Event>, Sdl2MtError> {
+        let caller = self.handle_id();
+        let taken = self.call_builtin(move |_sdl, _windows, state| state.take_unhandled_events(caller))?;
+        Ok(taken.into_iter().map(SendEvent::into_event).collect())
+    }
+
+    /// Sets how events are shared between handles that call `handle_ui_events` at the
+    /// same time. Events that are already waiting keep the handle they were dealt to.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_event_dispatch(&self, dispatch: EventDispatch) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| state.set_event_dispatch(dispatch))
+    }
+
+    /// Claims the events sent to a window for this handle, taking them over from any handle
+    /// that claimed them before. Claims only matter under `EventDispatch::ByWindow`, and a
+    /// handle that stops handling events for more than a second loses its claimed events to
+    /// whichever handle asks next.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn claim_window_events(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        let owner = self.handle_id();
+        self.run_builtin(move |_sdl, _windows, state| state.claim_window_events(window_id, owner))
+    }
+
+    /// Sends every new event to a channel, as soon as the UI thread sees it. Forwarded events
+    /// are considered handled, so they are not passed to `handle_ui_events`. Forwarding stops
+    /// once the receiving end of the channel is dropped.
+    ///
+    /// The UI thread picks up new events as soon as they arrive, so nobody needs to call
+    /// `handle_ui_events` to keep events flowing.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn forward_events(&self, sender: mpsc::Sender<SendEvent>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            state.add_event_forwarder(Box::new(move |event| {
+                match sender.send(SendEvent::new(event)) {
+                    Ok(()) => Forwarded::Handled,
+                    Err(_) => Forwarded::Disconnected,
+                }
+            }));
+        })
+    }
+}
@@ -0,0 +1,238 @@
+use Priority;
+use Sdl2Mt;
+use Sdl2MtError;
+use SdlCreateWindow;
+use SdlHandleEvent;
+use SdlLambda;
+use Sdl;
+use WindowId;
+use error;
+use render::WindowCanvas;
+
+use std::any::{Any, type_name};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+/// The core of what application code does with the UI thread. Code that takes a
+/// `UiDispatcher` instead of an `Sdl2Mt` can be unit tested with a `MockDispatcher`,
+/// without initializing SDL at all.
+pub trait UiDispatcher {
+    fn create_window(&self, window_creator: Box<SdlCreateWindow>) -> Result<Option<WindowId>, Sdl2MtError>;
+
+    fn create_simple_window<IntoString: Into<String>>(&self, name: IntoString, width: u32, height: u32) -> Result<WindowId, Sdl2MtError>;
+
+    fn run_on_ui_thread(&self, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError>;
+
+    fn run_on_ui_thread_with_priority(&self, priority: Priority, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError>;
+
+    fn call<T, F>(&self, function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) -> T + Send + 'static;
+
+    fn handle_ui_events(&self, event_handler: Box<SdlHandleEvent>) -> Result<(), Sdl2MtError>;
+
+    fn present(&self, window_id: WindowId) -> Result<(), Sdl2MtError>;
+
+    fn flush(&self) -> Result<(), Sdl2MtError>;
+}
+
+impl UiDispatcher for Sdl2Mt {
+    fn create_window(&self, window_creator: Box<SdlCreateWindow>) -> Result<Option<WindowId>, Sdl2MtError> {
+        Sdl2Mt::create_window(self, window_creator)
+    }
+
+    fn create_simple_window<IntoString: Into<String>>(&self, name: IntoString, width: u32, height: u32) -> Result<WindowId, Sdl2MtError> {
+        Sdl2Mt::create_simple_window(self, name, width, height)
+    }
+
+    fn run_on_ui_thread(&self, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        Sdl2Mt::run_on_ui_thread(self, lambda)
+    }
+
+    fn run_on_ui_thread_with_priority(&self, priority: Priority, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        Sdl2Mt::run_on_ui_thread_with_priority(self, priority, lambda)
+    }
+
+    fn call<T, F>(&self, function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) -> T + Send + 'static
+    {
+        Sdl2Mt::call(self, function)
+    }
+
+    fn handle_ui_events(&self, event_handler: Box<SdlHandleEvent>) -> Result<(), Sdl2MtError> {
+        Sdl2Mt::handle_ui_events(self, event_handler)
+    }
+
+    fn present(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        Sdl2Mt::present(self, window_id)
+    }
+
+    fn flush(&self) -> Result<(), Sdl2MtError> {
+        Sdl2Mt::flush(self)
+    }
+}
+
+/// A call recorded by a `MockDispatcher`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockCall {
+    /// `create_window`, with the window ID the mock handed out
+    CreateWindow(Option<WindowId>),
+    /// `create_simple_window`, with the name and size of the window, and the ID the mock handed
+    /// out, or `None` if it made the window creation fail
+    CreateSimpleWindow(String, u32, u32, Option<WindowId>),
+    RunOnUiThread(Priority),
+    Call,
+    HandleUiEvents,
+    Present(WindowId),
+    Flush,
+}
+
+struct MockState {
+    calls: Vec<MockCall>,
+    window_ids: VecDeque<Option<WindowId>>,
+    next_window_id: u32,
+    call_results: VecDeque<Box<dyn Any + Send>>,
+    failure: Option<Sdl2MtError>,
+}
+
+/// A `UiDispatcher` that records what is submitted to it instead of running it. Lambdas
+/// and event handlers are dropped without running, since there is no SDL to run them with.
+/// Results that would come from the UI thread can be queued up ahead of time.
+///
+/// The mock never initializes SDL, but still needs SDL2 to link, since the closures it
+/// takes work with sdl2's own types. For tests on machines without SDL2, see `FakeUi`.
+pub struct MockDispatcher {
+    state: Mutex<MockState>,
+}
+
+impl MockDispatcher {
+    pub fn new() -> MockDispatcher {
+        MockDispatcher {
+            state: Mutex::new(MockState {
+                calls: vec![],
+                window_ids: VecDeque::new(),
+                next_window_id: 1,
+                call_results: VecDeque::new(),
+                failure: None,
+            }),
+        }
+    }
+
+    /// Returns everything submitted so far, in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.lock().calls.clone()
+    }
+
+    /// Forgets the calls recorded so far.
+    pub fn clear_calls(&self) {
+        self.lock().calls.clear();
+    }
+
+    /// Queues the result of the next window creation. Without one, windows get
+    /// increasing IDs, starting at 1.
+    pub fn push_window_id(&self, window_id: Option<WindowId>) {
+        self.lock().window_ids.push_back(window_id);
+    }
+
+    /// Queues the return value of the next `call`, which has to ask for the same type.
+    pub fn push_call_result<T: Send + 'static>(&self, result: T) {
+        self.lock().call_results.push_back(Box::new(result));
+    }
+
+    /// Makes every call fail with `error`, e.g. `Sdl2MtError::UiThreadExited`, until
+    /// this is called again with `None`. Failed calls are not recorded.
+    pub fn fail_with(&self, error: Option<Sdl2MtError>) {
+        self.lock().failure = error;
+    }
+
+    /// Locks the state, even if a test panicked while holding the lock, so that one failed
+    /// assertion doesn't turn every later call into a `PoisonError`.
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Records a call. `record` only updates the mock's own state; anything that can run
+    /// user code or panic has to happen after it returns, once the lock is released.
+    fn record<T, F: FnOnce(&mut MockState) -> (MockCall, T)>(&self, record: F) -> Result<T, Sdl2MtError> {
+        let mut state = self.lock();
+        if let Some(ref error) = state.failure {
+            return Err(error.clone());
+        }
+        let (call, result) = record(&mut state);
+        state.calls.push(call);
+        Ok(result)
+    }
+}
+
+impl MockState {
+    fn next_window_id(&mut self) -> Option<WindowId> {
+        match self.window_ids.pop_front() {
+            Some(window_id) => window_id,
+            None => {
+                self.next_window_id += 1;
+                Some(WindowId::from_raw(self.next_window_id - 1))
+            },
+        }
+    }
+}
+
+impl Default for MockDispatcher {
+    fn default() -> MockDispatcher {
+        MockDispatcher::new()
+    }
+}
+
+impl UiDispatcher for MockDispatcher {
+    fn create_window(&self, _window_creator: Box<SdlCreateWindow>) -> Result<Option<WindowId>, Sdl2MtError> {
+        self.record(|state| {
+            let window_id = state.next_window_id();
+            (MockCall::CreateWindow(window_id), window_id)
+        })
+    }
+
+    /// Returns `Sdl2MtError::SdlError` if `None` was queued as the window ID, just like
+    /// `Sdl2Mt::create_simple_window` does when SDL can't make the window.
+    fn create_simple_window<IntoString: Into<String>>(&self, name: IntoString, width: u32, height: u32) -> Result<WindowId, Sdl2MtError> {
+        let name = name.into();
+        self.record(|state| {
+            let window_id = state.next_window_id();
+            (MockCall::CreateSimpleWindow(name, width, height, window_id), window_id)
+        }).and_then(|window_id| {
+            window_id.ok_or_else(|| error::sdl_error("create_simple_window", "MockDispatcher: window creation failed"))
+        })
+    }
+
+    fn run_on_ui_thread(&self, _lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        self.record(|_state| (MockCall::RunOnUiThread(Priority::Normal), ()))
+    }
+
+    fn run_on_ui_thread_with_priority(&self, priority: Priority, _lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        self.record(|_state| (MockCall::RunOnUiThread(priority), ()))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the next result queued with `push_call_result` is missing or of another type.
+    fn call<T, F>(&self, _function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) -> T + Send + 'static
+    {
+        let result = self.record(|state| (MockCall::Call, state.call_results.pop_front()))?;
+        let result = result.and_then(|result| result.downcast::<T>().ok())
+            .unwrap_or_else(|| panic!("MockDispatcher: no result of type {} queued for call", type_name::<T>()));
+        Ok(*result)
+    }
+
+    fn handle_ui_events(&self, _event_handler: Box<SdlHandleEvent>) -> Result<(), Sdl2MtError> {
+        self.record(|_state| (MockCall::HandleUiEvents, ()))
+    }
+
+    fn present(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.record(|_state| (MockCall::Present(window_id), ()))
+    }
+
+    fn flush(&self) -> Result<(), Sdl2MtError> {
+        self.record(|_state| (MockCall::Flush, ()))
+    }
+}
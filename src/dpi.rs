@@ -0,0 +1,94 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use render::WindowCanvas;
+use state::UiState;
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+
+/// How the process tells Windows that it handles high DPI displays, set with
+/// `Sdl2MtBuilder::windows_dpi_awareness`. Other platforms ignore this.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DpiAwareness {
+    /// Windows scales the whole window up as a bitmap, which looks blurry
+    Unaware,
+    /// the DPI of the primary display is used for every display
+    System,
+    /// each window follows the DPI of the display it is on
+    PerMonitor,
+    /// like `PerMonitor`, and Windows also scales the non-client area, like the title bar
+    PerMonitorV2,
+}
+
+impl DpiAwareness {
+    /// The value of SDL's `SDL_WINDOWS_DPI_AWARENESS` hint.
+    pub fn hint_value(self) -> &'static str {
+        match self {
+            DpiAwareness::Unaware => "unaware",
+            DpiAwareness::System => "system",
+            DpiAwareness::PerMonitor => "permonitor",
+            DpiAwareness::PerMonitorV2 => "permonitorv2",
+        }
+    }
+}
+
+/// Sent by `Sdl2Mt::scale_changes` when the ratio of drawable pixels to screen
+/// coordinates of a window changes, e.g. when it is moved to a display with another DPI.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScaleChanged {
+    pub window_id: WindowId,
+    pub scale: f32,
+}
+
+/// The ratio of drawable pixels to screen coordinates of a window.
+pub fn window_scale(canvas: &WindowCanvas) -> f32 {
+    let (width, _) = canvas.window().size();
+    let (drawable_width, _) = canvas.window().drawable_size();
+    if width == 0 {
+        1.0
+    } else {
+        drawable_width as f32 / width as f32
+    }
+}
+
+impl UiState {
+    /// Tells the `scale_changes` listeners if the window's scale changed since it was last seen.
+    pub fn check_scale(&mut self, canvases: &HashMap<WindowId, WindowCanvas>, window_id: WindowId) {
+        let scale = match canvases.get(&window_id) {
+            Some(canvas) => window_scale(canvas),
+            None => return,
+        };
+        let previous = self.window(window_id).scale.replace(scale);
+        if previous.is_none_or(|previous| previous == scale) {
+            return;
+        }
+
+        let change = ScaleChanged {
+            window_id,
+            scale,
+        };
+        self.scale_listeners.retain(|listener| listener.send(change).is_ok());
+    }
+}
+
+impl Sdl2Mt {
+    /// Sends a `ScaleChanged` to a channel whenever a window's scale factor changes, which
+    /// happens when it moves to a display with another DPI, or the display's scaling setting
+    /// changes. Redraw at the new resolution when it does, to avoid blurry or mis-sized output.
+    ///
+    /// The UI thread checks the scale whenever a window is shown, moved or resized.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn scale_changes(&self) -> Result<mpsc::Receiver<ScaleChanged>, Sdl2MtError> {
+        let (sender, receiver) = mpsc::channel();
+        self.run_builtin(move |_sdl, windows, state| {
+            // the current scale of every window is the baseline for the changes
+            for &window_id in windows.keys() {
+                state.check_scale(windows, window_id);
+            }
+            state.scale_listeners.push(sender);
+        })?;
+        Ok(receiver)
+    }
+}
@@ -0,0 +1,75 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use camera::Camera;
+use pixels::Color;
+use rect::{Point, Rect};
+use render::WindowCanvas;
+use trace;
+
+impl Sdl2Mt {
+    /// Runs a drawing call on the window's canvas with the given draw color and the
+    /// window's camera, logging it if it fails, or if the window doesn't exist.
+    fn draw_with<F>(&self, window_id: WindowId, color: Color, operation: &'static str, draw: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut WindowCanvas, &Camera) -> Result<(), String> + Send + 'static
+    {
+        self.run_builtin(move |_sdl, windows, state| {
+            if let Some(canvas) = windows.get_mut(&window_id) {
+                let camera = state.windows.get(&window_id).map_or(Camera::default(), |settings| settings.camera);
+                canvas.set_draw_color(color);
+                if let Err(error) = draw(canvas, &camera) {
+                    trace::sdl_error(operation, error);
+                }
+            } else {
+                trace::window_not_found(window_id);
+            }
+        })
+    }
+
+    /// Draws connected lines through `points` on the window's canvas, without a closure.
+    /// Like the other drawing methods, this draws in submission order with the rest of the
+    /// window's rendering, and shows up once the window is presented with `present`.
+    /// Coordinates go through the window's camera, see `set_camera`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn draw_lines(&self, window_id: WindowId, points: &[Point], color: Color) -> Result<(), Sdl2MtError> {
+        let points = points.to_vec();
+        self.draw_with(window_id, color, "SDL_RenderDrawLines", move |canvas, camera| {
+            let points: Vec<Point> = points.iter().map(|&point| camera.transform_point(point)).collect();
+            canvas.draw_lines(&points[..])
+        })
+    }
+
+    /// Draws single pixels on the window's canvas.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn draw_points(&self, window_id: WindowId, points: &[Point], color: Color) -> Result<(), Sdl2MtError> {
+        let points = points.to_vec();
+        self.draw_with(window_id, color, "SDL_RenderDrawPoints", move |canvas, camera| {
+            let points: Vec<Point> = points.iter().map(|&point| camera.transform_point(point)).collect();
+            canvas.draw_points(&points[..])
+        })
+    }
+
+    /// Draws the outlines of rectangles on the window's canvas.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn draw_rects(&self, window_id: WindowId, rects: &[Rect], color: Color) -> Result<(), Sdl2MtError> {
+        let rects = rects.to_vec();
+        self.draw_with(window_id, color, "SDL_RenderDrawRects", move |canvas, camera| {
+            let rects: Vec<Rect> = rects.iter().map(|&rect| camera.transform_rect(rect)).collect();
+            canvas.draw_rects(&rects)
+        })
+    }
+
+    /// Fills rectangles on the window's canvas.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn fill_rects(&self, window_id: WindowId, rects: &[Rect], color: Color) -> Result<(), Sdl2MtError> {
+        let rects = rects.to_vec();
+        self.draw_with(window_id, color, "SDL_RenderFillRects", move |canvas, camera| {
+            let rects: Vec<Rect> = rects.iter().map(|&rect| camera.transform_rect(rect)).collect();
+            canvas.fill_rects(&rects)
+        })
+    }
+}
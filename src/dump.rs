@@ -0,0 +1,108 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use error;
+use sys::video::SDL_WindowFlags;
+
+use std::fmt;
+
+/// A snapshot of everything the UI thread is managing, returned by `Sdl2Mt::dump_state`.
+/// Its `Display` output is meant for logs and bug reports.
+#[derive(Clone, Debug)]
+pub struct StateDump {
+    pub windows: Vec<WindowDump>,
+    /// how many messages were waiting for the UI thread when the dump was taken
+    pub queue_depth: usize,
+    /// how many events were waiting for an event handler
+    pub unhandled_events: usize,
+    pub event_forwarders: usize,
+    pub timers: usize,
+    pub idle_callbacks: usize,
+    pub submitters: usize,
+    /// debounced and throttled lambdas that are waiting to run
+    pub rate_limited: usize,
+    /// the most recent errors reported through `errors()`, oldest first
+    pub recent_errors: Vec<Sdl2MtError>,
+}
+
+/// One window in a `StateDump`.
+#[derive(Clone, Debug)]
+pub struct WindowDump {
+    pub id: WindowId,
+    pub title: String,
+    pub size: (u32, u32),
+    pub shown: bool,
+    pub minimized: bool,
+    /// how many frames were presented with `present`
+    pub frames_presented: u64,
+    pub present_callbacks: usize,
+}
+
+impl fmt::Display for StateDump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "sdl2_mt UI thread state")?;
+        writeln!(f, "  queued messages:   {}", self.queue_depth)?;
+        writeln!(f, "  unhandled events:  {}", self.unhandled_events)?;
+        writeln!(f, "  event forwarders:  {}", self.event_forwarders)?;
+        writeln!(f, "  timers:            {}", self.timers)?;
+        writeln!(f, "  idle callbacks:    {}", self.idle_callbacks)?;
+        writeln!(f, "  submitters:        {}", self.submitters)?;
+        writeln!(f, "  rate limited:      {}", self.rate_limited)?;
+
+        writeln!(f, "  windows: {}", self.windows.len())?;
+        for window in &self.windows {
+            writeln!(f, "    #{} {:?} {}x{}{}{}, {} frames presented, {} present callbacks",
+                window.id, window.title, window.size.0, window.size.1,
+                if window.shown { "" } else { " hidden" },
+                if window.minimized { " minimized" } else { "" },
+                window.frames_presented, window.present_callbacks)?;
+        }
+
+        writeln!(f, "  recent errors: {}", self.recent_errors.len())?;
+        for error in &self.recent_errors {
+            writeln!(f, "    {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Sdl2Mt {
+    /// Collects the windows, queued messages, event handlers, timers and recent errors
+    /// of the UI thread into one snapshot, for debugging problems like a window that
+    /// doesn't update. The snapshot is taken on the UI thread, so everything in it is
+    /// consistent with everything else.
+    ///
+    /// This function executes synchronously.
+    pub fn dump_state(&self) -> Result<StateDump, Sdl2MtError> {
+        let queue = self.queue.clone();
+        self.call_builtin(move |_sdl, canvases, state| {
+            let mut windows: Vec<WindowDump> = canvases.iter().map(|(&id, canvas)| {
+                let window = canvas.window();
+                let flags = window.window_flags();
+                let settings = state.windows.get(&id);
+                WindowDump {
+                    id,
+                    title: window.title().to_owned(),
+                    size: window.size(),
+                    shown: flags & SDL_WindowFlags::SDL_WINDOW_SHOWN as u32 != 0,
+                    minimized: flags & SDL_WindowFlags::SDL_WINDOW_MINIMIZED as u32 != 0,
+                    frames_presented: settings.map_or(0, |settings| settings.frames_presented),
+                    present_callbacks: settings.map_or(0, |settings| settings.present_callbacks.len()),
+                }
+            }).collect();
+            windows.sort_by_key(|window| window.id);
+
+            StateDump {
+                windows,
+                queue_depth: queue.len(),
+                unhandled_events: state.events.unhandled_len(),
+                event_forwarders: state.events.forwarders_len(),
+                timers: state.timers.len(),
+                idle_callbacks: state.idle.len(),
+                submitters: state.submitters.len(),
+                rate_limited: state.rate_limits.len(),
+                recent_errors: error::recent_errors(),
+            }
+        })
+    }
+}
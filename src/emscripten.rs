@@ -0,0 +1,30 @@
+use Sdl2Mt;
+use synchronous;
+
+use std::os::raw::c_int;
+
+extern "C" {
+    fn emscripten_set_main_loop(func: extern "C" fn(), fps: c_int, simulate_infinite_loop: c_int);
+    fn emscripten_cancel_main_loop();
+}
+
+/// Called by the browser once per animation frame.
+extern "C" fn main_loop_iteration() {
+    if synchronous::turn() != Some(true) {
+        unsafe { emscripten_cancel_main_loop() };
+    }
+}
+
+impl Sdl2Mt {
+    /// Hands the main thread over to the browser, which turns the UI loop once per animation
+    /// frame, until `exit` is called. There are no threads on emscripten, so the UI thread is
+    /// always the main thread, as if the handle had been built with `Sdl2MtBuilder::synchronous`.
+    /// Everything else about the handle works the same, so the application can submit its work
+    /// from the closures it runs on the UI thread, like timers and event forwarders.
+    ///
+    /// Like `emscripten_set_main_loop` itself, this never returns: everything after it only
+    /// runs in the closures.
+    pub fn run_main_loop(&self) {
+        unsafe { emscripten_set_main_loop(main_loop_iteration, 0, 1) };
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(feature = "sdl")]
+use Sdl2Mt;
+#[cfg(feature = "sdl")]
+use get_error;
+use WindowId;
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "sdl")]
+use std::collections::VecDeque;
+#[cfg(feature = "sdl")]
+use std::sync::Mutex;
+use std::sync::mpsc;
+
+/// How many errors each receiver returned by `errors()` holds on to, before newer
+/// errors are dropped for it.
+#[cfg(feature = "sdl")]
+const ERROR_STREAM_CAPACITY: usize = 256;
+
+/// How many of the most recent errors are kept for `dump_state`.
+#[cfg(feature = "sdl")]
+const RECENT_ERRORS: usize = 16;
+
+/// Errors that can be returned by the `Sdl2Mt` methods.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sdl2MtError {
+    /// The UI thread has exited, so the request could not be delivered or answered.
+    UiThreadExited,
+    /// The UI thread did not answer within the given time.
+    Timeout,
+    /// The UI thread's queue is full.
+    QueueFull,
+    /// A synchronous call like `call` or `handle_ui_events` was made from the UI thread
+    /// itself, e.g. from inside a lambda, where waiting for the UI thread would never end.
+    WouldDeadlock,
+    /// Messages were dropped without running, e.g. to make room in a full queue or
+    /// because the UI thread exited. Only reported through `errors()`.
+    MessagesDropped(usize),
+    /// The UI thread couldn't deliver a reply, because the caller had stopped waiting
+    /// for it, e.g. after a timeout. Only reported through `errors()`.
+    ReplyDiscarded,
+    /// An SDL call inside one of this crate's helpers failed, with the operation that failed
+    /// and SDL's own description of the error. Failures in helpers that run asynchronously
+    /// are reported through `errors()`.
+    SdlError(String),
+    /// There is no window with this ID, e.g. because it was closed while work for it was
+    /// still queued. Helpers that run asynchronously report this through `errors()`.
+    WindowNotFound(WindowId),
+    /// The UI thread panicked, with the panic message and location. Only reported through
+    /// `errors()`, and only when the UI thread was started with `Sdl2MtBuilder::panic_hook(true)`.
+    UiThreadPanicked(String),
+    /// An argument was out of range, e.g. a zero capacity, naming the argument and what
+    /// is wrong with it. It is checked before anything is sent to the UI thread.
+    InvalidArgument(&'static str),
+    /// A closure of the application's own failed, e.g. an event handler passed to
+    /// `try_handle_ui_events`, with what it was and the error it returned.
+    Callback(String),
+}
+
+impl From<mpsc::RecvTimeoutError> for Sdl2MtError {
+    fn from(error: mpsc::RecvTimeoutError) -> Sdl2MtError {
+        match error {
+            mpsc::RecvTimeoutError::Timeout => Sdl2MtError::Timeout,
+            mpsc::RecvTimeoutError::Disconnected => Sdl2MtError::UiThreadExited,
+        }
+    }
+}
+
+impl fmt::Display for Sdl2MtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Sdl2MtError::UiThreadExited => write!(f, "the sdl2_mt UI thread has exited"),
+            Sdl2MtError::Timeout => write!(f, "timed out waiting for the sdl2_mt UI thread"),
+            Sdl2MtError::QueueFull => write!(f, "the sdl2_mt UI thread's queue is full"),
+            Sdl2MtError::WouldDeadlock => write!(f, "synchronous calls can't be made from the sdl2_mt UI thread"),
+            Sdl2MtError::MessagesDropped(count) => write!(f, "the sdl2_mt UI thread dropped {} messages", count),
+            Sdl2MtError::ReplyDiscarded => write!(f, "a reply from the sdl2_mt UI thread was discarded"),
+            Sdl2MtError::SdlError(ref error) => write!(f, "SDL error on the sdl2_mt UI thread: {}", error),
+            Sdl2MtError::WindowNotFound(window_id) => write!(f, "there is no window with ID {}", window_id),
+            Sdl2MtError::UiThreadPanicked(ref message) => write!(f, "the sdl2_mt UI thread panicked: {}", message),
+            Sdl2MtError::InvalidArgument(problem) => write!(f, "invalid argument: {}", problem),
+            Sdl2MtError::Callback(ref error) => write!(f, "callback failed on the sdl2_mt UI thread: {}", error),
+        }
+    }
+}
+
+impl Error for Sdl2MtError {
+    fn description(&self) -> &str {
+        match *self {
+            Sdl2MtError::UiThreadExited => "UI thread exited",
+            Sdl2MtError::Timeout => "timed out waiting for the UI thread",
+            Sdl2MtError::QueueFull => "UI thread queue full",
+            Sdl2MtError::WouldDeadlock => "synchronous call from the UI thread",
+            Sdl2MtError::MessagesDropped(_) => "UI thread dropped messages",
+            Sdl2MtError::ReplyDiscarded => "UI thread reply discarded",
+            Sdl2MtError::SdlError(_) => "SDL call failed on the UI thread",
+            Sdl2MtError::WindowNotFound(_) => "no window with that ID",
+            Sdl2MtError::UiThreadPanicked(_) => "UI thread panicked",
+            Sdl2MtError::InvalidArgument(_) => "invalid argument",
+            Sdl2MtError::Callback(_) => "callback failed on the UI thread",
+        }
+    }
+}
+
+/// Returns SDL's description of the most recent error on the calling thread, if there is one.
+/// Lambdas running on the UI thread can use this after an SDL call failed without saying why.
+#[cfg(feature = "sdl")]
+pub fn last_error() -> Option<String> {
+    let error = get_error();
+    if error.is_empty() {
+        None
+    } else {
+        Some(error)
+    }
+}
+
+/// Builds the error for a failed SDL call, naming the operation that failed.
+#[cfg(feature = "sdl")]
+pub fn sdl_error<E: fmt::Display>(operation: &str, error: E) -> Sdl2MtError {
+    Sdl2MtError::SdlError(format!("{}: {}", operation, error))
+}
+
+/// Builds the error for a failed closure of the application's, naming what the closure was.
+#[cfg(feature = "sdl")]
+pub fn callback_error<E: fmt::Display>(callback: &str, error: E) -> Sdl2MtError {
+    Sdl2MtError::Callback(format!("{}: {}", callback, error))
+}
+
+#[cfg(feature = "sdl")]
+struct ErrorLog {
+    /// everyone listening for errors
+    streams: Vec<mpsc::SyncSender<Sdl2MtError>>,
+    recent: VecDeque<Sdl2MtError>,
+}
+
+/// There is only ever one UI thread, so this is global, which lets the queue
+/// and the reply channels report errors without a handle.
+#[cfg(feature = "sdl")]
+static ERROR_LOG: Mutex<ErrorLog> = Mutex::new(ErrorLog {
+    streams: Vec::new(),
+    recent: VecDeque::new(),
+});
+
+/// Hands an error to every receiver returned by `errors()`.
+#[cfg(feature = "sdl")]
+pub fn report(error: Sdl2MtError) {
+    let mut log = ERROR_LOG.lock().unwrap();
+    log.streams.retain(|stream| match stream.try_send(error.clone()) {
+        Err(mpsc::TrySendError::Disconnected(_)) => false,
+        // a receiver that has fallen behind misses newer errors
+        _ => true,
+    });
+
+    if log.recent.len() == RECENT_ERRORS {
+        log.recent.pop_front();
+    }
+    log.recent.push_back(error);
+}
+
+/// The most recently reported errors, oldest first.
+#[cfg(feature = "sdl")]
+pub fn recent_errors() -> Vec<Sdl2MtError> {
+    ERROR_LOG.lock().unwrap().recent.iter().cloned().collect()
+}
+
+#[cfg(feature = "sdl")]
+impl Sdl2Mt {
+    /// Returns a receiver for failures that happen in the background, where there is no
+    /// caller to return them to: messages that were dropped without running, replies that
+    /// couldn't be delivered, and SDL calls that failed inside this crate's helpers. This
+    /// lets an application monitor the UI thread's health without checking every call.
+    ///
+    /// Each call returns a new receiver, which gets every error from then on. A receiver
+    /// holds on to a limited number of errors; once it is full, newer errors are dropped
+    /// for it until it is read again.
+    pub fn errors(&self) -> mpsc::Receiver<Sdl2MtError> {
+        let (tx, rx) = mpsc::sync_channel(ERROR_STREAM_CAPACITY);
+        ERROR_LOG.lock().unwrap().streams.push(tx);
+        rx
+    }
+}
@@ -0,0 +1,35 @@
+use Sdl2Mt;
+
+/// What a handle does when a message can't be delivered to the UI thread, or a reply
+/// doesn't come back, e.g. because the UI thread has exited or a call timed out.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// return the error and nothing else
+    #[default]
+    ReturnError,
+    /// also report the error through `errors()`, and through `log` or `tracing` if those
+    /// features are enabled, before returning it
+    Report,
+    /// panic on the calling thread, for applications that can't carry on without the UI thread
+    Panic,
+}
+
+impl Sdl2Mt {
+    /// Returns a clone of this handle that follows `policy` when sending a message or
+    /// receiving a reply fails. The policy only applies to that clone and to clones made
+    /// from it, so that e.g. a render thread can panic while a background thread carries on.
+    /// Handles start out with `FailurePolicy::ReturnError`.
+    ///
+    /// The `try_` methods always return `Sdl2MtError::QueueFull` when the queue is full,
+    /// whatever the policy.
+    pub fn with_failure_policy(&self, policy: FailurePolicy) -> Sdl2Mt {
+        let mut handle = self.clone();
+        handle.failure_policy = policy;
+        handle
+    }
+
+    /// Returns the failure policy this handle follows.
+    pub fn failure_policy(&self) -> FailurePolicy {
+        self.failure_policy
+    }
+}
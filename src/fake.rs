@@ -0,0 +1,178 @@
+use Sdl2MtError;
+use WindowId;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// A color as red, green, blue and alpha, since the fake backend doesn't have SDL's `Color`.
+pub type Rgba = (u8, u8, u8, u8);
+
+/// A drawing command recorded by a `FakeUi` window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DrawCommand {
+    Clear(Rgba),
+    FillRect { x: i32, y: i32, width: u32, height: u32, color: Rgba },
+    Line { from: (i32, i32), to: (i32, i32), color: Rgba },
+    Text { x: i32, y: i32, text: String, color: Rgba },
+}
+
+/// An event queued on a `FakeUi`, standing in for the SDL events a test needs to simulate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FakeEvent {
+    Quit,
+    /// the user asked to close the window
+    WindowClose { window_id: WindowId },
+    WindowResized { window_id: WindowId, width: u32, height: u32 },
+    /// a key, named like SDL's `Keycode::name`, e.g. "Space" or "A"
+    KeyDown { window_id: WindowId, key: String },
+    KeyUp { window_id: WindowId, key: String },
+    TextInput { window_id: WindowId, text: String },
+    MouseMotion { window_id: WindowId, x: i32, y: i32 },
+    /// a mouse button, numbered like SDL's, starting with 1 for the left button
+    MouseButtonDown { window_id: WindowId, button: u8, x: i32, y: i32 },
+    MouseButtonUp { window_id: WindowId, button: u8, x: i32, y: i32 },
+}
+
+/// A snapshot of a `FakeUi` window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FakeWindow {
+    pub title: String,
+    pub size: (u32, u32),
+    /// what was drawn since the window was last presented
+    pub pending: Vec<DrawCommand>,
+    /// what was drawn before the window was last presented
+    pub presented: Vec<DrawCommand>,
+    /// how often the window was presented
+    pub frames: u64,
+}
+
+struct FakeState {
+    windows: HashMap<WindowId, FakeWindow>,
+    next_window_id: u32,
+    events: VecDeque<FakeEvent>,
+}
+
+/// A stand-in for the UI thread written purely in Rust, so that application logic can be
+/// tested on machines and CI images without SDL2. It keeps a map of windows, a queue of events
+/// and, for every window, the drawing commands sent to it, and runs everything on the calling
+/// thread. Build sdl2_mt with the `fake-backend` feature, and without its default `sdl` feature
+/// to drop the dependency on SDL2 altogether.
+///
+/// Its methods mirror the `Sdl2Mt` methods of the same name, including the errors they return,
+/// so the same logic can be driven by either through a small trait of the application's own.
+/// Clones share the same windows and events.
+#[derive(Clone)]
+pub struct FakeUi {
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl FakeUi {
+    pub fn new() -> FakeUi {
+        FakeUi {
+            state: Arc::new(Mutex::new(FakeState {
+                windows: HashMap::new(),
+                next_window_id: 1,
+                events: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Creates a window. Windows get increasing IDs, starting at 1.
+    pub fn create_simple_window<IntoString: Into<String>>(&self, name: IntoString, width: u32, height: u32) -> Result<WindowId, Sdl2MtError> {
+        let mut state = self.state.lock().unwrap();
+        let window_id = WindowId::from_raw(state.next_window_id);
+        state.next_window_id += 1;
+        state.windows.insert(window_id, FakeWindow {
+            title: name.into(),
+            size: (width, height),
+            pending: vec![],
+            presented: vec![],
+            frames: 0,
+        });
+        Ok(window_id)
+    }
+
+    /// Closes a window. Events already queued for it stay in the queue.
+    pub fn close_window(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.state.lock().unwrap().windows.remove(&window_id)
+            .map(|_| ())
+            .ok_or(Sdl2MtError::WindowNotFound(window_id))
+    }
+
+    /// Returns the IDs of the open windows, in the order they were created.
+    pub fn window_ids(&self) -> Vec<WindowId> {
+        let mut window_ids: Vec<WindowId> = self.state.lock().unwrap().windows.keys().cloned().collect();
+        window_ids.sort();
+        window_ids
+    }
+
+    /// Returns a snapshot of the window, with everything drawn to it.
+    pub fn window(&self, window_id: WindowId) -> Result<FakeWindow, Sdl2MtError> {
+        self.with_window(window_id, |window| window.clone())
+    }
+
+    pub fn window_size(&self, window_id: WindowId) -> Result<(u32, u32), Sdl2MtError> {
+        self.with_window(window_id, |window| window.size)
+    }
+
+    /// Changes the size of the window, and queues the `WindowResized` event SDL would send.
+    pub fn resize_window(&self, window_id: WindowId, width: u32, height: u32) -> Result<(), Sdl2MtError> {
+        self.with_window(window_id, |window| window.size = (width, height))?;
+        self.push_event(FakeEvent::WindowResized { window_id, width, height });
+        Ok(())
+    }
+
+    /// Records a drawing command for the next frame of the window.
+    pub fn draw(&self, window_id: WindowId, command: DrawCommand) -> Result<(), Sdl2MtError> {
+        self.with_window(window_id, |window| window.pending.push(command))
+    }
+
+    pub fn clear(&self, window_id: WindowId, color: Rgba) -> Result<(), Sdl2MtError> {
+        self.draw(window_id, DrawCommand::Clear(color))
+    }
+
+    /// Makes the commands drawn since the last call the window's presented frame.
+    pub fn present(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.with_window(window_id, |window| {
+            window.presented = window.pending.drain(..).collect();
+            window.frames += 1;
+        })
+    }
+
+    /// Queues an event, as if the user had caused it.
+    pub fn push_event(&self, event: FakeEvent) {
+        self.state.lock().unwrap().events.push_back(event);
+    }
+
+    /// Takes the oldest queued event.
+    pub fn poll_event(&self) -> Option<FakeEvent> {
+        self.state.lock().unwrap().events.pop_front()
+    }
+
+    /// Passes every queued event to `event_handler`, which returns true for the events
+    /// it handled. Events it didn't handle stay in the queue, in order, like
+    /// `Sdl2Mt::handle_ui_events` keeps them for the next handler. The handler can use
+    /// this `FakeUi`, e.g. to draw or to queue more events, which it gets on the next call.
+    pub fn handle_ui_events<F: FnMut(&FakeEvent) -> bool>(&self, mut event_handler: F) -> Result<(), Sdl2MtError> {
+        let events: Vec<FakeEvent> = self.state.lock().unwrap().events.drain(..).collect();
+        let unhandled: Vec<FakeEvent> = events.into_iter().filter(|event| !event_handler(event)).collect();
+
+        let mut state = self.state.lock().unwrap();
+        for event in unhandled.into_iter().rev() {
+            state.events.push_front(event);
+        }
+        Ok(())
+    }
+
+    fn with_window<T, F: FnOnce(&mut FakeWindow) -> T>(&self, window_id: WindowId, function: F) -> Result<T, Sdl2MtError> {
+        self.state.lock().unwrap().windows.get_mut(&window_id)
+            .map(function)
+            .ok_or(Sdl2MtError::WindowNotFound(window_id))
+    }
+}
+
+impl Default for FakeUi {
+    fn default() -> FakeUi {
+        FakeUi::new()
+    }
+}
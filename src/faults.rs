@@ -0,0 +1,69 @@
+use Sdl2Message::Lambda;
+use Sdl2Mt;
+use Sdl2MtError;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Keeps the UI thread's queue full until it is dropped. Returned by `Sdl2Mt::saturate_queue`.
+pub struct QueueSaturation {
+    release: mpsc::Sender<()>,
+    /// how many filler messages it took to fill the queue
+    pub filler_messages: usize,
+}
+
+impl Drop for QueueSaturation {
+    fn drop(&mut self) {
+        let _ = self.release.send(());
+    }
+}
+
+impl Sdl2Mt {
+    /// Makes the UI thread panic as soon as it picks up this message, ahead of any normal
+    /// priority work, just like a bug in a lambda would. From then on, every handle returns
+    /// `Sdl2MtError::UiThreadExited`, and with `Sdl2MtBuilder::panic_hook`, the panic is
+    /// reported like any other.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn kill_ui_thread(&self) -> Result<(), Sdl2MtError> {
+        self.send_control(Lambda(Box::new(|_sdl, _windows| {
+            panic!("sdl2_mt: UI thread killed by kill_ui_thread");
+        })))
+    }
+
+    /// Blocks the UI thread and fills its queue with empty lambdas, so that submitting
+    /// more work runs into the queue's backpressure policy: `try_` methods return
+    /// `Sdl2MtError::QueueFull`, and the others block, fail or drop messages, depending on
+    /// `Sdl2MtBuilder::backpressure`. The UI thread carries on once the returned value is dropped.
+    ///
+    /// Don't use this with `Sdl2MtBuilder::synchronous`, where blocking the UI loop blocks the test.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn saturate_queue(&self) -> Result<QueueSaturation, Sdl2MtError> {
+        let (release, released) = mpsc::channel();
+        self.run_on_ui_thread_once(move |_sdl, _windows| {
+            let _ = released.recv();
+        })?;
+
+        let mut saturation = QueueSaturation {
+            release,
+            filler_messages: 0,
+        };
+        loop {
+            match self.queue.try_push(self.id.0, Lambda(Box::new(|_sdl, _windows| {}))) {
+                Ok(()) => saturation.filler_messages += 1,
+                Err(Sdl2MtError::QueueFull) => return Ok(saturation),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Keeps the UI thread busy for `duration` once it gets to this message, like a slow
+    /// lambda would, so that timeouts, the watchdog and `EventLatency` can be tested.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn stall_ui_thread(&self, duration: Duration) -> Result<(), Sdl2MtError> {
+        self.run_on_ui_thread_once(move |_sdl, _windows| thread::sleep(duration))
+    }
+}
@@ -0,0 +1,89 @@
+//! SDL functions that are newer than the bindings in the version of `sdl2-sys` we depend on.
+
+use libc::{c_char, c_float, c_int, c_void};
+use sys::sdl::SDL_bool;
+use sys::rect::SDL_Rect;
+use sys::render::SDL_Renderer;
+use sys::version::SDL_version;
+use sys::video::SDL_Window;
+
+use std::mem;
+use std::ptr;
+use std::sync::Once;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub const SDL_SYSWM_WINDOWS: c_int = 1;
+pub const SDL_SYSWM_X11: c_int = 2;
+pub const SDL_SYSWM_COCOA: c_int = 4;
+pub const SDL_SYSWM_UIKIT: c_int = 5;
+pub const SDL_SYSWM_WAYLAND: c_int = 6;
+pub const SDL_SYSWM_ANDROID: c_int = 9;
+
+/// `SDL_DISPLAYEVENT`, available since SDL 2.0.9. sdl2's `Event` passes it on as `Unknown`.
+pub const SDL_DISPLAYEVENT: u32 = 0x150;
+
+/// `SDL_SysWMinfo`, with its union of per-platform structs as an array of pointer-sized
+/// fields. SDL's union is 64 bytes, so this is at least as large.
+#[repr(C)]
+pub struct SDL_SysWMinfo {
+    pub version: SDL_version,
+    pub subsystem: c_int,
+    pub info: [usize; 16],
+}
+
+extern "C" {
+    /// Available since SDL 2.0.5.
+    pub fn SDL_RenderSetIntegerScale(renderer: *mut SDL_Renderer, enable: c_int) -> c_int;
+
+    /// Available since SDL 2.0.5.
+    pub fn SDL_GetDisplayUsableBounds(display_index: c_int, rect: *mut SDL_Rect) -> c_int;
+
+    /// Available since SDL 2.0.5.
+    pub fn SDL_SetWindowOpacity(window: *mut SDL_Window, opacity: c_float) -> c_int;
+
+    /// Part of every SDL 2 release, but missing from the bindings.
+    pub fn SDL_GetWindowWMInfo(window: *mut SDL_Window, info: *mut SDL_SysWMinfo) -> SDL_bool;
+
+    /// Part of every SDL 2 release, but missing from the bindings.
+    pub fn SDL_LoadObject(sofile: *const c_char) -> *mut c_void;
+
+    /// Part of every SDL 2 release, but missing from the bindings.
+    pub fn SDL_LoadFunction(handle: *mut c_void, name: *const c_char) -> *mut c_void;
+}
+
+/// `SDL_RenderSetVSync`, available since SDL 2.0.18.
+pub type SdlRenderSetVSync = unsafe extern "C" fn(renderer: *mut SDL_Renderer, vsync: c_int) -> c_int;
+
+static RENDER_SET_VSYNC_LOOKUP: Once = Once::new();
+/// the address of `SDL_RenderSetVSync`, or 0 if SDL doesn't have it
+static RENDER_SET_VSYNC: AtomicUsize = AtomicUsize::new(0);
+
+/// `SDL_RenderSetVSync`, if the SDL library the program runs with has it. It is looked up
+/// at runtime, since linking against it would make SDL 2.0.18 a requirement for every program.
+pub fn render_set_vsync() -> Option<SdlRenderSetVSync> {
+    RENDER_SET_VSYNC_LOOKUP.call_once(|| {
+        let address = unsafe { load_function::<usize>(b"SDL_RenderSetVSync\0") };
+        RENDER_SET_VSYNC.store(address.unwrap_or(0), Ordering::Release);
+    });
+    match RENDER_SET_VSYNC.load(Ordering::Acquire) {
+        0 => None,
+        address => Some(unsafe { mem::transmute::<usize, SdlRenderSetVSync>(address) }),
+    }
+}
+
+/// Looks up a function in the SDL library that is loaded into the process. `name` must be
+/// nul-terminated, and `F` must be a function pointer type matching the function.
+unsafe fn load_function<F: Copy>(name: &[u8]) -> Option<F> {
+    // Windows needs the library's name; elsewhere, a null name searches the whole process
+    let library: *const c_char = if cfg!(windows) { b"SDL2.dll\0".as_ptr() as *const c_char } else { ptr::null() };
+    let handle = SDL_LoadObject(library);
+    if handle.is_null() {
+        return None;
+    }
+    let function = SDL_LoadFunction(handle, name.as_ptr() as *const c_char);
+    if function.is_null() {
+        None
+    } else {
+        Some(mem::transmute_copy(&function))
+    }
+}
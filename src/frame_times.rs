@@ -0,0 +1,152 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use present::PresentInfo;
+use render::WindowCanvas;
+use trace;
+
+use std::mem;
+use std::time::Duration;
+
+/// Called on the UI thread for every frame that took longer than the window's frame budget,
+/// with the frame's timing and the budget it missed.
+pub type SdlJank = dyn FnMut(&PresentInfo, Duration) + Send;
+
+/// The upper bounds of the buckets of `FrameTimes::histogram`, in milliseconds.
+/// The last bucket of the histogram counts every frame slower than the last bound.
+pub const FRAME_TIME_BUCKETS_MS: [u64; 9] = [4, 8, 12, 17, 20, 25, 34, 50, 100];
+
+/// The frame budget of windows on displays that don't report their refresh rate.
+const DEFAULT_REFRESH_RATE: u64 = 60;
+
+/// The time between consecutive frames presented with `present`, returned by
+/// `Sdl2Mt::frame_times`. Covers the frames since the previous call to `frame_times`
+/// for the same window.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FrameTimes {
+    /// how many frame intervals were measured
+    pub frames: u64,
+    /// how many frames fell into each of the buckets of `FRAME_TIME_BUCKETS_MS`, with
+    /// one extra bucket at the end for frames slower than that
+    pub histogram: [u64; 10],
+    /// how many frames took longer than one and a half times the budget
+    pub janky_frames: u64,
+    /// how many vsync intervals went by without a new frame
+    pub dropped_frames: u64,
+    pub worst: Duration,
+    /// the time one frame may take, by default one refresh of the window's display
+    pub budget: Duration,
+}
+
+/// Frame time bookkeeping for one window.
+#[derive(Default)]
+pub struct FrameTimeTracker {
+    times: FrameTimes,
+    /// set with `set_frame_budget`, instead of following the display's refresh rate
+    budget_override: Option<Duration>,
+    /// the refresh interval of the window's display, looked up on the first frame
+    refresh_interval: Option<Duration>,
+    jank_callbacks: Vec<Box<SdlJank>>,
+}
+
+impl FrameTimeTracker {
+    fn budget(&mut self, canvas: &WindowCanvas) -> Duration {
+        if let Some(budget) = self.budget_override {
+            return budget;
+        }
+
+        *self.refresh_interval.get_or_insert_with(|| {
+            let refresh_rate = match canvas.window().display_mode() {
+                Ok(mode) if mode.refresh_rate > 0 => mode.refresh_rate as u64,
+                _ => DEFAULT_REFRESH_RATE,
+            };
+            Duration::from_nanos(1_000_000_000 / refresh_rate)
+        })
+    }
+
+    /// Called by `UiState::present` after every frame.
+    pub fn record(&mut self, canvas: &WindowCanvas, info: &PresentInfo) {
+        let interval = match info.frame_interval {
+            Some(interval) => interval,
+            None => return,
+        };
+        let budget = self.budget(canvas);
+
+        let millis = interval.as_millis() as u64;
+        let bucket = FRAME_TIME_BUCKETS_MS.iter().position(|&bound| millis < bound).unwrap_or(FRAME_TIME_BUCKETS_MS.len());
+        self.times.histogram[bucket] += 1;
+        self.times.frames += 1;
+        if interval > self.times.worst {
+            self.times.worst = interval;
+        }
+
+        // frames shift around a little even when vsync is hit every time,
+        // so only a frame that is half a budget late counts as janky
+        if interval > budget + budget / 2 {
+            self.times.janky_frames += 1;
+            let budget_nanos = budget.as_nanos().max(1);
+            let missed = (interval.as_nanos() + budget_nanos / 2) / budget_nanos;
+            self.times.dropped_frames += missed.saturating_sub(1) as u64;
+
+            for callback in &mut self.jank_callbacks {
+                callback(info, budget);
+            }
+        }
+    }
+
+    fn take(&mut self) -> FrameTimes {
+        let mut times = mem::take(&mut self.times);
+        if let Some(budget) = self.budget_override.or(self.refresh_interval) {
+            times.budget = budget;
+        }
+        times
+    }
+}
+
+impl Sdl2Mt {
+    /// Returns a histogram of the time between frames presented with `present`, along with
+    /// how many frames went over the window's frame budget, for tuning the render loop.
+    /// Each call starts counting from zero again. Returns `Sdl2MtError::WindowNotFound` if
+    /// there is no window with that ID.
+    ///
+    /// This function executes synchronously.
+    pub fn frame_times(&self, window_id: WindowId) -> Result<FrameTimes, Sdl2MtError> {
+        self.call_builtin(move |_sdl, windows, state| {
+            if !windows.contains_key(&window_id) {
+                return None;
+            }
+            Some(state.window(window_id).frame_times.take())
+        })?.ok_or(Sdl2MtError::WindowNotFound(window_id))
+    }
+
+    /// Sets the time a frame of the window may take before it counts as janky. By default,
+    /// this is one refresh of the display the window was on when it presented its first frame.
+    /// `None` goes back to the default.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_frame_budget(&self, window_id: WindowId, budget: Option<Duration>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            if windows.contains_key(&window_id) {
+                state.window(window_id).frame_times.budget_override = budget;
+            } else {
+                trace::window_not_found(window_id);
+            }
+        })
+    }
+
+    /// Registers a callback that runs on the UI thread whenever a frame of the window,
+    /// presented with `present`, takes noticeably longer than the frame budget.
+    ///
+    /// The callbacks are dropped when the window is removed.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn on_jank(&self, window_id: WindowId, callback: Box<SdlJank>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            if windows.contains_key(&window_id) {
+                state.window(window_id).frame_times.jank_callbacks.push(callback);
+            } else {
+                trace::window_not_found(window_id);
+            }
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use pixels::PixelFormatEnum;
+use render::WindowCanvas;
+use trace;
+
+use std::sync::{Arc, Mutex};
+
+/// How many spare buffers a `FramePool` holds on to at most.
+const MAX_POOLED_FRAMES: usize = 8;
+
+/// A frame of raw pixels for `stream_frame`. The pixels are shared with the UI thread
+/// rather than copied, so a producer can keep its own reference to the frame.
+#[derive(Clone)]
+pub struct Frame {
+    pub pixels: Arc<[u8]>,
+    pub format: PixelFormatEnum,
+    pub width: u32,
+    pub height: u32,
+    /// the length of a row of pixels in bytes
+    pub pitch: usize,
+}
+
+/// Recycles frame buffers of a fixed size, so that streaming frames doesn't allocate once
+/// the pool is warmed up. The UI thread puts each frame's buffer back into the pool after
+/// uploading it; `acquire` hands out buffers nobody else holds a reference to anymore.
+///
+/// Cloning a `FramePool` is cheap, and every clone shares the same buffers.
+#[derive(Clone)]
+pub struct FramePool {
+    free: Arc<Mutex<Vec<Arc<[u8]>>>>,
+    frame_len: usize,
+}
+
+impl FramePool {
+    pub fn new(frame_len: usize) -> FramePool {
+        FramePool {
+            free: Arc::new(Mutex::new(vec![])),
+            frame_len,
+        }
+    }
+
+    /// Returns a buffer of the pool's frame length that the caller holds the only reference
+    /// to, so that `Arc::get_mut` always succeeds on it. Its contents are whatever the
+    /// previous frame left in it.
+    pub fn acquire(&self) -> Arc<[u8]> {
+        let mut free = self.free.lock().unwrap();
+        match free.iter().position(|buffer| Arc::strong_count(buffer) == 1 && Arc::weak_count(buffer) == 0) {
+            Some(index) => free.swap_remove(index),
+            None => vec![0; self.frame_len].into(),
+        }
+    }
+
+    /// Puts a buffer back into the pool, unless it has the wrong size or the pool is full.
+    pub fn release(&self, buffer: Arc<[u8]>) {
+        if buffer.len() != self.frame_len {
+            return;
+        }
+        let mut free = self.free.lock().unwrap();
+        if free.len() < MAX_POOLED_FRAMES {
+            free.push(buffer);
+        }
+    }
+}
+
+fn draw_frame(canvas: &mut WindowCanvas, frame: &Frame) -> Result<(), String> {
+    let creator = canvas.texture_creator();
+    let mut texture = creator.create_texture_streaming(frame.format, frame.width, frame.height).map_err(|error| error.to_string())?;
+    texture.update(None, &frame.pixels, frame.pitch).map_err(|error| error.to_string())?;
+    canvas.copy(&texture, None, None)
+}
+
+impl Sdl2Mt {
+    /// Uploads a frame of raw pixels and draws it over the window's whole canvas, stretched
+    /// to fit. The pixels are shared with the UI thread without copying them; once they have
+    /// been uploaded, the buffer goes back to `pool`, if one is given. The frame is not
+    /// presented, so that overlays can be drawn on top of it before calling `present`.
+    ///
+    /// Frames that don't match their own size and pitch, and frames for windows that don't
+    /// exist, are dropped.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn stream_frame(&self, window_id: WindowId, frame: Frame, pool: Option<&FramePool>) -> Result<(), Sdl2MtError> {
+        let pool = pool.cloned();
+        self.run_builtin(move |_sdl, windows, _state| {
+            let complete = frame.pixels.len() >= frame.pitch * frame.height as usize;
+            match windows.get_mut(&window_id) {
+                Some(canvas) if complete => {
+                    if let Err(error) = draw_frame(canvas, &frame) {
+                        trace::sdl_error("stream_frame", error);
+                    }
+                },
+                _ => {},
+            }
+
+            if let Some(pool) = pool {
+                pool.release(frame.pixels);
+            }
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use error;
+use pixels::PixelFormatEnum;
+use render::BlendMode;
+use surface::Surface;
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Set this environment variable to make `assert_window_matches` write the window's
+/// current contents as the new reference image, instead of comparing against it.
+pub const UPDATE_GOLDENS_VAR: &str = "SDL2_MT_UPDATE_GOLDENS";
+
+/// The contents of a window's canvas, read back with `Sdl2Mt::read_window_pixels`,
+/// as tightly packed rows of RGB24 pixels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WindowPixels {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl WindowPixels {
+    /// Returns the red, green and blue values of the pixel at `(x, y)`.
+    pub fn pixel(&self, x: u32, y: u32) -> (u8, u8, u8) {
+        let offset = (y * self.width + x) as usize * 3;
+        (self.data[offset], self.data[offset + 1], self.data[offset + 2])
+    }
+
+    /// Loads a BMP file, in whatever pixel format it was saved with.
+    pub fn load_bmp<P: AsRef<Path>>(path: P) -> Result<WindowPixels, String> {
+        let mut loaded = Surface::load_bmp(path)?;
+        let (width, height) = loaded.size();
+
+        // SDL converts between formats when blitting, and pads the rows of 24 bit surfaces
+        let mut converted = Surface::new(width, height, PixelFormatEnum::RGB24)?;
+        loaded.set_blend_mode(BlendMode::None)?;
+        loaded.blit(None, &mut converted, None)?;
+
+        let pitch = converted.pitch() as usize;
+        let row = width as usize * 3;
+        let data = converted.with_lock(|pixels| {
+            pixels.chunks(pitch).take(height as usize).flat_map(|line| line[..row].iter().cloned()).collect()
+        });
+        Ok(WindowPixels {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn save_bmp<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let mut data = self.data.clone();
+        let surface = Surface::from_data(&mut data, self.width, self.height, self.width * 3, PixelFormatEnum::RGB24)?;
+        surface.save_bmp(path)
+    }
+
+    /// Compares two images channel by channel. Returns how many pixels differ from `other`
+    /// by more than `tolerance` in any channel, or an error if the sizes don't match.
+    pub fn count_differences(&self, other: &WindowPixels, tolerance: u8) -> Result<usize, String> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(format!("the images have different sizes, {}x{} and {}x{}",
+                self.width, self.height, other.width, other.height));
+        }
+
+        Ok(self.data.chunks(3).zip(other.data.chunks(3)).filter(|&(a, b)| {
+            a.iter().zip(b).any(|(&a, &b)| (a as i16 - b as i16).abs() > tolerance as i16)
+        }).count())
+    }
+}
+
+/// Where a mismatching window's contents are written, next to the reference image.
+fn actual_path(reference_path: &Path) -> PathBuf {
+    let mut name = reference_path.file_stem().map(|stem| stem.to_os_string()).unwrap_or_default();
+    name.push(".actual.bmp");
+    reference_path.with_file_name(name)
+}
+
+impl Sdl2Mt {
+    /// Reads the current contents of a window's canvas. This is what has been drawn since the
+    /// last frame was presented, so read the pixels before calling `present`. Returns
+    /// `Sdl2MtError::WindowNotFound` if there is no window with that ID.
+    ///
+    /// This function executes synchronously.
+    pub fn read_window_pixels(&self, window_id: WindowId) -> Result<WindowPixels, Sdl2MtError> {
+        self.with_canvas(window_id, move |canvas| {
+            let (width, height) = canvas.output_size().map_err(|error| error::sdl_error("SDL_GetRendererOutputSize", error))?;
+            let data = canvas.read_pixels(None, PixelFormatEnum::RGB24).map_err(|error| error::sdl_error("SDL_RenderReadPixels", error))?;
+            Ok(WindowPixels {
+                width,
+                height,
+                data,
+            })
+        }).and_then(|result| result)
+    }
+
+    /// Compares the current contents of a window's canvas against a reference BMP image,
+    /// for regression tests of rendering code. A pixel matches if none of its channels differs
+    /// from the reference by more than `tolerance`. As with `read_window_pixels`, call this
+    /// before presenting the frame.
+    ///
+    /// When the `SDL2_MT_UPDATE_GOLDENS` environment variable is set, the window's contents are
+    /// written to `reference_path` instead, to create or update the reference image.
+    ///
+    /// This function executes synchronously.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window doesn't exist, its pixels can't be read, the reference image can't
+    /// be loaded, or any pixel doesn't match. On a mismatch, the window's contents are written
+    /// next to the reference image, as `<name>.actual.bmp`.
+    pub fn assert_window_matches<P: AsRef<Path>>(&self, window_id: WindowId, reference_path: P, tolerance: u8) {
+        let reference_path = reference_path.as_ref();
+        let actual = match self.read_window_pixels(window_id) {
+            Ok(actual) => actual,
+            Err(error) => panic!("sdl2_mt: could not read the pixels of window {}: {}", window_id, error),
+        };
+
+        if env::var_os(UPDATE_GOLDENS_VAR).is_some() {
+            if let Err(error) = actual.save_bmp(reference_path) {
+                panic!("sdl2_mt: could not write {}: {}", reference_path.display(), error);
+            }
+            return;
+        }
+
+        let reference = match WindowPixels::load_bmp(reference_path) {
+            Ok(reference) => reference,
+            Err(error) => panic!("sdl2_mt: could not load {}: {} (set {} to create it)",
+                reference_path.display(), error, UPDATE_GOLDENS_VAR),
+        };
+
+        let mismatch = match actual.count_differences(&reference, tolerance) {
+            Ok(0) => return,
+            Ok(count) => format!("{} of {} pixels differ by more than {}", count, actual.width * actual.height, tolerance),
+            Err(error) => error,
+        };
+        let actual_path = actual_path(reference_path);
+        let _ = actual.save_bmp(&actual_path);
+        panic!("sdl2_mt: window {} does not match {}: {}. Its contents were written to {}",
+            window_id, reference_path.display(), mismatch, actual_path.display());
+    }
+}
@@ -0,0 +1,91 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use stats::StatsCollector;
+
+/// Called on the UI thread when the queue or the unhandled events grow past their threshold.
+pub type SdlHighWater = dyn FnMut(HighWaterMark) + Send;
+
+/// Which threshold was crossed, and how far the queue or the unhandled events had grown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HighWaterMark {
+    /// the number of messages the UI thread found waiting when it woke up
+    QueueDepth(usize),
+    /// the number of events no event handler has taken yet
+    UnhandledEvents(usize),
+}
+
+/// A threshold, and whether the last check found it crossed.
+struct Threshold {
+    limit: usize,
+    crossed: bool,
+}
+
+impl Threshold {
+    /// Whether `value` just went past the limit. It has to fall back to the limit
+    /// or below before it is reported again, so that a backlog is reported once.
+    fn check(&mut self, value: usize) -> bool {
+        let crossed = value > self.limit;
+        let newly_crossed = crossed && !self.crossed;
+        self.crossed = crossed;
+        newly_crossed
+    }
+}
+
+struct Config {
+    queue_depth: Threshold,
+    unhandled_events: Threshold,
+    callback: Box<SdlHighWater>,
+}
+
+/// Watches how far the queue and the unhandled events grow, on the UI thread.
+pub struct HighWater {
+    config: Option<Config>,
+}
+
+impl HighWater {
+    pub fn new() -> HighWater {
+        HighWater { config: None }
+    }
+
+    /// Called by the UI thread after every batch of messages.
+    pub fn check(&mut self, stats: &StatsCollector, queue_depth: usize, unhandled_events: usize) {
+        stats.record_depths(queue_depth, unhandled_events);
+
+        if let Some(ref mut config) = self.config {
+            if config.queue_depth.check(queue_depth) {
+                (config.callback)(HighWaterMark::QueueDepth(queue_depth));
+            }
+            if config.unhandled_events.check(unhandled_events) {
+                (config.callback)(HighWaterMark::UnhandledEvents(unhandled_events));
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Calls `callback` on the UI thread when more than `queue_depth` messages are waiting
+    /// for it, or more than `unhandled_events` events are waiting for an event handler.
+    /// Either means the UI thread is falling behind, well before users notice stutter.
+    /// A threshold is reported again only after the backlog has gone back below it.
+    /// Replaces any previous thresholds.
+    ///
+    /// The highest values seen are always available from `stats()`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_high_water_marks(&self, queue_depth: usize, unhandled_events: usize, callback: Box<SdlHighWater>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            state.high_water.config = Some(Config {
+                queue_depth: Threshold { limit: queue_depth, crossed: false },
+                unhandled_events: Threshold { limit: unhandled_events, crossed: false },
+                callback,
+            });
+        })
+    }
+
+    /// Removes the thresholds set by `set_high_water_marks`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn clear_high_water_marks(&self) -> Result<(), Sdl2MtError> {
+        self.run_builtin(|_sdl, _windows, state| state.high_water.config = None)
+    }
+}
@@ -0,0 +1,100 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use render::WindowCanvas;
+use Sdl;
+use WindowId;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+type SdlIdle = dyn FnMut(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) -> bool + Send;
+
+/// How long the UI thread waits between idle passes, unless a message or event arrives
+/// first, so that registered idle callbacks don't keep a core busy.
+pub const IDLE_PAUSE: Duration = Duration::from_millis(1);
+
+/// Identifies an idle callback registered with `add_idle_callback`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdleId(usize);
+
+static NEXT_IDLE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Callbacks that run when the UI thread has nothing else to do.
+pub struct IdleCallbacks {
+    // rotated as callbacks run, so that each idle pass continues where the last one stopped
+    callbacks: VecDeque<(IdleId, Box<SdlIdle>)>,
+    budget: Duration,
+}
+
+impl IdleCallbacks {
+    pub fn new() -> IdleCallbacks {
+        IdleCallbacks {
+            callbacks: VecDeque::new(),
+            budget: Duration::from_millis(5),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Runs idle callbacks until each has run once or the time budget is used up.
+    /// At least one callback always runs, so a budget that is too small can't
+    /// starve them completely.
+    pub fn run(&mut self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>) {
+        let start = Instant::now();
+        for _ in 0..self.callbacks.len() {
+            let (id, mut callback) = match self.callbacks.pop_front() {
+                Some(entry) => entry,
+                None => break,
+            };
+
+            if callback(sdl, windows) {
+                self.callbacks.push_back((id, callback));
+            }
+
+            if start.elapsed() >= self.budget {
+                break;
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Registers a callback that the UI thread runs whenever both its message queue and
+    /// the SDL event queue are empty. Idle callbacks are meant for background maintenance
+    /// such as lazy texture uploads or trimming caches, and should do a small slice of work
+    /// each time they are called.
+    ///
+    /// The callback returns true to be called again on the next idle pass, or false to
+    /// unregister itself. While any idle callbacks are registered the UI thread does not
+    /// sleep, apart from a short pause between idle passes, so only keep returning true
+    /// while there is work left to do.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn add_idle_callback(&self, callback: Box<SdlIdle>) -> Result<IdleId, Sdl2MtError> {
+        let id = IdleId(NEXT_IDLE_ID.fetch_add(1, Ordering::Relaxed));
+        self.run_builtin(move |_sdl, _windows, state| state.idle.callbacks.push_back((id, callback)))?;
+        Ok(id)
+    }
+
+    /// Unregisters an idle callback. Unknown IDs are ignored.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn remove_idle_callback(&self, id: IdleId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| state.idle.callbacks.retain(|&(other, _)| other != id))
+    }
+
+    /// Sets how much time a single idle pass may spend running idle callbacks before
+    /// the UI thread checks for new messages again. The default is 5 milliseconds.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_idle_budget(&self, budget: Duration) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| state.idle.budget = budget)
+    }
+}
@@ -0,0 +1,158 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use event::Event;
+use get_error;
+use keyboard::{self, Keycode, Scancode};
+use lifecycle::LifecycleEvent;
+use mouse::{MouseButton, MouseState};
+use sys;
+use trace;
+use Sdl;
+use WindowId;
+
+/// The mouse as seen by injected events, so that injected motion carries relative
+/// movement and the buttons held down by earlier injected button events.
+#[derive(Default)]
+pub struct InjectedMouse {
+    position: (i32, i32),
+    buttons: u32,
+}
+
+fn button_mask(button: MouseButton) -> u32 {
+    match button {
+        MouseButton::Unknown => 0,
+        button => 1 << (button as u8 - 1),
+    }
+}
+
+pub fn timestamp() -> u32 {
+    unsafe { sys::timer::SDL_GetTicks() }
+}
+
+pub fn push_event(sdl: &Sdl, event: Event) {
+    let result = sdl.event().and_then(|events| events.push_event(event));
+    if let Err(error) = result {
+        trace::sdl_error("SDL_PushEvent", error);
+    }
+}
+
+impl Sdl2Mt {
+    /// Pushes a key press or release into SDL's event queue, where the event pump picks it
+    /// up like one from the keyboard. Together with the other `inject_*` functions, this lets
+    /// integration tests drive event handling code end to end.
+    ///
+    /// Injected events are pushed in the order they were submitted, relative to other messages.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn inject_key(&self, window_id: WindowId, keycode: Keycode, pressed: bool) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |sdl, _windows, _state| {
+            let scancode = Scancode::from_keycode(keycode);
+            let event = if pressed {
+                Event::KeyDown {
+                    timestamp: timestamp(),
+                    window_id: window_id.raw(),
+                    keycode: Some(keycode),
+                    scancode,
+                    keymod: keyboard::NOMOD,
+                    repeat: false,
+                }
+            } else {
+                Event::KeyUp {
+                    timestamp: timestamp(),
+                    window_id: window_id.raw(),
+                    keycode: Some(keycode),
+                    scancode,
+                    keymod: keyboard::NOMOD,
+                    repeat: false,
+                }
+            };
+            push_event(sdl, event);
+        })
+    }
+
+    /// Pushes a mouse motion event to `(x, y)` in window coordinates. The relative motion
+    /// is measured from the position of the previous injected mouse event.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn inject_mouse_move(&self, window_id: WindowId, x: i32, y: i32) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |sdl, _windows, state| {
+            let (last_x, last_y) = state.injected_mouse.position;
+            state.injected_mouse.position = (x, y);
+            push_event(sdl, Event::MouseMotion {
+                timestamp: timestamp(),
+                window_id: window_id.raw(),
+                which: 0,
+                mousestate: MouseState::from_sdl_state(state.injected_mouse.buttons),
+                x,
+                y,
+                xrel: x - last_x,
+                yrel: y - last_y,
+            });
+        })
+    }
+
+    /// Pushes a mouse button press or release at `(x, y)` in window coordinates.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn inject_mouse_button(&self, window_id: WindowId, button: MouseButton, pressed: bool, x: i32, y: i32) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |sdl, _windows, state| {
+            state.injected_mouse.position = (x, y);
+            let event = if pressed {
+                state.injected_mouse.buttons |= button_mask(button);
+                Event::MouseButtonDown {
+                    timestamp: timestamp(),
+                    window_id: window_id.raw(),
+                    which: 0,
+                    mouse_btn: button,
+                    x,
+                    y,
+                }
+            } else {
+                state.injected_mouse.buttons &= !button_mask(button);
+                Event::MouseButtonUp {
+                    timestamp: timestamp(),
+                    window_id: window_id.raw(),
+                    which: 0,
+                    mouse_btn: button,
+                    x,
+                    y,
+                }
+            };
+            push_event(sdl, event);
+        })
+    }
+
+    /// Pushes an application lifecycle event, as if the OS had sent it.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn inject_lifecycle(&self, lifecycle_event: LifecycleEvent) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, _state| {
+            // sdl2 can't push these, so the raw event is built here
+            let event_type = match lifecycle_event {
+                LifecycleEvent::Terminating => sys::event::SDL_APP_TERMINATING,
+                LifecycleEvent::LowMemory => sys::event::SDL_APP_LOWMEMORY,
+                LifecycleEvent::WillEnterBackground => sys::event::SDL_APP_WILLENTERBACKGROUND,
+                LifecycleEvent::DidEnterBackground => sys::event::SDL_APP_DIDENTERBACKGROUND,
+                LifecycleEvent::WillEnterForeground => sys::event::SDL_APP_WILLENTERFOREGROUND,
+                LifecycleEvent::DidEnterForeground => sys::event::SDL_APP_DIDENTERFOREGROUND,
+            };
+            let mut event = sys::event::SDL_Event { data: [0; 56] };
+            unsafe {
+                (*event.common()).type_ = event_type;
+                (*event.common()).timestamp = timestamp();
+                if sys::event::SDL_PushEvent(&mut event) < 0 {
+                    trace::sdl_error("SDL_PushEvent", get_error());
+                }
+            }
+        })
+    }
+
+    /// Pushes a quit event, as if the user had closed the last window.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn inject_quit(&self) -> Result<(), Sdl2MtError> {
+        self.run_builtin(|sdl, _windows, _state| {
+            push_event(sdl, Event::Quit { timestamp: timestamp() });
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use render::WindowCanvas;
+use state::UiState;
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::ptr;
+
+/// Closures of up to this many words are stored inline instead of on the heap.
+const INLINE_WORDS: usize = 4;
+
+type Storage = [usize; INLINE_WORDS];
+type BoxedFn = dyn FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &mut UiState) + Send;
+
+/// A function that runs once on the UI thread, stored without a heap allocation
+/// if it is small enough. Most closures sent to the UI thread capture little more
+/// than a window ID or a reply channel, so this saves an allocation per message.
+pub struct InlineFn {
+    repr: Repr,
+}
+
+enum Repr {
+    Inline {
+        storage: MaybeUninit<Storage>,
+        call: unsafe fn(*mut u8, &mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &mut UiState),
+        drop: unsafe fn(*mut u8),
+    },
+    Boxed(Box<BoxedFn>),
+}
+
+unsafe fn call_inline<F>(storage: *mut u8, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>, state: &mut UiState)
+    where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &mut UiState)
+{
+    let function = ptr::read(storage as *mut F);
+    function(sdl, windows, state)
+}
+
+unsafe fn drop_inline<F>(storage: *mut u8) {
+    ptr::drop_in_place(storage as *mut F)
+}
+
+impl InlineFn {
+    pub fn new<F>(function: F) -> InlineFn
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &mut UiState) + Send + 'static
+    {
+        let fits = mem::size_of::<F>() <= mem::size_of::<Storage>() &&
+                   mem::align_of::<F>() <= mem::align_of::<Storage>();
+        if !fits {
+            return InlineFn { repr: Repr::Boxed(Box::new(function)) };
+        }
+
+        let mut storage = MaybeUninit::<Storage>::uninit();
+        unsafe {
+            ptr::write(storage.as_mut_ptr() as *mut F, function);
+        }
+        InlineFn {
+            repr: Repr::Inline {
+                storage,
+                call: call_inline::<F>,
+                drop: drop_inline::<F>,
+            },
+        }
+    }
+
+    pub fn call(self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>, state: &mut UiState) {
+        // the function is moved out below, so it must not be dropped a second time
+        let mut this = ManuallyDrop::new(self);
+        match this.repr {
+            Repr::Inline { ref mut storage, call, .. } => unsafe {
+                call(storage.as_mut_ptr() as *mut u8, sdl, windows, state)
+            },
+            Repr::Boxed(ref mut function) => {
+                let function = unsafe { ptr::read(function) };
+                function(sdl, windows, state)
+            },
+        }
+    }
+}
+
+impl Drop for InlineFn {
+    fn drop(&mut self) {
+        if let Repr::Inline { ref mut storage, drop, .. } = self.repr {
+            unsafe { drop(storage.as_mut_ptr() as *mut u8) }
+        }
+    }
+}
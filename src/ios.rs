@@ -0,0 +1,58 @@
+use UiHandle;
+use WindowId;
+use get_error;
+use render::WindowCanvas;
+use sys::video::SDL_Window;
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+use std::time::Duration;
+
+extern "C" {
+    /// Part of every SDL 2 release for iOS, but missing from the bindings.
+    fn SDL_iPhoneSetAnimationCallback(window: *mut SDL_Window, interval: c_int, callback: Option<extern "C" fn(*mut c_void)>, param: *mut c_void) -> c_int;
+}
+
+thread_local! {
+    /// Windows with a frame queued by the display link that hasn't run yet, so that a busy
+    /// UI thread skips frames instead of working through a backlog of them.
+    static QUEUED_FRAMES: RefCell<HashSet<WindowId>> = RefCell::new(HashSet::new());
+}
+
+/// Called by UIKit's display link once per refresh, on the main thread while SDL pumps
+/// events. UIKit stops calling it while the application is in the background.
+extern "C" fn animation_frame(param: *mut c_void) {
+    let window_id = WindowId::from_raw(param as usize as u32);
+    let handle = match UiHandle::current() {
+        Some(handle) => handle,
+        None => return,
+    };
+    if !QUEUED_FRAMES.with(|queued| queued.borrow_mut().insert(window_id)) {
+        return;
+    }
+    let queued = handle.run_builtin(move |_sdl, windows, state| {
+        QUEUED_FRAMES.with(|queued| queued.borrow_mut().remove(&window_id));
+        state.run_display_link_frame(windows, window_id);
+    });
+    // a full queue skips the frame, like a late timer would
+    if queued.is_err() {
+        QUEUED_FRAMES.with(|queued| queued.borrow_mut().remove(&window_id));
+    }
+}
+
+/// Runs the window's scene frames from UIKit's display link, as close to every `interval`
+/// as whole refreshes of a 60 Hz display get. Returns SDL's error if it can't.
+pub fn start_frames(canvas: &WindowCanvas, window_id: WindowId, interval: Duration) -> Result<(), String> {
+    let refreshes = (interval.as_secs_f64() * 60.0).round().max(1.0) as c_int;
+    let param = window_id.raw() as usize as *mut c_void;
+    match unsafe { SDL_iPhoneSetAnimationCallback(canvas.window().raw(), refreshes, Some(animation_frame), param) } {
+        0 => Ok(()),
+        _ => Err(get_error()),
+    }
+}
+
+pub fn stop_frames(canvas: &WindowCanvas) {
+    unsafe { SDL_iPhoneSetAnimationCallback(canvas.window().raw(), 1, None, ptr::null_mut()) };
+}
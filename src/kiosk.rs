@@ -0,0 +1,197 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use error;
+use event::{Event, WindowEvent};
+use ffi;
+use rect::Rect;
+use render::WindowCanvas;
+use state::UiState;
+use trace;
+use VideoSubsystem;
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+
+/// How `create_kiosk_windows` should cover the connected displays.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KioskLayout {
+    /// One borderless, fullscreen window per display.
+    PerDisplay,
+    /// A single borderless window stretched over the bounding box of all displays.
+    Spanning,
+}
+
+/// The group of windows managed by kiosk mode.
+pub struct KioskGroup {
+    title: String,
+    layout: KioskLayout,
+    display_bounds: Vec<Rect>,
+    windows: Vec<WindowId>,
+    /// set when an event hints that the displays changed since `display_bounds` was read
+    displays_changed: bool,
+}
+
+fn current_display_bounds(video: &VideoSubsystem) -> Vec<Rect> {
+    let count = video.num_video_displays().unwrap_or(0);
+    (0..count).filter_map(|index| video.display_bounds(index).ok()).collect()
+}
+
+fn build_kiosk_window(video: &VideoSubsystem, title: &str, bounds: Rect, fullscreen: bool) -> Result<WindowCanvas, String> {
+    let mut builder = video.window(title, bounds.width(), bounds.height());
+    builder.position(bounds.x(), bounds.y()).borderless();
+    if fullscreen {
+        // fullscreen-desktop windows go fullscreen on whichever display they are positioned on
+        builder.fullscreen_desktop();
+    }
+
+    let window = builder.build().map_err(|error| error.to_string())?;
+    window.into_canvas().software().build().map_err(|error| error.to_string())
+}
+
+impl KioskGroup {
+    /// Destroys the windows of this group and creates new ones matching the current displays.
+    /// Windows that can't be created are left out, and the first error is returned.
+    fn rebuild(&mut self, video: &VideoSubsystem, canvases: &mut HashMap<WindowId, WindowCanvas>) -> Result<(), String> {
+        for id in self.windows.drain(..) {
+            canvases.remove(&id);
+        }
+
+        self.display_bounds = current_display_bounds(video);
+        let window_bounds = match self.layout {
+            KioskLayout::PerDisplay => self.display_bounds.clone(),
+            KioskLayout::Spanning => {
+                let mut bounds = self.display_bounds.iter();
+                match bounds.next() {
+                    Some(first) => vec![bounds.fold(*first, |acc, rect| acc.union(*rect))],
+                    None => vec![],
+                }
+            }
+        };
+
+        let fullscreen = self.layout == KioskLayout::PerDisplay;
+        let mut result = Ok(());
+        for bounds in window_bounds {
+            match build_kiosk_window(video, &self.title, bounds, fullscreen) {
+                Ok(canvas) => {
+                    let id = WindowId::from_raw(canvas.window().id());
+                    canvases.insert(id, canvas);
+                    self.windows.push(id);
+                },
+                Err(error) => {
+                    if result.is_ok() {
+                        result = Err(error);
+                    }
+                },
+            }
+        }
+        result
+    }
+}
+
+impl UiState {
+    /// Notes that the displays may have changed. SDL reports this with display events, and
+    /// versions before 2.0.9 only by moving or resizing the fullscreen kiosk windows.
+    pub fn observe_kiosk(&mut self, event: &Event) {
+        let kiosk = match self.kiosk {
+            Some(ref mut kiosk) => kiosk,
+            None => return,
+        };
+        match *event {
+            Event::Unknown { type_, .. } if type_ == ffi::SDL_DISPLAYEVENT => kiosk.displays_changed = true,
+            Event::Window { window_id, win_event: WindowEvent::Moved(..), .. } |
+            Event::Window { window_id, win_event: WindowEvent::SizeChanged(..), .. }
+                if kiosk.windows.contains(&WindowId::from_raw(window_id)) => {
+                    kiosk.displays_changed = true;
+                },
+            _ => {},
+        }
+    }
+
+    /// Display hotplug is detected by comparing the current display layout to
+    /// the one the kiosk windows were created for, once `observe_kiosk` has seen
+    /// an event that hints at a change. This is called before every event poll,
+    /// so enumerating the displays is skipped unless there was such an event.
+    pub fn sync_kiosk(&mut self, sdl: &mut Sdl, canvases: &mut HashMap<WindowId, WindowCanvas>) {
+        let kiosk = match self.kiosk {
+            Some(ref mut kiosk) if kiosk.displays_changed => kiosk,
+            _ => return,
+        };
+        kiosk.displays_changed = false;
+
+        let video = match sdl.video() {
+            Ok(video) => video,
+            Err(_) => return,
+        };
+
+        if current_display_bounds(&video) != kiosk.display_bounds {
+            if let Err(error) = kiosk.rebuild(&video, canvases) {
+                trace::sdl_error("rebuilding the kiosk windows", error);
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Creates borderless windows covering every connected display, as is common for
+    /// digital signage. The windows are managed as a group: whenever a display is
+    /// connected, disconnected or changes resolution, the group is recreated to match.
+    /// Any previous kiosk group is destroyed first.
+    ///
+    /// Because windows are recreated on display changes, the IDs returned here can become
+    /// stale. Use `kiosk_windows()` to get the current set.
+    ///
+    /// If SDL fails to create any of the windows, the others are still created and kept
+    /// in the group, and `Sdl2MtError::SdlError` is returned; `kiosk_windows()` still
+    /// returns the windows that were created.
+    ///
+    /// This function executes synchronously. It will block until the windows have been created.
+    pub fn create_kiosk_windows<IntoString: Into<String>>(&self, title: IntoString, layout: KioskLayout) -> Result<Vec<WindowId>, Sdl2MtError> {
+        let title = title.into();
+        self.call_builtin(move |sdl, windows, state| {
+            let mut kiosk = KioskGroup {
+                title,
+                layout,
+                display_bounds: vec![],
+                windows: vec![],
+                displays_changed: false,
+            };
+
+            if let Some(mut old) = state.kiosk.take() {
+                for id in old.windows.drain(..) {
+                    windows.remove(&id);
+                }
+            }
+
+            let result = sdl.video().and_then(|video| kiosk.rebuild(&video, windows));
+            let ids = kiosk.windows.clone();
+            state.kiosk = Some(kiosk);
+            result.map(|()| ids).map_err(|error| error::sdl_error("create_kiosk_windows", error))
+        }).and_then(|result| result)
+    }
+
+    /// Returns the IDs of the windows currently in the kiosk group, if there is one.
+    ///
+    /// This function executes synchronously.
+    pub fn kiosk_windows(&self) -> Result<Vec<WindowId>, Sdl2MtError> {
+        self.call_builtin(|_sdl, windows, state| {
+            match state.kiosk {
+                Some(ref kiosk) => kiosk.windows.iter().cloned().filter(|id| windows.contains_key(id)).collect(),
+                None => vec![],
+            }
+        })
+    }
+
+    /// Closes every window in the kiosk group and stops tracking display changes.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn close_kiosk_windows(&self) -> Result<(), Sdl2MtError> {
+        self.run_builtin(|_sdl, windows, state| {
+            if let Some(kiosk) = state.kiosk.take() {
+                for id in kiosk.windows {
+                    windows.remove(&id);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,140 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use event::Event;
+use sys;
+
+use std::time::Duration;
+
+/// Called on the UI thread for every event that was handled later than the latency budget allows.
+pub type SdlLatencyWarning = dyn FnMut(&Event, Duration) + Send;
+
+/// Returns the time at which SDL received an event, in milliseconds since SDL was initialized.
+pub fn event_timestamp(event: &Event) -> u32 {
+    use event::Event::*;
+    match *event {
+        Quit { timestamp } |
+        AppTerminating { timestamp } |
+        AppLowMemory { timestamp } |
+        AppWillEnterBackground { timestamp } |
+        AppDidEnterBackground { timestamp } |
+        AppWillEnterForeground { timestamp } |
+        AppDidEnterForeground { timestamp } |
+        Window { timestamp, .. } |
+        KeyDown { timestamp, .. } |
+        KeyUp { timestamp, .. } |
+        TextEditing { timestamp, .. } |
+        TextInput { timestamp, .. } |
+        MouseMotion { timestamp, .. } |
+        MouseButtonDown { timestamp, .. } |
+        MouseButtonUp { timestamp, .. } |
+        MouseWheel { timestamp, .. } |
+        JoyAxisMotion { timestamp, .. } |
+        JoyBallMotion { timestamp, .. } |
+        JoyHatMotion { timestamp, .. } |
+        JoyButtonDown { timestamp, .. } |
+        JoyButtonUp { timestamp, .. } |
+        JoyDeviceAdded { timestamp, .. } |
+        JoyDeviceRemoved { timestamp, .. } |
+        ControllerAxisMotion { timestamp, .. } |
+        ControllerButtonDown { timestamp, .. } |
+        ControllerButtonUp { timestamp, .. } |
+        ControllerDeviceAdded { timestamp, .. } |
+        ControllerDeviceRemoved { timestamp, .. } |
+        ControllerDeviceRemapped { timestamp, .. } |
+        FingerDown { timestamp, .. } |
+        FingerUp { timestamp, .. } |
+        FingerMotion { timestamp, .. } |
+        DollarGesture { timestamp, .. } |
+        DollarRecord { timestamp, .. } |
+        MultiGesture { timestamp, .. } |
+        ClipboardUpdate { timestamp } |
+        DropFile { timestamp, .. } |
+        User { timestamp, .. } |
+        Unknown { timestamp, .. } => timestamp,
+    }
+}
+
+/// Percentiles of the time between SDL receiving an event and an event handler or
+/// forwarder handling it, over the events handled since the previous call to `stats()`.
+/// SDL timestamps events in whole milliseconds, so these are only accurate to a millisecond.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EventLatency {
+    pub events_handled: u64,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl EventLatency {
+    /// Computes the percentiles of latencies in milliseconds, sorting them in the process.
+    pub fn from_samples(events_handled: u64, samples: &mut [u32]) -> EventLatency {
+        if samples.is_empty() {
+            return EventLatency { events_handled, ..EventLatency::default() };
+        }
+
+        samples.sort();
+        let percentile = |p: usize| Duration::from_millis(samples[(samples.len() - 1) * p / 100] as u64);
+        EventLatency {
+            events_handled,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: percentile(100),
+        }
+    }
+}
+
+/// Measures event latency on the UI thread. Samples are collected here and handed
+/// over to the statistics in bulk, so that handling an event never takes a lock.
+pub struct LatencyTracker {
+    pub samples: Vec<u32>,
+    budget: Option<(Duration, Box<SdlLatencyWarning>)>,
+}
+
+impl LatencyTracker {
+    pub fn new() -> LatencyTracker {
+        LatencyTracker {
+            samples: vec![],
+            budget: None,
+        }
+    }
+
+    /// Called whenever an event has been handled.
+    pub fn record(&mut self, event: &Event) {
+        let now = unsafe { sys::timer::SDL_GetTicks() };
+        // the tick counter wraps around after 49 days
+        let latency = now.wrapping_sub(event_timestamp(event));
+        self.samples.push(latency);
+
+        if let Some((budget, ref mut warning)) = self.budget {
+            let latency = Duration::from_millis(latency as u64);
+            if latency > budget {
+                warning(event, latency);
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Calls `warning` on the UI thread for every event that is handled more than `budget`
+    /// after SDL received it, along with how late it was. Latency is measured when an event
+    /// handler or forwarder handles the event; events that are never handled are not counted.
+    /// Replaces any previous budget.
+    ///
+    /// Percentiles of the latency are always available from `stats()`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_event_latency_budget(&self, budget: Duration, warning: Box<SdlLatencyWarning>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            state.latency.budget = Some((budget, warning));
+        })
+    }
+
+    /// Removes the budget set by `set_event_latency_budget`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn clear_event_latency_budget(&self) -> Result<(), Sdl2MtError> {
+        self.run_builtin(|_sdl, _windows, state| state.latency.budget = None)
+    }
+}
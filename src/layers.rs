@@ -0,0 +1,131 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use pixels::Color;
+use render::{BlendMode, WindowCanvas};
+use render_target::RenderTarget;
+use state::UiState;
+use sys;
+use trace;
+
+/// The layers of a window, drawn from the bottom up when the window is presented.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Layer {
+    Background,
+    Content,
+    Overlay,
+}
+
+impl Layer {
+    fn index(self) -> usize {
+        match self {
+            Layer::Background => 0,
+            Layer::Content => 1,
+            Layer::Overlay => 2,
+        }
+    }
+}
+
+/// The textures behind a window's layers, kept in its `WindowState` once a layer has been
+/// drawn to. Each texture has the size of the window's drawable area.
+#[derive(Default)]
+pub struct Layers {
+    textures: [Option<RenderTarget>; 3],
+}
+
+impl Layers {
+    /// The texture of a layer, created empty if it is missing or the window has been resized.
+    fn texture(&mut self, canvas: &mut WindowCanvas, previous: *mut sys::render::SDL_Texture, layer: Layer) -> Result<&RenderTarget, String> {
+        let (width, height) = canvas.output_size()?;
+        let slot = &mut self.textures[layer.index()];
+        let stale = match *slot {
+            Some(ref texture) => (texture.width, texture.height) != (width, height),
+            None => true,
+        };
+        if stale {
+            let texture = RenderTarget::new(canvas, width, height)?;
+            texture.with_texture(|texture| texture.set_blend_mode(BlendMode::Blend));
+            texture.draw(canvas, previous, |canvas| {
+                canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                canvas.clear();
+            })?;
+            *slot = Some(texture);
+        }
+        Ok(slot.as_ref().unwrap())
+    }
+}
+
+impl UiState {
+    /// Draws into one of the window's layers, which keeps what is drawn until it is cleared.
+    fn draw_layer<F>(&mut self, canvas: &mut WindowCanvas, window_id: WindowId, layer: Layer, draw: F) -> Result<(), String>
+        where F: FnOnce(&mut WindowCanvas)
+    {
+        let previous = self.active_target_raw(window_id);
+        let layers = self.window(window_id).layers.get_or_insert_with(Layers::default);
+        let texture = layers.texture(canvas, previous, layer)?;
+        texture.draw(canvas, previous, draw)
+    }
+
+    /// Draws the window's layers onto it, right before it is presented.
+    pub fn composite_layers(&mut self, canvas: &mut WindowCanvas, window_id: WindowId) {
+        let layers = match self.windows.get(&window_id).and_then(|settings| settings.layers.as_ref()) {
+            Some(layers) => layers,
+            None => return,
+        };
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+        for texture in layers.textures.iter().filter_map(|texture| texture.as_ref()) {
+            if let Err(error) = texture.with_texture(|texture| canvas.copy(texture, None, None)) {
+                trace::sdl_error("composite layers", error);
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Draws into one of the window's layers. Layers keep their contents from frame to
+    /// frame, and `present` draws them over each other, background first, so that e.g. an
+    /// overlay thread can update a HUD without redrawing, or fighting over the canvas with,
+    /// the thread that renders the content. What is drawn into a layer only shows up
+    /// where it isn't transparent; layers start out fully transparent.
+    ///
+    /// Once a window has layers, `present` replaces whatever was drawn to the window
+    /// directly with the layers. Layers are cleared when the window is resized.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn draw_layer<F>(&self, window_id: WindowId, layer: Layer, draw: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut WindowCanvas) + Send + 'static
+    {
+        self.run_builtin(move |_sdl, windows, state| {
+            if let Some(canvas) = windows.get_mut(&window_id) {
+                if let Err(error) = state.draw_layer(canvas, window_id, layer, draw) {
+                    trace::sdl_error("draw_layer", error);
+                }
+            } else {
+                trace::window_not_found(window_id);
+            }
+        })
+    }
+
+    /// Makes one of the window's layers fully transparent.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn clear_layer(&self, window_id: WindowId, layer: Layer) -> Result<(), Sdl2MtError> {
+        self.draw_layer(window_id, layer, |canvas| {
+            canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+            canvas.clear();
+        })
+    }
+
+    /// Throws away the window's layers, so that `present` shows what was drawn to the
+    /// window directly again.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn remove_layers(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            if let Some(settings) = state.windows.get_mut(&window_id) {
+                settings.layers = None;
+            }
+        })
+    }
+}
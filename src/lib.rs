@@ -1,142 +1,633 @@
-#[macro_use]
-extern crate lazy_static;
+#[cfg(feature = "sdl")]
 extern crate sdl2;
+#[cfg(feature = "crossbeam-channel")]
+#[macro_use]
+extern crate crossbeam_channel;
+#[cfg(feature = "async")]
+extern crate futures_core;
+#[cfg(feature = "signals")]
+extern crate ctrlc;
+#[cfg(feature = "animated-images")]
+extern crate gif;
+#[cfg(feature = "animated-images")]
+extern crate png;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "tracing")]
+#[macro_use]
+extern crate tracing;
+// renamed, so that it doesn't hide sdl2's own `log` module
+#[cfg(feature = "log")]
+extern crate log as log_crate;
+#[cfg(feature = "sdl")]
 pub use sdl2::*;
+#[cfg(feature = "sdl")]
 use event::Event;
 
-use std::collections::{HashMap, LinkedList};
+#[cfg(feature = "sdl")]
+use std::cell::Cell;
+#[cfg(feature = "sdl")]
+use std::collections::HashMap;
+#[cfg(feature = "sdl")]
+use std::env;
+#[cfg(feature = "sdl")]
+use std::fmt;
+#[cfg(feature = "sdl")]
 use std::sync::{Arc, Mutex, mpsc};
+#[cfg(feature = "sdl")]
 use std::thread;
-use std::thread::sleep;
-use std::time::Duration;
+#[cfg(feature = "sdl")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "sdl")]
+type SdlLambda = dyn FnMut(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>) + Send;
+#[cfg(feature = "sdl")]
+type SdlCreateWindow = dyn FnMut(&mut Sdl, &mut VideoSubsystem) -> Option<render::WindowCanvas> + Send;
+#[cfg(feature = "sdl")]
+type SdlHandleEvent = dyn FnMut(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>, &Event) -> bool + Send;
+#[cfg(feature = "sdl")]
+type SdlBuiltin = dyn FnMut(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>, &mut UiState) + Send;
+
+#[cfg(feature = "sdl")]
+mod animation;
+#[cfg(all(feature = "sdl", feature = "async"))]
+mod async_api;
+#[cfg(feature = "sdl")]
+mod auto_pause;
+#[cfg(feature = "sdl")]
+mod av_clock;
+#[cfg(feature = "sdl")]
+mod camera;
+#[cfg(feature = "sdl")]
+mod clock;
+#[cfg(feature = "sdl")]
+mod coalesce;
+#[cfg(feature = "sdl")]
+mod completion;
+#[cfg(feature = "sdl")]
+mod component;
+#[cfg(feature = "sdl")]
+#[macro_use]
+mod context;
+#[cfg(all(feature = "sdl", feature = "crossbeam-channel"))]
+mod crossbeam_queue;
+#[cfg(feature = "sdl")]
+mod dispatch;
+#[cfg(feature = "sdl")]
+mod dispatcher;
+#[cfg(feature = "sdl")]
+mod dpi;
+#[cfg(feature = "sdl")]
+mod draw;
+#[cfg(feature = "sdl")]
+mod dump;
+#[cfg(all(feature = "sdl", target_os = "emscripten"))]
+mod emscripten;
+mod error;
+#[cfg(feature = "sdl")]
+mod failure;
+#[cfg(feature = "fake-backend")]
+mod fake;
+#[cfg(feature = "sdl")]
+mod faults;
+#[cfg(feature = "sdl")]
+mod ffi;
+#[cfg(feature = "sdl")]
+mod font;
+#[cfg(feature = "sdl")]
+mod frame_times;
+#[cfg(feature = "sdl")]
+mod frames;
+#[cfg(feature = "sdl")]
+mod golden;
+#[cfg(feature = "sdl")]
+mod high_water;
+#[cfg(feature = "sdl")]
+mod idle;
+#[cfg(feature = "sdl")]
+mod inject;
+#[cfg(feature = "sdl")]
+mod inline_fn;
+#[cfg(all(feature = "sdl", target_os = "ios"))]
+mod ios;
+#[cfg(feature = "sdl")]
+mod kiosk;
+#[cfg(feature = "sdl")]
+mod latency;
+#[cfg(feature = "sdl")]
+mod layers;
+#[cfg(feature = "sdl")]
+mod lifecycle;
+#[cfg(feature = "sdl")]
+mod native_handle;
+#[cfg(feature = "sdl")]
+mod plot;
+#[cfg(feature = "sdl")]
+pub mod prelude;
+#[cfg(feature = "sdl")]
+mod present;
+#[cfg(feature = "sdl")]
+mod progress;
+#[cfg(feature = "sdl")]
+mod queue;
+#[cfg(feature = "sdl")]
+mod rate_limit;
+#[cfg(feature = "sdl")]
+mod raw_pointers;
+#[cfg(feature = "sdl")]
+mod render_target;
+#[cfg(feature = "sdl")]
+mod reply;
+#[cfg(feature = "sdl")]
+mod run_loop;
+#[cfg(feature = "sdl")]
+mod satellites;
+#[cfg(feature = "sdl")]
+mod scene;
+#[cfg(feature = "sdl")]
+mod script;
+#[cfg(all(feature = "sdl", feature = "signals"))]
+mod signals;
+#[cfg(feature = "sdl")]
+mod simple_window;
+#[cfg(feature = "sdl")]
+mod single_instance;
+#[cfg(feature = "sdl")]
+mod splash;
+#[cfg(feature = "sdl")]
+mod spy;
+#[cfg(all(feature = "sdl", feature = "log"))]
+mod sdl_log;
+#[cfg(feature = "sdl")]
+mod state;
+#[cfg(feature = "sdl")]
+mod stats;
+#[cfg(feature = "sdl")]
+mod submitter;
+#[cfg(feature = "sdl")]
+mod subtitles;
+#[cfg(feature = "sdl")]
+mod synchronous;
+#[cfg(feature = "sdl")]
+mod task;
+#[cfg(feature = "sdl")]
+mod text_grid;
+#[cfg(feature = "sdl")]
+mod ticks;
+#[cfg(feature = "sdl")]
+mod tiling;
+#[cfg(feature = "sdl")]
+mod timers;
+#[cfg(feature = "sdl")]
+mod trace;
+#[cfg(feature = "sdl")]
+mod ui_handle;
+#[cfg(feature = "sdl")]
+mod ui_panic;
+#[cfg(all(feature = "sdl", feature = "tokio"))]
+mod tokio_bridge;
+#[cfg(feature = "sdl")]
+mod viewport;
+#[cfg(feature = "sdl")]
+mod wakeup;
+#[cfg(feature = "sdl")]
+mod watchdog;
+#[cfg(feature = "sdl")]
+mod window;
+mod window_id;
+#[cfg(feature = "sdl")]
+mod window_profile;
+#[cfg(feature = "sdl")]
+mod workers;
 
-type SdlLambda = FnMut(&mut Sdl, &mut HashMap<u32, render::WindowCanvas>) + Send;
-type SdlCreateWindow = FnMut(&mut Sdl, &mut VideoSubsystem) -> Option<render::WindowCanvas> + Send;
-type SdlHandleEvent = FnMut(&mut Sdl, &mut HashMap<u32, render::WindowCanvas>, &Event) -> bool + Send;
+#[cfg(feature = "sdl")]
+pub use animation::{Animation, AnimationFrame, AnimationHandle};
+#[cfg(all(feature = "sdl", feature = "async"))]
+pub use async_api::{EventStream, UiFuture};
+#[cfg(feature = "sdl")]
+pub use auto_pause::AutoPause;
+#[cfg(feature = "sdl")]
+pub use av_clock::{AvClock, DEFAULT_MAX_FRAME_LATENESS};
+#[cfg(feature = "sdl")]
+pub use camera::Camera;
+#[cfg(feature = "sdl")]
+pub use clock::TestClock;
+#[cfg(feature = "sdl")]
+pub use completion::{CallbackOn, CompletionQueue};
+#[cfg(feature = "sdl")]
+pub use context::{UiContext, UiOutcome};
+#[cfg(feature = "sdl")]
+pub use dispatch::{EventDispatch, SendEvent};
+#[cfg(feature = "sdl")]
+pub use dispatcher::{MockCall, MockDispatcher, UiDispatcher};
+#[cfg(feature = "sdl")]
+pub use dpi::{DpiAwareness, ScaleChanged};
+#[cfg(feature = "sdl")]
+pub use dump::{StateDump, WindowDump};
+pub use error::Sdl2MtError;
+#[cfg(feature = "sdl")]
+pub use error::last_error;
+#[cfg(feature = "sdl")]
+pub use failure::FailurePolicy;
+#[cfg(feature = "fake-backend")]
+pub use fake::{DrawCommand, FakeEvent, FakeUi, FakeWindow, Rgba};
+#[cfg(feature = "sdl")]
+pub use faults::QueueSaturation;
+#[cfg(feature = "sdl")]
+pub use font::{GLYPH_SIZE, draw_text, text_size};
+#[cfg(feature = "sdl")]
+pub use frame_times::{FRAME_TIME_BUCKETS_MS, FrameTimes};
+#[cfg(feature = "sdl")]
+pub use frames::{Frame, FramePool};
+#[cfg(feature = "sdl")]
+pub use golden::{UPDATE_GOLDENS_VAR, WindowPixels};
+#[cfg(feature = "sdl")]
+pub use high_water::HighWaterMark;
+#[cfg(feature = "sdl")]
+pub use idle::IdleId;
+#[cfg(feature = "sdl")]
+pub use kiosk::KioskLayout;
+#[cfg(feature = "sdl")]
+pub use latency::EventLatency;
+#[cfg(feature = "sdl")]
+pub use layers::Layer;
+#[cfg(feature = "sdl")]
+pub use lifecycle::LifecycleEvent;
+#[cfg(feature = "sdl")]
+pub use native_handle::NativeHandle;
+#[cfg(feature = "sdl")]
+pub use plot::{AxesConfig, draw_line_series};
+#[cfg(feature = "sdl")]
+pub use present::PresentInfo;
+#[cfg(feature = "sdl")]
+pub use progress::{Progress, ProgressReporter, draw_progress_bar};
+#[cfg(feature = "sdl")]
+pub use queue::Backpressure;
+#[cfg(feature = "sdl")]
+pub use raw_pointers::RawSdlPointers;
+#[cfg(feature = "sdl")]
+pub use render_target::TargetId;
+#[cfg(feature = "sdl")]
+pub use run_loop::LoopControl;
+#[cfg(feature = "sdl")]
+pub use scene::{DEFAULT_SCENE_FRAME_INTERVAL, Scene, SceneAction};
+#[cfg(feature = "sdl")]
+pub use script::Script;
+#[cfg(feature = "sdl")]
+pub use simple_window::SimpleWindowOptions;
+#[cfg(feature = "sdl")]
+pub use single_instance::SingleInstance;
+#[cfg(feature = "sdl")]
+pub use splash::Splash;
+#[cfg(feature = "sdl")]
+pub use spy::{HandleId, SpiedMessage};
+#[cfg(feature = "sdl")]
+pub use stats::Sdl2MtStats;
+#[cfg(feature = "sdl")]
+pub use submitter::Submitter;
+#[cfg(feature = "sdl")]
+pub use subtitles::{Cue, CuePosition, CueStyle, Subtitles};
+#[cfg(feature = "sdl")]
+pub use task::{TaskState, TaskToken};
+#[cfg(feature = "sdl")]
+pub use text_grid::{GridCell, TextGrid};
+#[cfg(feature = "sdl")]
+pub use tiling::{Layout, layout_cells};
+#[cfg(feature = "sdl")]
+pub use timers::TimerId;
+#[cfg(feature = "sdl")]
+pub use ui_handle::UiHandle;
+#[cfg(feature = "sdl")]
+pub use watchdog::{SlowWork, WorkKind};
+pub use window_id::WindowId;
+#[cfg(feature = "sdl")]
+pub use window_profile::WindowProfile;
+#[cfg(feature = "sdl")]
+pub use workers::JobHandle;
+#[cfg(feature = "sdl")]
+use clock::Clock;
+#[cfg(feature = "sdl")]
+use inline_fn::InlineFn;
+#[cfg(feature = "sdl")]
+use queue::{MessageQueue, Popped};
+#[cfg(feature = "sdl")]
+use reply::{ReplyChannel, ReplySender};
+#[cfg(feature = "sdl")]
+use spy::{MessageSpy, OwnId};
+#[cfg(feature = "sdl")]
+use state::UiState;
+#[cfg(feature = "sdl")]
+use ticks::FrameCounter;
+#[cfg(feature = "sdl")]
+use trace::Queued;
+#[cfg(feature = "sdl")]
+use ui_panic::UiPanic;
+#[cfg(feature = "sdl")]
+use stats::StatsCollector;
+#[cfg(feature = "sdl")]
+use watchdog::Watchdog;
+#[cfg(feature = "sdl")]
+use workers::WorkerPool;
 
+#[cfg(feature = "sdl")]
 pub enum Sdl2Message {
     Lambda(Box<SdlLambda>),
-    CreateWindow(Box<SdlCreateWindow>, mpsc::Sender<Option<u32>>),
-    HandleEvent(Box<SdlHandleEvent>, mpsc::Sender<()>),
+    CreateWindow(Box<SdlCreateWindow>, ReplySender<Option<WindowId>>),
+    HandleEvent(Box<SdlHandleEvent>, ReplySender<()>),
+
+    /// Used by the helpers in this crate that need access to the bookkeeping
+    /// the UI thread keeps alongside the windows.
+    #[doc(hidden)]
+    Builtin(Box<SdlBuiltin>),
+
+    /// Like `Builtin`, but only runs once, and small functions are stored without boxing them.
+    #[doc(hidden)]
+    Inline(InlineFn),
+
+    /// Sent by `Sdl2Mt::present`, which usually runs once per frame, as plain data.
+    #[doc(hidden)]
+    Present(WindowId),
+
+    /// Sent by `Sdl2Mt::clear`, as plain data.
+    #[doc(hidden)]
+    Clear(WindowId, pixels::Color),
+
+    /// Sent by `Sdl2Mt::present_color`, as plain data.
+    #[doc(hidden)]
+    PresentColor(WindowId, pixels::Color),
+
+    /// Tells the UI thread that a `Submitter` has lambdas waiting.
+    #[doc(hidden)]
+    DrainSubmitters,
     Exit
 }
 
+/// The order in which the UI thread picks up messages. High priority messages
+/// run before any normal priority message that is still waiting in the queue.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Priority {
+    High,
+    Normal,
+}
+
+#[cfg(feature = "sdl")]
 use Sdl2Message::*;
 
-fn sdl_handler(rx: mpsc::Receiver<Sdl2Message>) {
-
-    // initialization of the library should be the only possible time we panic.
-    let mut sdl_context = sdl2::init().unwrap();
-    let mut video = sdl_context.video().unwrap();
-    let mut events = sdl_context.event_pump().unwrap();
-
-    let mut windows = HashMap::new();
-    let mut unhandled_events = LinkedList::new(); // really, we need to drop old events at some point
-    for message in rx {
-        match message {
-            // Lambda is used for simple, asynchronous blocks of code that need to be run on
-            // the UI thread. This does not block the calling thread, so no tx sync is used.
-            Lambda(mut lambda) => lambda(&mut sdl_context, &mut windows),
-
-            CreateWindow(mut create_window, tx) => {
-                let window_id;
-                if let Some(canvas) = create_window(&mut sdl_context, &mut video) {
-                    let id = canvas.window().id();
-                    windows.insert(id, canvas);
-                    window_id = Some(id);
-                } else {
-                    window_id = None;
-                }
+#[cfg(feature = "sdl")]
+thread_local! {
+    /// Set on the UI thread, so that synchronous calls made from it fail instead of deadlocking.
+    static ON_UI_THREAD: Cell<bool> = const { Cell::new(false) };
+}
 
-                // Send the Window ID back to the requesting thread
-                // -----------------------------------------------------------------
-                // if send fails, sdl2_mt can panic or print an error or do nothing.
-                // panicking in a library is a bad plan.
-                // printing errors from a library needs to be configurable.
-                //   if printing is configurable, might as well make panicking an option too.
-                // for now, sdl2_mt will do nothing.
-                let _ = tx.send(window_id);
-            },
+#[cfg(feature = "sdl")]
+fn on_ui_thread() -> bool {
+    ON_UI_THREAD.with(|on_ui_thread| on_ui_thread.get())
+}
+
+/// Synchronous calls wait for the UI thread, so the UI thread itself can't make them.
+#[cfg(feature = "sdl")]
+fn ensure_not_ui_thread() -> Result<(), Sdl2MtError> {
+    if on_ui_thread() {
+        Err(Sdl2MtError::WouldDeadlock)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sdl")]
+fn sdl_handler(queue: Arc<MessageQueue>, shared: Arc<Shared>, unhandled_capacity: usize) {
+    ON_UI_THREAD.with(|on_ui_thread| on_ui_thread.set(true));
+    let mut ui = UiLoop::new(queue, shared, unhandled_capacity);
+    while ui.turn(true) {}
+}
+
+/// Everything the UI thread works with. Usually this lives on the thread spawned by `build()`,
+/// which keeps turning it until it exits; in synchronous mode, it lives on the thread that
+/// called `build()`, which turns it with `Sdl2Mt::turn`.
+#[cfg(feature = "sdl")]
+struct UiLoop {
+    queue: Arc<MessageQueue>,
+    shared: Arc<Shared>,
+    sdl_context: Sdl,
+    video: VideoSubsystem,
+    windows: HashMap<WindowId, render::WindowCanvas>,
+    state: UiState,
+    // every message that is waiting when the UI thread wakes up is handled in one pass,
+    // which saves a trip through the queue's lock for each of them under load.
+    batch: Vec<Queued>,
+    // closes the queue even if the UI thread panics, so that nobody is left waiting on it
+    _close_queue: CloseOnDrop,
+}
+
+#[cfg(feature = "sdl")]
+impl UiLoop {
+    fn new(queue: Arc<MessageQueue>, shared: Arc<Shared>, unhandled_capacity: usize) -> UiLoop {
+        let close_queue = CloseOnDrop(queue.clone(), shared.clone());
+
+        // initialization of the library should be the only possible time we panic.
+        let sdl_context = sdl2::init().unwrap();
+        let video = sdl_context.video().unwrap();
+        let events = sdl_context.event_pump().unwrap();
+
+        let mut state = UiState::new(events, unhandled_capacity);
+        state.events.wakeup_type = queue.wakeup.register();
+        state.events.quit_on_panic = shared.panic.enabled;
+        state.clock = shared.clock.clone();
+        state.frames = shared.frames.clone();
+        UiHandle::install(queue.clone(), shared.clock.clone());
+
+        UiLoop {
+            queue,
+            shared,
+            sdl_context,
+            video,
+            windows: HashMap::new(),
+            state,
+            batch: Vec::new(),
+            _close_queue: close_queue,
+        }
+    }
+
+    /// Runs due timers, then handles every waiting message and pumps events once. If there
+    /// is nothing to do and `wait` is set, sleeps until the next event, message or timer.
+    /// Returns false once the UI thread has been told to exit.
+    fn turn(&mut self, wait: bool) -> bool {
+        let UiLoop { ref queue, ref shared, ref mut sdl_context, ref mut video, ref mut windows, ref mut state, ref mut batch, .. } = *self;
+        let watchdog = &shared.watchdog;
+
+        watchdog.begin(WorkKind::Timers, Instant::now());
+        state.run_due_timers(sdl_context, windows);
+        watchdog.end();
 
-            HandleEvent(mut handle_event, tx) => {
-                // len() should be O(1) according to docs, unlike most linked lists
-                let len = unhandled_events.len() as isize;
-                let num_events_to_drop = len - 2000;
-                for event_num in 0..len {
-                    // we're within the length of the list, this unwrap is safe.
-                    let event = unhandled_events.pop_front().unwrap();
-                    if !handle_event(&mut sdl_context, &mut windows, &event) {
-                        // place unhandled events back on the end of the list,
-                        // dropping enough of the oldest events to keep at most
-                        // 2000 unhandled events, which is enough for several
-                        // seconds of collection even during fast user input.
-                        //
-                        // if no event handler takes responsibility for an event
-                        // over the course of several entire seconds, it is then
-                        // unlikely to ever be handled by any event handler.
-                        if event_num >= num_events_to_drop {
-                            unhandled_events.push_back(event);
+        if !state.latency.samples.is_empty() {
+            shared.stats.record_event_latencies(&mut state.latency.samples);
+        }
+
+        let first = match queue.try_pop() {
+            Popped::Message(message) => message,
+            Popped::Closed => return false,
+            Popped::Empty => {
+                if !state.idle.is_empty() && !state.paused {
+                    state.events.pump.pump_events();
+                    let events_pending = unsafe {
+                        sys::event::SDL_HasEvents(sys::event::SDL_FIRSTEVENT, sys::event::SDL_LASTEVENT) != 0
+                    };
+                    if !events_pending {
+                        watchdog.begin(WorkKind::IdleCallbacks, Instant::now());
+                        state.idle.run(sdl_context, windows);
+                        watchdog.end();
+                        if wait {
+                            queue.wakeup.wait(|| queue.len() > 0, Some(Instant::now() + idle::IDLE_PAUSE));
                         }
+                        return true;
                     }
                 }
 
-                for event in events.poll_iter() {
-                    if !handle_event(&mut sdl_context, &mut windows, &event) {
-                        // if the event was unhandled, add it to the list
-                        unhandled_events.push_back(event);
-                    }
+                // nothing to do until the next event or message arrives, or the next timer is due.
+                // events are pumped right away, so that forwarders see them without delay.
+                // a test clock that was advanced since the timers ran may have made one due.
+                if wait {
+                    let next_timer = state.timers.next_deadline();
+                    let timer_due = || next_timer.is_some_and(|deadline| deadline <= state.clock.now());
+                    queue.wakeup.wait(|| queue.len() > 0 || timer_due(), state.clock.wait_deadline(next_timer));
                 }
-                
-                // Synchronize with calling thread to prevent unbounded HandleEvents messages queueing up
-                // Same logic as above regarding errors
-                let _ = tx.send(()); 
+                state.pump_events(sdl_context, windows);
+                return true;
             },
+        };
+        batch.push(first);
+        queue.pop_all(batch);
+        let queue_depth = batch.len();
+
+        for Queued { message, trace, origin, queued_at } in batch.drain(..) {
+            let _executing = trace.executing();
+            let started = Instant::now();
+            let kind = WorkKind::of(&message);
+            watchdog.begin(kind, started);
+            match message {
+                // Lambda is used for simple, asynchronous blocks of code that need to be run on
+                // the UI thread. This does not block the calling thread, so no tx sync is used.
+                Lambda(mut lambda) => lambda(sdl_context, windows),
+
+                Builtin(mut builtin) => builtin(sdl_context, windows, state),
+
+                Inline(function) => function.call(sdl_context, windows, state),
+
+                Present(window_id) => {
+                    if windows.contains_key(&window_id) {
+                        state.present(windows, window_id);
+                    } else {
+                        trace::window_not_found(window_id);
+                    }
+                },
+
+                Clear(window_id, color) => state.clear(windows, window_id, color),
+
+                PresentColor(window_id, color) => {
+                    if windows.contains_key(&window_id) {
+                        state.clear(windows, window_id, color);
+                        state.present(windows, window_id);
+                    } else {
+                        trace::window_not_found(window_id);
+                    }
+                },
+
+                DrainSubmitters => state.drain_submitters(sdl_context, windows),
+
+                CreateWindow(mut create_window, tx) => {
+                    let window_id;
+                    if let Some(canvas) = create_window(sdl_context, video) {
+                        let id = WindowId::from_raw(canvas.window().id());
+                        windows.insert(id, canvas);
+                        window_id = Some(id);
+                    } else {
+                        window_id = None;
+                    }
+
+                    // Send the Window ID back to the requesting thread
+                    // -----------------------------------------------------------------
+                    // if send fails, the requesting thread stopped waiting, and already got an error
+                    // that its handle's FailurePolicy dealt with. panicking on the UI thread would
+                    // take every other window down with it, so this is only reported through errors().
+                    tx.send(window_id);
+                },
 
-            Exit => break
+                HandleEvent(mut handle_event, tx) => {
+                    state.handle_events(sdl_context, windows, origin, &mut *handle_event);
+
+                    // Synchronize with calling thread to prevent unbounded HandleEvents messages queueing up
+                    // Same logic as above regarding errors
+                    tx.send(());
+                },
+
+                Exit => {
+                    watchdog.end();
+                    return false;
+                },
+            }
+            watchdog.end();
+            shared.stats.record_message(started.elapsed());
+            if shared.spy.is_enabled() {
+                shared.spy.record(kind, origin, queued_at, started);
+            }
         }
+
+        // SDL only learns about new events while they are pumped, so do that once
+        // per batch, to keep the windows responsive while the UI thread is busy.
+        state.pump_events(sdl_context, windows);
+
+        let unhandled_events = state.events.unhandled_len();
+        state.high_water.check(&shared.stats, queue_depth, unhandled_events);
+        true
     }
 }
 
+/// A handle to the UI thread.
+///
+/// Handles are `Send` and `Sync`, so a single handle can be shared between threads by
+/// reference, e.g. behind an `Arc` or in a global, without wrapping it in a `Mutex`.
+/// Cloning a handle is cheap as well, and every clone talks to the same UI thread.
+#[cfg(feature = "sdl")]
 #[derive(Clone)]
-pub struct Sdl2Mt(mpsc::Sender<Sdl2Message>);
-
-#[derive(Copy, Clone, Debug)]
-pub struct UiThreadExited;
+pub struct Sdl2Mt {
+    queue: Arc<MessageQueue>,
+    shared: Arc<Shared>,
+    window_replies: ReplyChannel<Option<WindowId>>,
+    event_replies: ReplyChannel<()>,
+    failure_policy: FailurePolicy,
+    id: OwnId,
+}
 
-/// map_ute is a 'nop' function that simply converts any type into the UiThreadExited error
-#[inline]
-fn map_ute<T>(_: T) -> UiThreadExited {
-    UiThreadExited
+/// State shared between every handle and the UI thread.
+#[cfg(feature = "sdl")]
+struct Shared {
+    /// lambdas submitted with `run_coalesced` that haven't run yet, by key
+    coalesced: Mutex<HashMap<String, Box<SdlLambda>>>,
+    stats: StatsCollector,
+    watchdog: Watchdog,
+    panic: UiPanic,
+    clock: Clock,
+    spy: MessageSpy,
+    frames: FrameCounter,
+    workers: WorkerPool,
+    window_profile: WindowProfile,
+    #[cfg(feature = "signals")]
+    shutdown: signals::Shutdown,
 }
 
+#[cfg(feature = "sdl")]
 impl Sdl2Mt {
     /// A quick, simple way to create a window. Just give it a name, width, and height.
+    /// The window follows the profile set with `Sdl2MtBuilder::default_window_profile`.
+    /// Returns `Sdl2MtError::SdlError` if SDL can't make the window.
     ///
     /// This function executes synchronously. It will block until the
     /// window_creator function has completed.
-    ///
-    /// # Panics
-    ///
-    /// This function will panic if the Window or the Canvas `build()` functions
-    /// do not succeed.
-    pub fn create_simple_window<IntoString: Into<String>>(&self, name: IntoString, width: u32, height: u32) -> Result<u32, UiThreadExited> {
-        let name = name.into();
-        self.create_window(Box::new(move |_sdl, video_subsystem| {
-            let canvas = video_subsystem.window(&name, width, height)
-                .position_centered()
-                .resizable()
-                .build()
-                .unwrap()
-                .into_canvas()
-                .software()
-                .build()
-                .unwrap();
-
-            // avoids some potential graphical glitches
-            sleep(Duration::from_millis(20));
-            
-            Some(canvas)
-        })).map(|id| id.unwrap())
+    pub fn create_simple_window<IntoString: Into<String>>(&self, name: IntoString, width: u32, height: u32) -> Result<WindowId, Sdl2MtError> {
+        let failure = Arc::new(Mutex::new(None));
+        let creator = self.shared.window_profile.window_creator(name.into(), width, height, Some(failure.clone()));
+        let window_id = self.create_window(creator)?;
+        simple_window::created(window_id, &failure, "create_simple_window")
     }
 
     /// Executes a window_creator function that accepts &mut VideoSubsystem
@@ -146,44 +637,551 @@ impl Sdl2Mt {
     ///
     /// This function executes synchronously. It will block until the
     /// window_creator function has completed.
-    pub fn create_window(&self, window_creator: Box<SdlCreateWindow>) -> Result<Option<u32>, UiThreadExited> {
-        let (tx, rx) = mpsc::channel();
-        self.0.send(CreateWindow(window_creator, tx)).map_err(map_ute)?;
-        rx.recv().map_err(map_ute)
+    pub fn create_window(&self, window_creator: Box<SdlCreateWindow>) -> Result<Option<WindowId>, Sdl2MtError> {
+        ensure_not_ui_thread()?;
+        let (tx, rx) = self.window_replies.take();
+        let sent = Instant::now();
+        self.send_message(Priority::Normal, CreateWindow(window_creator, tx))?;
+        let reply = self.check_failure(self.window_replies.recv(rx))?;
+        self.shared.stats.record_reply(sent.elapsed());
+        Ok(reply)
+    }
+
+    /// Like `create_window`, but gives up waiting after `timeout` and returns
+    /// `Sdl2MtError::Timeout`. The window_creator function will still run once
+    /// the UI thread gets to it, but its result is discarded.
+    pub fn create_window_timeout(&self, window_creator: Box<SdlCreateWindow>, timeout: Duration) -> Result<Option<WindowId>, Sdl2MtError> {
+        ensure_not_ui_thread()?;
+        let (tx, rx) = self.window_replies.take();
+        let sent = Instant::now();
+        self.send_message(Priority::Normal, CreateWindow(window_creator, tx))?;
+        let reply = self.check_failure(self.window_replies.recv_timeout(rx, timeout))?;
+        self.shared.stats.record_reply(sent.elapsed());
+        Ok(reply)
     }
 
     //// Executes a lambda function on the UI thread
     //// Either succeeds or the channel is closed and it returns a `SendError`
     ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    pub fn run_on_ui_thread(&self, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        self.send_message(Priority::Normal, Lambda(lambda))
+    }
+
+    /// Like `run_on_ui_thread`, but returns `Sdl2MtError::QueueFull` right away instead
+    /// of blocking when the UI thread's queue is full. This lets producers decide for
+    /// themselves whether to drop or merge work when the UI thread can't keep up, so the
+    /// handle's `FailurePolicy` doesn't apply to `QueueFull`, only to other failures.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn try_run_on_ui_thread(&self, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        self.try_send_message(Lambda(lambda))
+    }
+
+    /// Executes a lambda function on the UI thread, like `run_on_ui_thread`, but without
+    /// boxing it first. Lambdas that capture no more than a few words, e.g. a window ID
+    /// and a color, are sent to the UI thread without any heap allocation, which adds up
+    /// for work that is submitted every frame.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the UI thread's queue is full, in which case it waits for space in the queue.
+    pub fn run_on_ui_thread_once<F>(&self, lambda: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>) + Send + 'static
+    {
+        self.send_message(Priority::Normal, Inline(InlineFn::new(move |sdl, windows, _state| lambda(sdl, windows))))
+    }
+
+    /// Executes several lambda functions on the UI thread, in order, delivered as a single
+    /// message. No events are handled and no other messages run in between the lambdas
+    /// of a batch.
+    ///
     /// This function executes asynchronously. It will *not* block the calling thread.
-    pub fn run_on_ui_thread(&self, lambda: Box<SdlLambda>) -> Result<(), UiThreadExited> {
-        self.0.send(Lambda(lambda)).map_err(map_ute)
+    pub fn run_batch(&self, mut lambdas: Vec<Box<SdlLambda>>) -> Result<(), Sdl2MtError> {
+        self.run_on_ui_thread(Box::new(move |sdl, windows| {
+            for lambda in &mut lambdas {
+                lambda(sdl, windows);
+            }
+            lambdas.clear();
+        }))
+    }
+
+    /// Executes a lambda function on the UI thread with the given priority. High priority
+    /// lambdas run ahead of everything that is waiting in the normal queue, which keeps
+    /// latency-sensitive work from getting stuck behind a backlog of heavy render or upload
+    /// lambdas. Event handling and `exit()` always use the high priority queue.
+    ///
+    /// Each priority has its own lane, which holds up to `Sdl2MtBuilder::queue_capacity`
+    /// messages and follows the same backpressure policy.
+    ///
+    /// High and normal priority lambdas submitted from the same thread are not guaranteed
+    /// to run in submission order.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the lane is full, in which case it waits for space in the queue.
+    pub fn run_on_ui_thread_with_priority(&self, priority: Priority, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        self.send_message(priority, Lambda(lambda))
+    }
+
+    /// Executes a function on the UI thread and hands its return value back
+    /// to the calling thread.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// function has completed.
+    pub fn call<T, F>(&self, function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>) -> T + Send + 'static
+    {
+        ensure_not_ui_thread()?;
+        let (tx, rx) = mpsc::channel();
+        let sent = Instant::now();
+        self.send_message(Priority::Normal, Inline(InlineFn::new(move |sdl, windows, _state| {
+            let _ = tx.send(function(sdl, windows));
+        })))?;
+        self.recv_reply(rx, sent)
+    }
+
+    /// Like `call`, but gives up waiting after `timeout` and returns `Sdl2MtError::Timeout`.
+    /// The function will still run once the UI thread gets to it, but its return value
+    /// is discarded.
+    pub fn call_timeout<T, F>(&self, function: F, timeout: Duration) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>) -> T + Send + 'static
+    {
+        ensure_not_ui_thread()?;
+        let (tx, rx) = mpsc::channel();
+        let sent = Instant::now();
+        self.send_message(Priority::Normal, Inline(InlineFn::new(move |sdl, windows, _state| {
+            let _ = tx.send(function(sdl, windows));
+        })))?;
+        self.recv_reply_timeout(rx, sent, timeout)
+    }
+
+    /// Runs a function with the window's canvas on the UI thread and hands its return value
+    /// back to the calling thread. Returns `Sdl2MtError::WindowNotFound` if there is no
+    /// window with that ID.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// function has completed.
+    pub fn with_canvas<T, F>(&self, window_id: WindowId, function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut render::WindowCanvas) -> T + Send + 'static
+    {
+        self.call(move |_sdl, windows| windows.get_mut(&window_id).map(function))?
+            .ok_or(Sdl2MtError::WindowNotFound(window_id))
+    }
+
+    /// Runs a function with the SDL context on the UI thread and hands its return value back
+    /// to the calling thread, e.g. to check which subsystems are up, or where the mouse is.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// function has completed.
+    pub fn with_sdl<T, F>(&self, function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl) -> T + Send + 'static
+    {
+        self.call(move |sdl, _windows| function(sdl))
+    }
+
+    /// Blocks until every lambda submitted before this call, from any handle, has run.
+    /// This establishes an ordering between asynchronous UI work and whatever the
+    /// calling thread does next, e.g. "after all draws, take a screenshot".
+    ///
+    /// Timers and idle callbacks that have not run yet are not waited for.
+    ///
+    /// This function executes synchronously.
+    pub fn flush(&self) -> Result<(), Sdl2MtError> {
+        // the channel is first-in-first-out across all handles, and high priority
+        // messages always run before it is read, so an empty call acts as a barrier.
+        self.call(|_sdl, _windows| ())
+    }
+
+    /// Like `call`, but also hands the function the UI thread's internal state.
+    fn call_builtin<T, F>(&self, function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>, &mut UiState) -> T + Send + 'static
+    {
+        ensure_not_ui_thread()?;
+        let (tx, rx) = mpsc::channel();
+        let sent = Instant::now();
+        self.send_message(Priority::Normal, Inline(InlineFn::new(move |sdl, windows, state| {
+            let _ = tx.send(function(sdl, windows, state));
+        })))?;
+        self.recv_reply(rx, sent)
+    }
+
+    /// Runs a function with access to the UI thread's internal state, without
+    /// waiting for it to complete.
+    fn run_builtin<F>(&self, function: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>, &mut UiState) + Send + 'static
+    {
+        self.send_message(Priority::Normal, Inline(InlineFn::new(function)))
     }
 
     /// Executes an event_handler function.
     ///
+    /// Within a call, the event_handler sees each window's events in the order they arrived;
+    /// while an earlier event for a window is being kept for another handle, the later events
+    /// for that window are kept back too. Events it returns `false` for don't hold back later
+    /// ones: they are offered again on the next call, ahead of any new events, but after the
+    /// later events it already handled.
+    ///
     /// This function executes synchronously. It will block until the
     /// event_handler function has completed.
-    pub fn handle_ui_events(&self, event_handler: Box<SdlHandleEvent>) -> Result<(), UiThreadExited> {
-        let (tx, rx) = mpsc::channel();
-        self.0.send(HandleEvent(event_handler, tx)).map_err(map_ute)?;
-        rx.recv().map_err(map_ute)
+    pub fn handle_ui_events(&self, event_handler: Box<SdlHandleEvent>) -> Result<(), Sdl2MtError> {
+        ensure_not_ui_thread()?;
+        let (tx, rx) = self.event_replies.take();
+        let sent = Instant::now();
+        self.send_message(Priority::High, HandleEvent(event_handler, tx))?;
+        self.check_failure(self.event_replies.recv(rx))?;
+        self.shared.stats.record_reply(sent.elapsed());
+        Ok(())
+    }
+
+    /// Like `handle_ui_events`, but gives up waiting after `timeout` and returns
+    /// `Sdl2MtError::Timeout`, so that a stuck or overloaded UI thread can't hang
+    /// the calling thread forever. The event_handler function will still run once
+    /// the UI thread gets to it.
+    pub fn handle_ui_events_timeout(&self, event_handler: Box<SdlHandleEvent>, timeout: Duration) -> Result<(), Sdl2MtError> {
+        ensure_not_ui_thread()?;
+        let (tx, rx) = self.event_replies.take();
+        let sent = Instant::now();
+        self.send_message(Priority::High, HandleEvent(event_handler, tx))?;
+        self.check_failure(self.event_replies.recv_timeout(rx, timeout))?;
+        self.shared.stats.record_reply(sent.elapsed());
+        Ok(())
+    }
+
+    /// Like `handle_ui_events`, but the event_handler may fail, e.g. when it does I/O, instead
+    /// of unwrapping and taking the UI thread down with it. An event the handler failed on
+    /// counts as handled. Every error is reported through `errors()`, and the first one is
+    /// returned as `Sdl2MtError::Callback` once all pending events have been handled.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// event_handler function has completed.
+    pub fn try_handle_ui_events<E, F>(&self, mut event_handler: F) -> Result<(), Sdl2MtError>
+        where E: fmt::Display,
+              F: FnMut(&mut Sdl, &mut HashMap<WindowId, render::WindowCanvas>, &Event) -> Result<bool, E> + Send + 'static
+    {
+        let first_error = Arc::new(Mutex::new(None));
+        let record = first_error.clone();
+        self.handle_ui_events(Box::new(move |sdl, windows, event| {
+            match event_handler(sdl, windows, event) {
+                Ok(handled) => handled,
+                Err(error) => {
+                    let error = error.to_string();
+                    record.lock().unwrap().get_or_insert_with(|| error.clone());
+                    trace::callback_error("event handler", error);
+                    true
+                },
+            }
+        }))?;
+        let first_error = first_error.lock().unwrap().take();
+        match first_error {
+            Some(error) => Err(error::callback_error("event handler", error)),
+            None => Ok(()),
+        }
     }
 
     /// Terminates the UI thread. Not strictly necessary if the program will exit anyways,
     /// such as when the main program thread returns from main.
-    pub fn exit(self) -> Result<(), UiThreadExited> {
-        self.0.send(Exit).map_err(map_ute)
+    ///
+    /// Exiting is a high priority message, so any lambdas still waiting in the queue are dropped.
+    pub fn exit(self) -> Result<(), Sdl2MtError> {
+        self.send_control(Exit)
+    }
+
+    fn send_message(&self, priority: Priority, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.check_failure(self.queue.push(priority, self.id.0, message, false))
+    }
+
+    /// Sends a message that `Backpressure::DropOldest` may evict before others to make room.
+    fn send_coalescable(&self, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.check_failure(self.queue.push(Priority::Normal, self.id.0, message, true))
+    }
+
+    /// Sends a message without waiting for space, returning `Sdl2MtError::QueueFull` instead.
+    /// A full queue is expected here, so the failure policy doesn't see it.
+    fn try_send_message(&self, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        match self.queue.try_push(self.id.0, message) {
+            Err(Sdl2MtError::QueueFull) => Err(Sdl2MtError::QueueFull),
+            result => self.check_failure(result),
+        }
+    }
+
+    /// Sends one of the crate's own control messages, which skip the queue's capacity.
+    fn send_control(&self, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.check_failure(self.queue.push_control(self.id.0, message))
+    }
+
+    /// Waits for the UI thread's reply to a message sent at `sent`.
+    fn recv_reply<T>(&self, rx: mpsc::Receiver<T>, sent: Instant) -> Result<T, Sdl2MtError> {
+        let reply = self.check_failure(synchronous::recv(&rx, None))?;
+        self.shared.stats.record_reply(sent.elapsed());
+        Ok(reply)
+    }
+
+    fn recv_reply_timeout<T>(&self, rx: mpsc::Receiver<T>, sent: Instant, timeout: Duration) -> Result<T, Sdl2MtError> {
+        let reply = self.check_failure(synchronous::recv(&rx, Some(timeout)))?;
+        self.shared.stats.record_reply(sent.elapsed());
+        Ok(reply)
+    }
+
+    /// Applies the handle's failure policy to the result of sending a message or waiting for a reply.
+    fn check_failure<T>(&self, result: Result<T, Sdl2MtError>) -> Result<T, Sdl2MtError> {
+        if let Err(ref error) = result {
+            match self.failure_policy {
+                FailurePolicy::ReturnError => {},
+                FailurePolicy::Report => trace::call_failed(error),
+                FailurePolicy::Panic => panic!("sdl2_mt: {}", error),
+            }
+        }
+        result
     }
 }
 
-lazy_static! {
-    static ref MT_HANDLE: Arc<Mutex<Sdl2Mt>> = {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || sdl_handler(rx));
-        let handle = Sdl2Mt(tx);
-        Arc::new(Mutex::new(handle))
-    };
+/// Closes the message queue when dropped.
+#[cfg(feature = "sdl")]
+struct CloseOnDrop(Arc<MessageQueue>, Arc<Shared>);
+
+#[cfg(feature = "sdl")]
+impl Drop for CloseOnDrop {
+    fn drop(&mut self) {
+        self.0.close();
+        self.1.watchdog.ui_thread_exited();
+    }
+}
+
+#[cfg(feature = "sdl")]
+static MT_HANDLE: Mutex<Option<Sdl2Mt>> = Mutex::new(None);
+
+/// Configures the UI thread before it is started. Since there is only ever one UI thread,
+/// the configuration only takes effect if `build()` is the first call to start it; after that,
+/// `build()` returns a handle to the existing UI thread, just like `init()` does.
+#[cfg(feature = "sdl")]
+pub struct Sdl2MtBuilder {
+    queue_capacity: usize,
+    backpressure: Backpressure,
+    unhandled_event_capacity: usize,
+    panic_hook: bool,
+    headless: bool,
+    test_clock: Option<TestClock>,
+    synchronous: bool,
+    dpi_awareness: Option<DpiAwareness>,
+    dpi_scaling: Option<bool>,
+    window_profile: WindowProfile,
+    #[cfg(feature = "signals")]
+    quit_on_signals: bool,
+}
+
+#[cfg(feature = "sdl")]
+impl Sdl2MtBuilder {
+    pub fn new() -> Sdl2MtBuilder {
+        Sdl2MtBuilder {
+            queue_capacity: 4096,
+            backpressure: Backpressure::Block,
+            unhandled_event_capacity: dispatch::DEFAULT_UNHANDLED_CAPACITY,
+            panic_hook: false,
+            headless: false,
+            test_clock: None,
+            synchronous: false,
+            dpi_awareness: None,
+            dpi_scaling: None,
+            window_profile: WindowProfile::new(),
+            #[cfg(feature = "signals")]
+            quit_on_signals: false,
+        }
+    }
+
+    /// How many messages of each priority can wait for the UI thread before the backpressure
+    /// policy kicks in. The default is 4096.
+    ///
+    /// Returns `Sdl2MtError::InvalidArgument` if `capacity` is zero, since no message
+    /// could ever be queued.
+    pub fn queue_capacity(mut self, capacity: usize) -> Result<Sdl2MtBuilder, Sdl2MtError> {
+        if capacity == 0 {
+            return Err(Sdl2MtError::InvalidArgument("queue capacity must be greater than zero"));
+        }
+        self.queue_capacity = capacity;
+        Ok(self)
+    }
+
+    /// What submitting work does when the UI thread's queue is full. The default is
+    /// `Backpressure::Block`. The `try_` methods never block, regardless of this setting.
+    pub fn backpressure(mut self, backpressure: Backpressure) -> Sdl2MtBuilder {
+        self.backpressure = backpressure;
+        self
+    }
+
+    /// How many events that no event handler has handled yet are kept around for the
+    /// next call to `handle_ui_events`. Once there are more, the oldest are dropped.
+    /// The default is 2000.
+    pub fn unhandled_event_capacity(mut self, capacity: usize) -> Sdl2MtBuilder {
+        self.unhandled_event_capacity = capacity;
+        self
+    }
+
+    /// Installs a panic hook that notices when the UI thread panics, so that the rest of the
+    /// application can shut down cleanly. The panic is reported as `UiThreadPanicked` through
+    /// `errors()` and by `ui_thread_panic()`, and everyone receiving events through
+    /// `forward_events` and friends gets a final `Event::Quit`. Whatever panic hook was
+    /// installed before still runs for every panic. The default is false.
+    ///
+    /// Either way, calls on handles return `Sdl2MtError::UiThreadExited` once the UI thread is gone.
+    pub fn panic_hook(mut self, enabled: bool) -> Sdl2MtBuilder {
+        self.panic_hook = enabled;
+        self
+    }
+
+    /// Runs SDL with its dummy video and audio drivers, which need no display server or sound
+    /// card, so that tests using windows can run on CI machines. Windows can be created and
+    /// drawn to with software canvases, like the ones `create_simple_window` makes, but
+    /// nothing is ever shown, and no input events arrive.
+    ///
+    /// This sets the `SDL_VIDEODRIVER` and `SDL_AUDIODRIVER` environment variables of the
+    /// whole process, which SDL reads when it is initialized.
+    pub fn headless(mut self) -> Sdl2MtBuilder {
+        self.headless = true;
+        self
+    }
+
+    /// Schedules timers, debounced and throttled lambdas against a virtual clock that only
+    /// moves when the test advances it, with `TestClock::advance` or `Sdl2Mt::advance_clock`.
+    pub fn test_clock(mut self, clock: TestClock) -> Sdl2MtBuilder {
+        self.test_clock = Some(clock);
+        self
+    }
+
+    /// Sets SDL's `SDL_WINDOWS_DPI_AWARENESS` hint, which tells Windows how the process handles
+    /// displays with different DPI settings. Without it, Windows scales windows on high DPI
+    /// displays up as bitmaps, which looks blurry. Use `DpiAwareness::PerMonitorV2` along with
+    /// `scale_changes` to render sharply on every display. Other platforms ignore this.
+    ///
+    /// This requires SDL 2.24 or newer at runtime.
+    pub fn windows_dpi_awareness(mut self, awareness: DpiAwareness) -> Sdl2MtBuilder {
+        self.dpi_awareness = Some(awareness);
+        self
+    }
+
+    /// Sets SDL's `SDL_WINDOWS_DPI_SCALING` hint. When enabled, window sizes and mouse
+    /// coordinates on Windows are in scaled points rather than pixels, like on macOS, and the
+    /// drawable size of `allow_highdpi()` windows is in pixels. This implies per-monitor DPI
+    /// awareness. Other platforms ignore this.
+    ///
+    /// This requires SDL 2.24 or newer at runtime.
+    pub fn windows_dpi_scaling(mut self, enabled: bool) -> Sdl2MtBuilder {
+        self.dpi_scaling = Some(enabled);
+        self
+    }
+
+    /// Turns SIGINT and SIGTERM, or Ctrl+C on Windows, into a `Quit` event, so that programs
+    /// started from a terminal close their windows cleanly instead of dying in the middle of
+    /// presenting a frame. Every handle can also check `shutdown_requested`, or listen to
+    /// `shutdown_signals`, to stop its own work.
+    ///
+    /// # Panics
+    ///
+    /// `build()` will panic if the process already has a handler for these signals.
+    #[cfg(feature = "signals")]
+    pub fn quit_on_signals(mut self) -> Sdl2MtBuilder {
+        self.quit_on_signals = true;
+        self
+    }
+
+    /// Sets the title prefix, icon, size, window flags and renderer options that every
+    /// window made by `create_simple_window` starts with.
+    pub fn default_window_profile(mut self, profile: WindowProfile) -> Sdl2MtBuilder {
+        self.window_profile = profile;
+        self
+    }
+
+    /// Doesn't start a UI thread at all. Instead, the thread calling `build()` becomes the UI
+    /// thread, and handles messages and events whenever it calls `Sdl2Mt::turn`, or makes a
+    /// synchronous call like `call`. This makes tests single threaded and deterministic, without
+    /// sleeping to give the UI thread time to catch up.
+    ///
+    /// Other threads can still submit work, but synchronous calls from them block until the
+    /// building thread turns the loop. Async futures can't be awaited on the building thread.
+    ///
+    /// On emscripten, which has no threads, this is always the case, and the browser turns the
+    /// loop once `Sdl2Mt::run_main_loop` is called.
+    pub fn synchronous(mut self) -> Sdl2MtBuilder {
+        self.synchronous = true;
+        self
+    }
+
+    /// Starts the UI thread, which also initializes the `Sdl2` library, and returns a handle to it.
+    pub fn build(self) -> Sdl2Mt {
+        let mut global = MT_HANDLE.lock().unwrap();
+        if let Some(ref handle) = *global {
+            return handle.clone();
+        }
+
+        if self.headless {
+            env::set_var("SDL_VIDEODRIVER", "dummy");
+            env::set_var("SDL_AUDIODRIVER", "dummy");
+        }
+        // hints have to be set before SDL creates its first window
+        if let Some(awareness) = self.dpi_awareness {
+            hint::set("SDL_WINDOWS_DPI_AWARENESS", awareness.hint_value());
+        }
+        if let Some(enabled) = self.dpi_scaling {
+            hint::set("SDL_WINDOWS_DPI_SCALING", if enabled { "1" } else { "0" });
+        }
+
+        let queue = Arc::new(MessageQueue::new(self.queue_capacity, self.backpressure));
+        let ui_queue = queue.clone();
+        if let Some(ref clock) = self.test_clock {
+            clock.attach(queue.clone());
+        }
+        let shared = Arc::new(Shared {
+            coalesced: Mutex::new(HashMap::new()),
+            stats: StatsCollector::new(),
+            watchdog: Watchdog::new(),
+            panic: UiPanic::new(self.panic_hook),
+            clock: self.test_clock.map_or(Clock::Real, Clock::Test),
+            spy: MessageSpy::new(),
+            frames: FrameCounter::default(),
+            workers: WorkerPool::new(),
+            window_profile: self.window_profile,
+            #[cfg(feature = "signals")]
+            shutdown: signals::Shutdown::new(),
+        });
+        if self.panic_hook {
+            ui_panic::install_hook(shared.clone());
+        }
+        let ui_shared = shared.clone();
+        let unhandled_capacity = self.unhandled_event_capacity;
+        if self.synchronous || cfg!(target_os = "emscripten") {
+            synchronous::install(UiLoop::new(ui_queue, ui_shared, unhandled_capacity));
+        } else {
+            thread::spawn(move || sdl_handler(ui_queue, ui_shared, unhandled_capacity));
+        }
+
+        let handle = Sdl2Mt {
+            queue,
+            shared,
+            window_replies: ReplyChannel::new(),
+            event_replies: ReplyChannel::new(),
+            failure_policy: FailurePolicy::default(),
+            id: OwnId::new(),
+        };
+        #[cfg(feature = "signals")]
+        {
+            if self.quit_on_signals {
+                signals::install(handle.clone());
+            }
+        }
+        *global = Some(handle.clone());
+        handle
+    }
+}
+
+#[cfg(feature = "sdl")]
+impl Default for Sdl2MtBuilder {
+    fn default() -> Sdl2MtBuilder {
+        Sdl2MtBuilder::new()
+    }
 }
 
 /// Initializes an `Sdl2Mt` instance, which also initializes the `Sdl2` library.
@@ -193,14 +1191,12 @@ lazy_static! {
 /// `init()` will panic if `Sdl2` initialization fails. If this is unacceptable, you should
 /// `catch_panic()` around your `init()` call. Initialization should never fail under
 /// anything approaching reasonable circumstances.
+#[cfg(feature = "sdl")]
 pub fn init() -> Sdl2Mt {
-    let handle = (*MT_HANDLE).clone();
-    let locked = handle.lock().unwrap();
-    let new_handle = locked.clone();
-    new_handle
+    Sdl2MtBuilder::new().build()
 }
 
-#[cfg(test)]
+#[cfg(all(feature = "sdl", test))]
 mod tests {
     use super::*;
     use std::thread::sleep;
@@ -216,4 +1212,10 @@ mod tests {
         b.run_on_ui_thread(Box::new(|_, _| {})).unwrap();
         sleep(Duration::from_millis(250));
     }
+
+    #[test]
+    fn handle_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Sdl2Mt>();
+    }
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use dispatch::Forwarded;
+use event::Event;
+use state::UiState;
+
+use std::sync::mpsc;
+
+/// The application lifecycle events that SDL reports on Android and iOS. Mobile operating
+/// systems kill applications that keep rendering in the background, or that ignore
+/// low memory warnings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// the OS is terminating the application
+    Terminating,
+    /// the OS is low on memory; free what you can
+    LowMemory,
+    /// the application is about to go into the background, and should stop rendering
+    WillEnterBackground,
+    DidEnterBackground,
+    WillEnterForeground,
+    /// the application is in the foreground again, and can resume rendering
+    DidEnterForeground,
+}
+
+impl LifecycleEvent {
+    pub fn from_event(event: &Event) -> Option<LifecycleEvent> {
+        match *event {
+            Event::AppTerminating { .. } => Some(LifecycleEvent::Terminating),
+            Event::AppLowMemory { .. } => Some(LifecycleEvent::LowMemory),
+            Event::AppWillEnterBackground { .. } => Some(LifecycleEvent::WillEnterBackground),
+            Event::AppDidEnterBackground { .. } => Some(LifecycleEvent::DidEnterBackground),
+            Event::AppWillEnterForeground { .. } => Some(LifecycleEvent::WillEnterForeground),
+            Event::AppDidEnterForeground { .. } => Some(LifecycleEvent::DidEnterForeground),
+            _ => None,
+        }
+    }
+}
+
+impl UiState {
+    /// Pauses rendering while the application is in the background.
+    pub fn observe_lifecycle(&mut self, event: &Event) {
+        match LifecycleEvent::from_event(event) {
+            Some(LifecycleEvent::WillEnterBackground) => self.paused = true,
+            Some(LifecycleEvent::DidEnterForeground) => self.paused = false,
+            _ => {},
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Sends the application lifecycle events to a channel, as soon as the UI thread sees them.
+    /// Unlike `forward_events`, this leaves the events to other forwarders and event handlers too.
+    ///
+    /// Whether or not anybody listens, the UI thread pauses rendering between
+    /// `WillEnterBackground` and `DidEnterForeground`: `present` drops frames, and idle
+    /// callbacks don't run.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn lifecycle_events(&self) -> Result<mpsc::Receiver<LifecycleEvent>, Sdl2MtError> {
+        let (sender, receiver) = mpsc::channel();
+        self.run_builtin(move |_sdl, _windows, state| {
+            state.add_event_forwarder(Box::new(move |event| {
+                match LifecycleEvent::from_event(event) {
+                    Some(lifecycle_event) => match sender.send(lifecycle_event) {
+                        Ok(()) => Forwarded::Unhandled,
+                        Err(_) => Forwarded::Disconnected,
+                    },
+                    None => Forwarded::Unhandled,
+                }
+            }));
+        })?;
+        Ok(receiver)
+    }
+
+    /// Whether rendering is paused because the application is in the background.
+    ///
+    /// This function executes synchronously.
+    pub fn is_paused(&self) -> Result<bool, Sdl2MtError> {
+        self.call_builtin(|_sdl, _windows, state| state.paused)
+    }
+}
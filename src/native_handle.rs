@@ -0,0 +1,64 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use error;
+use ffi;
+use get_error;
+use sys;
+
+/// The platform's own handle of a window, returned by `Sdl2Mt::native_handle`, for
+/// integrations like global hotkeys, OS media controls or taskbar progress. The pointers
+/// are addresses, and belong to SDL, which frees them when the window is destroyed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NativeHandle {
+    /// the window's `HWND`, and the `HINSTANCE` it was created with
+    Windows { hwnd: usize, hinstance: usize },
+    /// the X11 `Display*`, and the `Window` XID
+    X11 { display: usize, window: u64 },
+    /// the `wl_display*` and the window's `wl_surface*`
+    Wayland { display: usize, surface: usize },
+    /// the window's `NSWindow*`
+    Cocoa { ns_window: usize },
+    /// the window's `UIWindow*`
+    UIKit { ui_window: usize },
+    /// the window's `ANativeWindow*`
+    Android { native_window: usize },
+    /// a video driver this crate doesn't know the handles of, with SDL's `SDL_SYSWM_TYPE` value
+    Other(i32),
+}
+
+impl Sdl2Mt {
+    /// Returns the native handle of a window, or `Sdl2MtError::WindowNotFound` if there is
+    /// no window with that ID.
+    /// Returns `Sdl2MtError::SdlError` if the video driver has no native handles, like the
+    /// dummy driver that `Sdl2MtBuilder::headless` uses.
+    ///
+    /// This function executes synchronously.
+    pub fn native_handle(&self, window_id: WindowId) -> Result<NativeHandle, Sdl2MtError> {
+        self.with_canvas(window_id, move |canvas| {
+            let mut wm_info = ffi::SDL_SysWMinfo {
+                version: sys::version::SDL_version { major: 0, minor: 0, patch: 0 },
+                subsystem: 0,
+                info: [0; 16],
+            };
+            unsafe {
+                // SDL fills in the fields that the version it is told about has
+                sys::version::SDL_GetVersion(&mut wm_info.version);
+                if ffi::SDL_GetWindowWMInfo(canvas.window().raw(), &mut wm_info) == 0 {
+                    return Err(error::sdl_error("SDL_GetWindowWMInfo", get_error()));
+                }
+            }
+
+            let info = wm_info.info;
+            Ok(match wm_info.subsystem {
+                ffi::SDL_SYSWM_WINDOWS => NativeHandle::Windows { hwnd: info[0], hinstance: info[2] },
+                ffi::SDL_SYSWM_X11 => NativeHandle::X11 { display: info[0], window: info[1] as u64 },
+                ffi::SDL_SYSWM_WAYLAND => NativeHandle::Wayland { display: info[0], surface: info[1] },
+                ffi::SDL_SYSWM_COCOA => NativeHandle::Cocoa { ns_window: info[0] },
+                ffi::SDL_SYSWM_UIKIT => NativeHandle::UIKit { ui_window: info[0] },
+                ffi::SDL_SYSWM_ANDROID => NativeHandle::Android { native_window: info[0] },
+                subsystem => NativeHandle::Other(subsystem),
+            })
+        }).and_then(|result| result)
+    }
+}
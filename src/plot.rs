@@ -0,0 +1,167 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use font;
+use pixels::Color;
+use rect::{Point, Rect};
+use render::WindowCanvas;
+use trace;
+
+/// How a plot drawn with `Sdl2Mt::plot_line_series` looks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AxesConfig {
+    /// the range of the x axis, or None to fit the data
+    pub x_range: Option<(f64, f64)>,
+    /// the range of the y axis, or None to fit the data
+    pub y_range: Option<(f64, f64)>,
+    /// about how many ticks each axis gets; the actual ticks are at round numbers
+    pub ticks: u32,
+    pub background: Color,
+    pub axes_color: Color,
+    pub line_color: Color,
+    /// the space around the plot, in pixels
+    pub margin: u32,
+}
+
+impl Default for AxesConfig {
+    fn default() -> AxesConfig {
+        AxesConfig {
+            x_range: None,
+            y_range: None,
+            ticks: 5,
+            background: Color::RGB(255, 255, 255),
+            axes_color: Color::RGB(0, 0, 0),
+            line_color: Color::RGB(30, 100, 200),
+            margin: 8,
+        }
+    }
+}
+
+/// The smallest and largest finite value, widened if they are the same so that the
+/// range can be divided by.
+fn fit_range<I: Iterator<Item = f64>>(values: I) -> (f64, f64) {
+    let (min, max) = values.filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+    if min > max {
+        (0.0, 1.0)
+    } else if min == max {
+        (min - 0.5, max + 0.5)
+    } else {
+        (min, max)
+    }
+}
+
+/// A step between ticks of 1, 2 or 5 times a power of ten, that splits `range` into
+/// about `count` parts.
+fn tick_step(range: f64, count: u32) -> f64 {
+    let rough = range / count.max(1) as f64;
+    let magnitude = 10f64.powf(rough.log10().floor());
+    let nice = match rough / magnitude {
+        fraction if fraction < 1.5 => 1.0,
+        fraction if fraction < 3.5 => 2.0,
+        fraction if fraction < 7.5 => 5.0,
+        _ => 10.0,
+    };
+    nice * magnitude
+}
+
+/// The ticks within `(min, max)`, with their labels.
+fn ticks((min, max): (f64, f64), count: u32) -> Vec<(f64, String)> {
+    let step = tick_step(max - min, count);
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let mut ticks = vec![];
+    let mut index = (min / step).ceil();
+    while index * step <= max + step * 1e-9 {
+        let value = index * step;
+        // avoids "-0"
+        let label = format!("{:.*}", decimals, if value.abs() < step * 1e-9 { 0.0 } else { value });
+        ticks.push((value, label));
+        index += 1.0;
+    }
+    ticks
+}
+
+/// Draws a line chart of `points` over the whole canvas, with labeled axes.
+pub fn draw_line_series(canvas: &mut WindowCanvas, points: &[(f64, f64)], axes: &AxesConfig) -> Result<(), String> {
+    let x_range = axes.x_range.unwrap_or_else(|| fit_range(points.iter().map(|point| point.0)));
+    let y_range = axes.y_range.unwrap_or_else(|| fit_range(points.iter().map(|point| point.1)));
+    let x_ticks = ticks(x_range, axes.ticks);
+    let y_ticks = ticks(y_range, axes.ticks);
+
+    // room for the labels left of and below the plot
+    let label_width = y_ticks.iter().map(|tick| font::text_size(&tick.1, 1).0).max().unwrap_or(0);
+    let viewport = canvas.viewport();
+    let margin = axes.margin as i32;
+    let left = margin + label_width as i32 + 6;
+    let top = margin;
+    let right = viewport.width() as i32 - margin - font::GLYPH_SIZE as i32;
+    let bottom = viewport.height() as i32 - margin - font::GLYPH_SIZE as i32 - 6;
+    if right <= left || bottom <= top {
+        return Ok(());
+    }
+
+    let to_x = |x: f64| left + ((x - x_range.0) / (x_range.1 - x_range.0) * (right - left) as f64).round() as i32;
+    let to_y = |y: f64| bottom - ((y - y_range.0) / (y_range.1 - y_range.0) * (bottom - top) as f64).round() as i32;
+
+    canvas.set_draw_color(axes.background);
+    canvas.clear();
+
+    canvas.set_draw_color(axes.axes_color);
+    canvas.draw_line(Point::new(left, top), Point::new(left, bottom))?;
+    canvas.draw_line(Point::new(left, bottom), Point::new(right, bottom))?;
+    for &(value, ref label) in &x_ticks {
+        let x = to_x(value);
+        canvas.set_draw_color(axes.axes_color);
+        canvas.draw_line(Point::new(x, bottom), Point::new(x, bottom + 4))?;
+        let (width, _) = font::text_size(label, 1);
+        font::draw_text(canvas, label, x - width as i32 / 2, bottom + 6, 1, axes.axes_color)?;
+    }
+    for &(value, ref label) in &y_ticks {
+        let y = to_y(value);
+        canvas.set_draw_color(axes.axes_color);
+        canvas.draw_line(Point::new(left - 4, y), Point::new(left, y))?;
+        let (width, height) = font::text_size(label, 1);
+        font::draw_text(canvas, label, left - 6 - width as i32, y - height as i32 / 2, 1, axes.axes_color)?;
+    }
+
+    // points that aren't finite break the line, like gaps in the data
+    canvas.set_clip_rect(Rect::new(left, top, (right - left) as u32 + 1, (bottom - top) as u32 + 1));
+    canvas.set_draw_color(axes.line_color);
+    let mut result = Ok(());
+    for segment in points.split(|point| !point.0.is_finite() || !point.1.is_finite()) {
+        let line: Vec<Point> = segment.iter().map(|&(x, y)| Point::new(to_x(x), to_y(y))).collect();
+        result = result.and_then(|_| match line.len() {
+            0 => Ok(()),
+            1 => canvas.draw_point(line[0]),
+            _ => canvas.draw_lines(&line[..]),
+        });
+    }
+    canvas.set_clip_rect(None);
+    result
+}
+
+impl Sdl2Mt {
+    /// Draws a line chart of `points` over the whole window and presents it. The axes are
+    /// scaled to fit the data, unless `axes` gives their ranges, and get ticks at round
+    /// numbers, labeled with the built-in font. Points that aren't finite leave a gap.
+    ///
+    /// Meant for scripts that want to look at some data without a charting crate; call it
+    /// again with more points to update the chart.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn plot_line_series(&self, window_id: WindowId, points: &[(f64, f64)], axes: &AxesConfig) -> Result<(), Sdl2MtError> {
+        let points = points.to_vec();
+        let axes = axes.clone();
+        self.run_builtin(move |_sdl, windows, state| {
+            match windows.get_mut(&window_id) {
+                Some(canvas) => {
+                    if let Err(error) = draw_line_series(canvas, &points, &axes) {
+                        trace::sdl_error("plot_line_series", error);
+                    }
+                },
+                None => return trace::window_not_found(window_id),
+            }
+            state.present(windows, window_id);
+        })
+    }
+}
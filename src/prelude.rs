@@ -0,0 +1,15 @@
+//! The types that most programs using sdl2_mt need, for a single glob import:
+//!
+//! ```
+//! use sdl2_mt::prelude::*;
+//! ```
+
+pub use {Sdl2Mt, Sdl2MtBuilder, Sdl2MtError};
+pub use {LoopControl, Priority, SimpleWindowOptions, UiContext, UiHandle, WindowId};
+pub use {Scene, SceneAction, UiDispatcher};
+pub use event::{Event, WindowEvent};
+pub use keyboard::Keycode;
+pub use mouse::MouseButton;
+pub use pixels::Color;
+pub use rect::{Point, Rect};
+pub use render::WindowCanvas;
@@ -0,0 +1,140 @@
+use Priority;
+use Sdl2Message::{Clear, Present, PresentColor};
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use pixels::Color;
+use render::WindowCanvas;
+use state::UiState;
+use trace;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub type SdlPresent = dyn FnMut(&PresentInfo) + Send;
+
+/// Timing information about a frame, passed to the callbacks registered with `on_present`.
+#[derive(Copy, Clone, Debug)]
+pub struct PresentInfo {
+    pub window_id: WindowId,
+    /// counts the frames presented through `Sdl2Mt::present` for this window, starting at 0
+    pub frame: u64,
+    /// the moment the present call returned
+    pub presented_at: Instant,
+    /// how long the present call itself took; with vsync enabled this includes
+    /// the time spent waiting for the display
+    pub present_duration: Duration,
+    /// the time since the previous frame was presented, if there was one
+    pub frame_interval: Option<Duration>,
+}
+
+impl UiState {
+    pub fn clear(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, window_id: WindowId, color: Color) {
+        match canvases.get_mut(&window_id) {
+            Some(canvas) => {
+                canvas.set_draw_color(color);
+                canvas.clear();
+            },
+            None => trace::window_not_found(window_id),
+        }
+    }
+
+    pub fn present(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, window_id: WindowId) {
+        let canvas = match canvases.get_mut(&window_id) {
+            Some(canvas) => canvas,
+            None => return,
+        };
+        self.reset_render_target(canvas, window_id);
+        // mobile operating systems kill applications that render in the background
+        if self.paused || self.auto_pause.paused {
+            return;
+        }
+
+        self.composite_layers(canvas, window_id);
+        // subtitles go on top of everything that was rendered
+        if let Some(subtitles) = self.windows.get(&window_id).and_then(|settings| settings.subtitles.as_ref()) {
+            if let Err(error) = subtitles.draw(canvas) {
+                trace::sdl_error("draw subtitles", error);
+            }
+        }
+
+        let start = Instant::now();
+        canvas.present();
+        let presented_at = Instant::now();
+        self.frames.increment();
+
+        let settings = self.window(window_id);
+        let info = PresentInfo {
+            window_id,
+            frame: settings.frames_presented,
+            presented_at,
+            present_duration: presented_at - start,
+            frame_interval: settings.last_present.map(|last| presented_at - last),
+        };
+        settings.frames_presented += 1;
+        settings.last_present = Some(presented_at);
+
+        for callback in &mut settings.present_callbacks {
+            callback(&info);
+        }
+        settings.frame_times.record(canvas, &info);
+    }
+}
+
+impl Sdl2Mt {
+    /// Presents the window's canvas on the UI thread, then runs the window's
+    /// `on_present` callbacks. Frames presented by calling `canvas.present()`
+    /// directly inside a lambda are not seen by those callbacks.
+    ///
+    /// While the application is in the background, frames are dropped instead;
+    /// see `lifecycle_events`. The same goes for while `set_auto_pause` pauses rendering.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn present(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.send_message(Priority::Normal, Present(window_id))
+    }
+
+    /// Fills the window's canvas with a color, e.g. before drawing a frame. Like `present`,
+    /// this is sent to the UI thread as plain data, without allocating a closure.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn clear(&self, window_id: WindowId, color: Color) -> Result<(), Sdl2MtError> {
+        self.send_message(Priority::Normal, Clear(window_id, color))
+    }
+
+    /// Fills the window's canvas with a color and presents it, in one message.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn present_color(&self, window_id: WindowId, color: Color) -> Result<(), Sdl2MtError> {
+        self.send_message(Priority::Normal, PresentColor(window_id, color))
+    }
+
+    /// Registers a callback that runs on the UI thread right after each frame of the
+    /// window is presented with `present`, with timing information about that frame.
+    /// This gives audio/video sync code and frame profilers an anchor to the actual
+    /// display timing.
+    ///
+    /// The callbacks are dropped when the window is removed.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn on_present(&self, window_id: WindowId, callback: Box<SdlPresent>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            if windows.contains_key(&window_id) {
+                state.window(window_id).present_callbacks.push(callback);
+            } else {
+                trace::window_not_found(window_id);
+            }
+        })
+    }
+
+    /// Removes all `on_present` callbacks of a window.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn clear_present_callbacks(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            if let Some(settings) = state.windows.get_mut(&window_id) {
+                settings.present_callbacks.clear();
+            }
+        })
+    }
+}
@@ -0,0 +1,145 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use event::Event;
+use keyboard::Keycode;
+use pixels::Color;
+use rect::Rect;
+use render::WindowCanvas;
+use scene::{Scene, SceneAction};
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How far a task has got, as reported to a `ProgressReporter`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Progress {
+    /// from 0.0 to 1.0
+    pub fraction: f32,
+    pub finished: bool,
+    pub cancelled: bool,
+}
+
+/// Shared between a background task, which reports how far it has got, and the UI thread,
+/// which shows it with `Sdl2Mt::show_progress`. Either side can cancel; the task should check
+/// `is_cancelled` between steps and stop early. Reporters can be cloned and sent to other
+/// threads freely.
+#[derive(Clone, Debug)]
+pub struct ProgressReporter(Arc<Mutex<Progress>>);
+
+impl ProgressReporter {
+    pub fn new() -> ProgressReporter {
+        ProgressReporter(Arc::new(Mutex::new(Progress {
+            fraction: 0.0,
+            finished: false,
+            cancelled: false,
+        })))
+    }
+
+    /// Sets the fraction of the work done, clamped to 0.0 to 1.0.
+    pub fn set(&self, fraction: f32) {
+        self.0.lock().unwrap().fraction = fraction.clamp(0.0, 1.0);
+    }
+
+    /// Marks the task as done, which hides its progress.
+    pub fn finish(&self) {
+        let mut progress = self.0.lock().unwrap();
+        progress.fraction = 1.0;
+        progress.finished = true;
+    }
+
+    /// Asks the task to stop, which also hides its progress.
+    pub fn cancel(&self) {
+        self.0.lock().unwrap().cancelled = true;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.lock().unwrap().cancelled
+    }
+
+    pub fn progress(&self) -> Progress {
+        *self.0.lock().unwrap()
+    }
+}
+
+impl Default for ProgressReporter {
+    fn default() -> ProgressReporter {
+        ProgressReporter::new()
+    }
+}
+
+/// Draws a progress bar across the middle of the canvas.
+pub fn draw_progress_bar(canvas: &mut WindowCanvas, progress: &Progress) {
+    let viewport = canvas.viewport();
+    let width = viewport.width() * 3 / 5;
+    let height = 16;
+    let x = (viewport.width() - width) as i32 / 2;
+    let y = (viewport.height() as i32 - height as i32) / 2;
+
+    canvas.set_draw_color(Color::RGB(64, 64, 64));
+    let _ = canvas.fill_rect(Rect::new(x, y, width, height));
+    let done = (width as f32 * progress.fraction) as u32;
+    if done > 0 {
+        canvas.set_draw_color(Color::RGB(80, 160, 255));
+        let _ = canvas.fill_rect(Rect::new(x, y, done, height));
+    }
+}
+
+/// The overlay scene that `show_progress` pushes.
+struct ProgressOverlay<R> {
+    reporter: ProgressReporter,
+    render: R,
+}
+
+impl<R> Scene for ProgressOverlay<R>
+    where R: FnMut(&mut WindowCanvas, &Progress) + Send
+{
+    fn event(&mut self, event: &Event) -> SceneAction {
+        if let Event::KeyDown { keycode: Some(Keycode::Escape), .. } = *event {
+            self.reporter.cancel();
+        }
+        SceneAction::None
+    }
+
+    fn update(&mut self, _elapsed: Duration) -> SceneAction {
+        let progress = self.reporter.progress();
+        if progress.finished || progress.cancelled {
+            SceneAction::Pop
+        } else {
+            SceneAction::None
+        }
+    }
+
+    fn render(&mut self, canvas: &mut WindowCanvas) {
+        let progress = self.reporter.progress();
+        (self.render)(canvas, &progress);
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
+
+impl Sdl2Mt {
+    /// Shows a progress bar over the window's scenes until the reporter is finished or
+    /// cancelled. Pressing Escape in the window cancels it. The bar is an overlay scene on
+    /// the window's scene stack, and removes itself when it is done.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn show_progress(&self, window_id: WindowId, reporter: &ProgressReporter) -> Result<(), Sdl2MtError> {
+        self.show_progress_with(window_id, reporter, draw_progress_bar)
+    }
+
+    /// Like `show_progress`, but draws the progress with `render`, e.g. as part of a
+    /// loading screen.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn show_progress_with<R>(&self, window_id: WindowId, reporter: &ProgressReporter, render: R) -> Result<(), Sdl2MtError>
+        where R: FnMut(&mut WindowCanvas, &Progress) + Send + 'static
+    {
+        self.push_scene(window_id, Box::new(ProgressOverlay {
+            reporter: reporter.clone(),
+            render,
+        }))
+    }
+}
@@ -0,0 +1,201 @@
+#[cfg(not(feature = "crossbeam-channel"))]
+use {Priority, Sdl2Message, Sdl2MtError};
+#[cfg(not(feature = "crossbeam-channel"))]
+use spy::HandleId;
+use trace::Queued;
+#[cfg(not(feature = "crossbeam-channel"))]
+use trace;
+#[cfg(not(feature = "crossbeam-channel"))]
+use wakeup::Wakeup;
+
+#[cfg(not(feature = "crossbeam-channel"))]
+use std::collections::VecDeque;
+#[cfg(not(feature = "crossbeam-channel"))]
+use std::sync::{Condvar, Mutex};
+
+#[cfg(feature = "crossbeam-channel")]
+pub use crossbeam_queue::MessageQueue;
+
+/// What submitting a message does when the UI thread's queue is full.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Backpressure {
+    /// wait until there is room in the queue
+    Block,
+    /// fail with `Sdl2MtError::QueueFull`
+    Error,
+    /// make room by dropping the oldest coalescable message (see `run_coalesced`),
+    /// and wait like `Block` if there is none. With the `crossbeam-channel` feature,
+    /// this always waits like `Block`.
+    DropOldest,
+}
+
+#[cfg(not(feature = "crossbeam-channel"))]
+struct Entry {
+    queued: Queued,
+    coalescable: bool,
+}
+
+#[cfg(not(feature = "crossbeam-channel"))]
+struct Inner {
+    /// each lane holds up to the queue's capacity, except that control messages always fit
+    high: VecDeque<Queued>,
+    normal: VecDeque<Entry>,
+    closed: bool,
+}
+
+#[cfg(not(feature = "crossbeam-channel"))]
+/// The queue of messages waiting for the UI thread.
+pub struct MessageQueue {
+    inner: Mutex<Inner>,
+    not_full: Condvar,
+    capacity: usize,
+    backpressure: Backpressure,
+    pub wakeup: Wakeup,
+}
+
+pub enum Popped {
+    Message(Queued),
+    Empty,
+    Closed,
+}
+
+#[cfg(not(feature = "crossbeam-channel"))]
+impl MessageQueue {
+    pub fn new(capacity: usize, backpressure: Backpressure) -> MessageQueue {
+        MessageQueue {
+            inner: Mutex::new(Inner {
+                high: VecDeque::new(),
+                normal: VecDeque::new(),
+                closed: false,
+            }),
+            not_full: Condvar::new(),
+            capacity,
+            backpressure,
+            wakeup: Wakeup::new(),
+        }
+    }
+
+    /// Adds a message to the queue, following the queue's backpressure policy when it is full.
+    pub fn push(&self, priority: Priority, origin: HandleId, message: Sdl2Message, coalescable: bool) -> Result<(), Sdl2MtError> {
+        self.push_with(priority, origin, message, coalescable, Some(self.backpressure))
+    }
+
+    /// Adds a message to the queue, failing instead of waiting when it is full.
+    pub fn try_push(&self, origin: HandleId, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.push_with(Priority::Normal, origin, message, false, Some(Backpressure::Error))
+    }
+
+    /// Adds a control message, like `Exit`, to the high priority lane. Only the crate itself
+    /// sends these, and never more than a few at a time, so they are let in even when the
+    /// lane is full, and never wait.
+    pub fn push_control(&self, origin: HandleId, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.push_with(Priority::High, origin, message, false, None)
+    }
+
+    /// Adds a message to its lane; `backpressure` is `None` for control messages, which always fit.
+    fn push_with(&self, priority: Priority, origin: HandleId, message: Sdl2Message, coalescable: bool, backpressure: Option<Backpressure>) -> Result<(), Sdl2MtError> {
+        // messages are only ever dropped after the lock is released, since dropping
+        // a message can run arbitrary code, like the destructors of captured values.
+        let mut evicted = None;
+        let queued = Queued::new(priority, origin, message);
+        let result = {
+            let mut inner = self.inner.lock().unwrap();
+            loop {
+                if inner.closed {
+                    break Err(Sdl2MtError::UiThreadExited);
+                }
+
+                let waiting = match priority {
+                    Priority::High => inner.high.len(),
+                    Priority::Normal => inner.normal.len(),
+                };
+                if backpressure.is_none() || waiting < self.capacity {
+                    match priority {
+                        Priority::High => inner.high.push_back(queued),
+                        Priority::Normal => inner.normal.push_back(Entry { queued, coalescable }),
+                    }
+                    break Ok(());
+                }
+
+                match backpressure {
+                    Some(Backpressure::Error) => break Err(Sdl2MtError::QueueFull),
+                    // only normal priority messages can be coalescable
+                    Some(Backpressure::DropOldest) if priority == Priority::Normal => {
+                        if let Some(index) = inner.normal.iter().position(|entry| entry.coalescable) {
+                            evicted = inner.normal.remove(index);
+                            trace::dropped("the queue was full", 1);
+                            continue;
+                        }
+                    },
+                    _ => {},
+                }
+
+                inner = self.not_full.wait(inner).unwrap();
+            }
+        };
+
+        match result {
+            Ok(()) => self.wakeup.wake(),
+            Err(ref error) => trace::rejected(error),
+        }
+        drop(evicted);
+        result
+    }
+
+    fn pop_locked(&self, inner: &mut Inner) -> Option<Queued> {
+        if let Some(message) = inner.high.pop_front() {
+            self.not_full.notify_all();
+            return Some(message);
+        }
+
+        // both lanes wait on the same condition, so everyone gets to check their own lane
+        inner.normal.pop_front().map(|entry| {
+            self.not_full.notify_all();
+            entry.queued
+        })
+    }
+
+    /// Takes the next message off the queue, without waiting.
+    pub fn try_pop(&self) -> Popped {
+        let mut inner = self.inner.lock().unwrap();
+        match self.pop_locked(&mut inner) {
+            Some(message) => Popped::Message(message),
+            None if inner.closed => Popped::Closed,
+            None => Popped::Empty,
+        }
+    }
+
+    /// Takes every message that is currently waiting off the queue, high priority messages first.
+    pub fn pop_all(&self, batch: &mut Vec<Queued>) {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.high.is_empty() && inner.normal.is_empty() {
+                return;
+            }
+            batch.extend(inner.high.drain(..));
+            batch.extend(inner.normal.drain(..).map(|entry| entry.queued));
+        }
+        self.not_full.notify_all();
+    }
+
+    /// The number of messages waiting in the queue.
+    pub fn len(&self) -> usize {
+        let inner = self.inner.lock().unwrap();
+        inner.high.len() + inner.normal.len()
+    }
+
+    /// Refuses any further messages and drops the ones that are still waiting,
+    /// which also drops their reply channels so that no calling thread is left waiting.
+    pub fn close(&self) {
+        let (high, normal) = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.closed = true;
+            (inner.high.split_off(0), inner.normal.split_off(0))
+        };
+
+        self.not_full.notify_all();
+        trace::dropped("the UI thread exited", high.len() + normal.len());
+        drop(high);
+        drop(normal);
+    }
+}
@@ -0,0 +1,103 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use SdlLambda;
+use timers::{TimerAction, TimerId};
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+struct Throttle {
+    last_run: Instant,
+    /// the most recent lambda submitted while throttled, which runs at the end of the interval
+    pending: Option<Box<SdlLambda>>,
+}
+
+/// Bookkeeping for `run_debounced` and `run_throttled`, by key.
+pub struct RateLimits {
+    debounced: HashMap<String, TimerId>,
+    throttled: HashMap<String, Throttle>,
+}
+
+impl RateLimits {
+    pub fn new() -> RateLimits {
+        RateLimits {
+            debounced: HashMap::new(),
+            throttled: HashMap::new(),
+        }
+    }
+
+    /// The number of keys with a debounced or throttled lambda waiting.
+    pub fn len(&self) -> usize {
+        self.debounced.len() + self.throttled.values().filter(|throttle| throttle.pending.is_some()).count()
+    }
+}
+
+impl Sdl2Mt {
+    /// Executes a lambda function on the UI thread once no other lambda has been submitted
+    /// with the same key for `quiet_period`. Every call restarts the wait, and only the most
+    /// recent lambda runs. Useful for expensive work that should only happen once a burst of
+    /// input is over, like re-laying out a window while it is being resized.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn run_debounced<IntoString: Into<String>>(&self, key: IntoString, quiet_period: Duration, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        let key = key.into();
+        let deadline = self.shared.clock.now() + quiet_period;
+        self.run_builtin(move |_sdl, _windows, state| {
+            if let Some(previous) = state.rate_limits.debounced.remove(&key) {
+                state.timers.cancel(previous);
+            }
+
+            let id = TimerId::next();
+            let mut lambda = Some(lambda);
+            let timer_key = key.clone();
+            state.timers.add(id, deadline, None, TimerAction::Builtin(Box::new(move |sdl, windows, state| {
+                state.rate_limits.debounced.remove(&timer_key);
+                if let Some(mut lambda) = lambda.take() {
+                    lambda(sdl, windows);
+                }
+            })));
+            state.rate_limits.debounced.insert(key, id);
+        })
+    }
+
+    /// Executes a lambda function on the UI thread at most once per `min_interval` for a key.
+    /// The first lambda runs right away; lambdas submitted before the interval is over replace
+    /// each other, and the last one runs at the end of the interval. This keeps a high frequency
+    /// producer, like a data feed at 1 kHz, from flooding the UI thread with redraws while
+    /// still showing the latest data.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn run_throttled<IntoString: Into<String>>(&self, key: IntoString, min_interval: Duration, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        let key = key.into();
+        self.run_builtin(move |sdl, windows, state| {
+            let now = state.clock.now();
+            let mut lambda = lambda;
+
+            let next_run = match state.rate_limits.throttled.get_mut(&key) {
+                Some(throttle) if now < throttle.last_run + min_interval => {
+                    let already_scheduled = throttle.pending.is_some();
+                    throttle.pending = Some(lambda);
+                    if already_scheduled {
+                        return;
+                    }
+                    throttle.last_run + min_interval
+                },
+                _ => {
+                    state.rate_limits.throttled.insert(key, Throttle { last_run: now, pending: None });
+                    lambda(sdl, windows);
+                    return;
+                }
+            };
+
+            let timer_key = key.clone();
+            state.timers.add(TimerId::next(), next_run, None, TimerAction::Builtin(Box::new(move |sdl, windows, state| {
+                if let Some(throttle) = state.rate_limits.throttled.get_mut(&timer_key) {
+                    if let Some(mut lambda) = throttle.pending.take() {
+                        throttle.last_run = state.clock.now();
+                        lambda(sdl, windows);
+                    }
+                }
+            })));
+        })
+    }
+}
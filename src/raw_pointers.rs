@@ -0,0 +1,53 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use sys::render::SDL_Renderer;
+use sys::video::SDL_Window;
+
+/// The addresses of the SDL objects behind a window, returned by `Sdl2Mt::raw_sdl_pointers`.
+/// They are plain integers, so that they can be sent between threads, but they may only be
+/// turned back into pointers and used on the UI thread.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RawSdlPointers {
+    /// the address of the `SDL_Window`
+    pub window: usize,
+    /// the address of the window's `SDL_Renderer`
+    pub renderer: usize,
+}
+
+impl RawSdlPointers {
+    pub fn window_ptr(&self) -> *mut SDL_Window {
+        self.window as *mut SDL_Window
+    }
+
+    pub fn renderer_ptr(&self) -> *mut SDL_Renderer {
+        self.renderer as *mut SDL_Renderer
+    }
+}
+
+impl Sdl2Mt {
+    /// Returns the addresses of the raw `SDL_Window` and `SDL_Renderer` of a window, or None
+    /// if there is no window with that ID, for C libraries like video decoders or capture SDKs
+    /// that need the raw SDL objects.
+    ///
+    /// This function executes synchronously.
+    ///
+    /// # Safety
+    ///
+    /// SDL is not thread safe, and this crate assumes that it is the only one touching these
+    /// objects. The caller must make sure that:
+    ///
+    /// - the pointers are only used on the UI thread, e.g. inside `run_on_ui_thread`, and
+    ///   so are any C libraries that are handed the pointers,
+    /// - they are not used after the window is destroyed, which frees both objects,
+    /// - the objects are not destroyed, or changed in ways that break the `WindowCanvas`
+    ///   this crate keeps for them, like replacing the renderer.
+    pub unsafe fn raw_sdl_pointers(&self, window_id: WindowId) -> Result<Option<RawSdlPointers>, Sdl2MtError> {
+        self.call(move |_sdl, windows| {
+            windows.get(&window_id).map(|canvas| RawSdlPointers {
+                window: canvas.window().raw() as usize,
+                renderer: canvas.raw() as usize,
+            })
+        })
+    }
+}
@@ -0,0 +1,237 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use error;
+use get_error;
+use pixels::PixelFormatEnum;
+use rect::Rect;
+use render::{Texture, TextureCreator, WindowCanvas};
+use state::UiState;
+use sys;
+use trace;
+use video::WindowContext;
+
+use std::collections::HashMap;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Identifies an offscreen texture created with `create_render_target`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TargetId(usize);
+
+static NEXT_TARGET_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A target texture, kept in the `WindowState` of the window whose renderer created it.
+///
+/// sdl2's `Texture` borrows its `TextureCreator`, so the two can't be stored side by side;
+/// the raw texture is kept instead. The creator keeps the renderer alive until the texture
+/// has been destroyed, even if the window is dropped first.
+pub struct RenderTarget {
+    creator: TextureCreator<WindowContext>,
+    raw: *mut sys::render::SDL_Texture,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RenderTarget {
+    /// A texture of the canvas' renderer that can be drawn to.
+    pub fn new(canvas: &WindowCanvas, width: u32, height: u32) -> Result<RenderTarget, String> {
+        if !canvas.render_target_supported() {
+            return Err("render targets are not supported".to_string());
+        }
+        let creator = canvas.texture_creator();
+        let texture = creator.create_texture_target(PixelFormatEnum::ARGB8888, width, height)
+            .map_err(|error| error.to_string())?;
+        let raw = texture.raw();
+        mem::forget(texture);
+        Ok(RenderTarget {
+            creator,
+            raw,
+            width,
+            height,
+        })
+    }
+
+    /// Draws with the canvas pointed at this texture, then points it back at `previous`,
+    /// the target it drew to before.
+    pub fn draw<F: FnOnce(&mut WindowCanvas)>(&self, canvas: &mut WindowCanvas, previous: *mut sys::render::SDL_Texture, draw: F) -> Result<(), String> {
+        set_target(canvas, self.raw)?;
+        draw(canvas);
+        set_target(canvas, previous)
+    }
+
+    /// Lends the texture out as sdl2's own type.
+    pub fn with_texture<T, F: FnOnce(&mut Texture) -> T>(&self, f: F) -> T {
+        let mut texture = unsafe { self.creator.raw_create_texture(self.raw) };
+        let result = f(&mut texture);
+        // still owned by this target
+        mem::forget(texture);
+        result
+    }
+}
+
+impl Drop for RenderTarget {
+    fn drop(&mut self) {
+        unsafe { sys::render::SDL_DestroyTexture(self.raw) }
+    }
+}
+
+pub fn set_target(canvas: &mut WindowCanvas, raw: *mut sys::render::SDL_Texture) -> Result<(), String> {
+    if unsafe { sys::render::SDL_SetRenderTarget(canvas.raw(), raw) } == 0 {
+        Ok(())
+    } else {
+        Err(get_error())
+    }
+}
+
+impl UiState {
+    /// The texture the window draws to, set with `set_render_target`, or null for the window.
+    pub fn active_target_raw(&self, window_id: WindowId) -> *mut sys::render::SDL_Texture {
+        self.windows.get(&window_id)
+            .and_then(|settings| settings.active_target.and_then(|active| settings.targets.get(&active)))
+            .map_or(ptr::null_mut(), |active| active.raw)
+    }
+
+    fn target_window(&self, target: TargetId) -> Option<WindowId> {
+        self.windows.iter()
+            .find(|&(_, settings)| settings.targets.contains_key(&target))
+            .map(|(&window_id, _)| window_id)
+    }
+
+    /// Switches the window back from the target set with `set_render_target`, before it
+    /// is presented.
+    pub fn reset_render_target(&mut self, canvas: &mut WindowCanvas, window_id: WindowId) {
+        let active = self.windows.get_mut(&window_id).and_then(|settings| settings.active_target.take());
+        if active.is_some() {
+            if let Err(error) = set_target(canvas, ptr::null_mut()) {
+                trace::sdl_error("SDL_SetRenderTarget", error);
+            }
+        }
+    }
+
+    /// Draws with the canvas pointed at the target, then points it back at what it drew to
+    /// before.
+    fn draw_to_target<F>(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, target: TargetId, draw: F) -> Result<(), String>
+        where F: FnOnce(&mut WindowCanvas)
+    {
+        let window_id = match self.target_window(target) {
+            Some(window_id) => window_id,
+            None => return Ok(()),
+        };
+        let previous = self.active_target_raw(window_id);
+        match (canvases.get_mut(&window_id), self.windows.get(&window_id)) {
+            (Some(canvas), Some(settings)) => settings.targets[&target].draw(canvas, previous, draw),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Creates an offscreen texture of `width` by `height` pixels for the window's renderer,
+    /// which can be drawn to with `with_render_target` or `set_render_target`, and drawn
+    /// onto the window with `copy_target_to_window`. Use it for post-processing, for layers
+    /// that are expensive to draw and change rarely, or for thumbnails.
+    ///
+    /// The texture is owned by the UI thread, and destroyed with `destroy_render_target`,
+    /// or when the window is closed. Returns `Sdl2MtError::WindowNotFound` if the window
+    /// doesn't exist, and `Sdl2MtError::SdlError` if its renderer doesn't support render targets.
+    ///
+    /// This function executes synchronously.
+    pub fn create_render_target(&self, window_id: WindowId, width: u32, height: u32) -> Result<TargetId, Sdl2MtError> {
+        self.call_builtin(move |_sdl, windows, state| {
+            let canvas = match windows.get_mut(&window_id) {
+                Some(canvas) => canvas,
+                None => return Err(Sdl2MtError::WindowNotFound(window_id)),
+            };
+            let render_target = RenderTarget::new(canvas, width, height)
+                .map_err(|error| error::sdl_error("create_render_target", error))?;
+            let target = TargetId(NEXT_TARGET_ID.fetch_add(1, Ordering::Relaxed));
+            state.window(window_id).targets.insert(target, render_target);
+            Ok(target)
+        }).and_then(|result| result)
+    }
+
+    /// Destroys a render target. If the window is drawing to it, it draws to itself again.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn destroy_render_target(&self, target: TargetId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            let window_id = match state.target_window(target) {
+                Some(window_id) => window_id,
+                None => return,
+            };
+            if state.windows[&window_id].active_target == Some(target) {
+                if let Some(canvas) = windows.get_mut(&window_id) {
+                    state.reset_render_target(canvas, window_id);
+                }
+            }
+            state.window(window_id).targets.remove(&target);
+        })
+    }
+
+    /// Runs `draw` with the window's canvas pointed at the target, so everything it draws
+    /// ends up in the texture instead of the window.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn with_render_target<F>(&self, target: TargetId, draw: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut WindowCanvas) + Send + 'static
+    {
+        self.run_builtin(move |_sdl, windows, state| {
+            if let Err(error) = state.draw_to_target(windows, target, draw) {
+                trace::sdl_error("SDL_SetRenderTarget", error);
+            }
+        })
+    }
+
+    /// Points the window's drawing at the target, or back at the window with None, for the
+    /// drawing methods like `fill_rects` and `clear`, and lambdas, that follow. `present`
+    /// always presents the window itself, and points drawing back at it.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_render_target(&self, window_id: WindowId, target: Option<TargetId>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            let canvas = match windows.get_mut(&window_id) {
+                Some(canvas) => canvas,
+                None => return trace::window_not_found(window_id),
+            };
+            let raw = match target {
+                Some(target) => match state.windows.get(&window_id).and_then(|settings| settings.targets.get(&target)) {
+                    Some(render_target) => render_target.raw,
+                    None => return,
+                },
+                None => ptr::null_mut(),
+            };
+            match set_target(canvas, raw) {
+                Ok(()) => state.window(window_id).active_target = target,
+                Err(error) => trace::sdl_error("SDL_SetRenderTarget", error),
+            }
+        })
+    }
+
+    /// Draws the target's texture onto its window, stretched to `destination`, or to the
+    /// whole window with None. This draws to the window even while `set_render_target`
+    /// points drawing at a target.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn copy_target_to_window(&self, target: TargetId, destination: Option<Rect>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            let window_id = match state.target_window(target) {
+                Some(window_id) => window_id,
+                None => return,
+            };
+            let active = state.active_target_raw(window_id);
+            let (canvas, settings) = match (windows.get_mut(&window_id), state.windows.get(&window_id)) {
+                (Some(canvas), Some(settings)) => (canvas, settings),
+                _ => return,
+            };
+
+            let result = set_target(canvas, ptr::null_mut())
+                .and_then(|_| settings.targets[&target].with_texture(|texture| canvas.copy(texture, None, destination)))
+                .and_then(|_| set_target(canvas, active));
+            if let Err(error) = result {
+                trace::sdl_error("copy_target_to_window", error);
+            }
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use Sdl2MtError;
+use synchronous;
+use trace;
+
+use std::sync::{Mutex, mpsc};
+use std::time::Duration;
+
+/// The sending half of a reply channel. The UI thread sends the reply along with the
+/// sender itself, so that the caller gets the whole channel back and can reuse it for
+/// its next round-trip, while the UI thread is still the only one holding a sender
+/// until it replies. If the UI thread drops the message without replying, the channel
+/// is disconnected and the caller returns `UiThreadExited` instead of hanging.
+pub struct ReplySender<T>(mpsc::Sender<(T, ReplySender<T>)>);
+
+/// The receiving half of a reply channel.
+pub type ReplyReceiver<T> = mpsc::Receiver<(T, ReplySender<T>)>;
+
+impl<T> ReplySender<T> {
+    pub fn send(self, reply: T) {
+        // the receiver may have given up waiting, in which case the reply is discarded
+        let tx = self.0.clone();
+        if tx.send((reply, self)).is_err() {
+            trace::reply_discarded();
+        }
+    }
+}
+
+/// Caches a reply channel for a handle's synchronous round-trips, so that calling
+/// e.g. `handle_ui_events` every frame doesn't set up and tear down a channel each time.
+///
+/// Each clone of a handle gets its own cache. A handle that is used from several threads
+/// at once falls back to a fresh channel for whoever finds the cache empty.
+pub struct ReplyChannel<T> {
+    cached: Mutex<Option<(ReplySender<T>, ReplyReceiver<T>)>>,
+}
+
+impl<T> ReplyChannel<T> {
+    pub fn new() -> ReplyChannel<T> {
+        ReplyChannel {
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Takes the cached channel, or creates a new one if there is none.
+    pub fn take(&self) -> (ReplySender<T>, ReplyReceiver<T>) {
+        match self.cached.lock().unwrap().take() {
+            Some(channel) => channel,
+            None => {
+                let (tx, rx) = mpsc::channel();
+                (ReplySender(tx), rx)
+            },
+        }
+    }
+
+    /// Waits for the reply and puts the channel back into the cache.
+    pub fn recv(&self, rx: ReplyReceiver<T>) -> Result<T, Sdl2MtError> {
+        let (reply, tx) = synchronous::recv(&rx, None)?;
+        *self.cached.lock().unwrap() = Some((tx, rx));
+        Ok(reply)
+    }
+
+    /// Like `recv`, but gives up after `timeout`. A channel that timed out is not reused,
+    /// since the late reply would otherwise be mistaken for the reply to the next call.
+    pub fn recv_timeout(&self, rx: ReplyReceiver<T>, timeout: Duration) -> Result<T, Sdl2MtError> {
+        let (reply, tx) = synchronous::recv(&rx, Some(timeout))?;
+        *self.cached.lock().unwrap() = Some((tx, rx));
+        Ok(reply)
+    }
+}
+
+impl<T> Clone for ReplyChannel<T> {
+    fn clone(&self) -> ReplyChannel<T> {
+        ReplyChannel::new()
+    }
+}
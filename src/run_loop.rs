@@ -0,0 +1,67 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use event::Event;
+use render::WindowCanvas;
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How long `run_until_quit` sleeps between looking for new events.
+const POLL_INTERVAL: Duration = Duration::from_millis(15);
+
+/// What `run_until_quit` does after its handler has seen an event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LoopControl {
+    /// the event was handled
+    Handled,
+    /// the event was not handled, and stays queued for other event handlers
+    Unhandled,
+    /// the event was handled, and `run_until_quit` should return
+    Stop,
+}
+
+impl Sdl2Mt {
+    /// Runs an event handler on the UI thread for every event until a `Quit` event arrives,
+    /// or the handler returns `LoopControl::Stop`. This is the loop that most programs would
+    /// otherwise write around `handle_ui_events` themselves. The handler sees the `Quit`
+    /// event before this returns. Events after the one that stopped the loop stay queued.
+    ///
+    /// This function executes synchronously. It will block until the loop stops.
+    pub fn run_until_quit<F>(&self, handler: F) -> Result<(), Sdl2MtError>
+        where F: FnMut(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &Event) -> LoopControl + Send + 'static
+    {
+        let handler = Arc::new(Mutex::new(handler));
+        let stopped = Arc::new(AtomicBool::new(false));
+        loop {
+            let handler = handler.clone();
+            let stop = stopped.clone();
+            self.handle_ui_events(Box::new(move |sdl, windows, event| {
+                if stop.load(Ordering::SeqCst) {
+                    return false;
+                }
+                let control = (*handler.lock().unwrap())(sdl, windows, event);
+                if let Event::Quit { .. } = *event {
+                    stop.store(true, Ordering::SeqCst);
+                }
+                match control {
+                    LoopControl::Handled => true,
+                    LoopControl::Unhandled => false,
+                    LoopControl::Stop => {
+                        stop.store(true, Ordering::SeqCst);
+                        true
+                    },
+                }
+            }))?;
+
+            if stopped.load(Ordering::SeqCst) {
+                return Ok(());
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(any(feature = "image", feature = "mixer", feature = "ttf"))]
+use Sdl2Mt;
+#[cfg(any(feature = "image", feature = "mixer", feature = "ttf"))]
+use Sdl2MtError;
+#[cfg(feature = "ttf")]
+use WindowId;
+#[cfg(feature = "ttf")]
+use error;
+#[cfg(feature = "image")]
+use image;
+#[cfg(feature = "mixer")]
+use mixer;
+#[cfg(feature = "ttf")]
+use render::WindowCanvas;
+#[cfg(any(feature = "image", feature = "mixer", feature = "ttf"))]
+use trace;
+#[cfg(feature = "ttf")]
+use ttf::{self, Sdl2TtfContext};
+
+#[cfg(feature = "ttf")]
+use std::collections::HashMap;
+
+/// The SDL2 satellite libraries enabled through this crate's features, initialized when the
+/// UI thread starts and kept until it exits. Libraries that can't be initialized are
+/// reported through `errors()`, and left out.
+pub struct Satellites {
+    #[cfg(feature = "image")]
+    image: Option<image::Sdl2ImageContext>,
+    #[cfg(feature = "mixer")]
+    mixer: Option<mixer::Sdl2MixerContext>,
+    /// fonts borrow the context, so it lives as long as the program to let lambdas keep them
+    #[cfg(feature = "ttf")]
+    ttf: Option<&'static Sdl2TtfContext>,
+}
+
+impl Satellites {
+    pub fn init() -> Satellites {
+        Satellites {
+            #[cfg(feature = "image")]
+            image: image::init(image::INIT_PNG | image::INIT_JPG | image::INIT_TIF | image::INIT_WEBP)
+                .map_err(|error| trace::sdl_error("IMG_Init", error))
+                .ok(),
+            #[cfg(feature = "mixer")]
+            mixer: mixer::init(mixer::INIT_FLAC | mixer::INIT_MOD | mixer::INIT_MP3 | mixer::INIT_OGG)
+                .map_err(|error| trace::sdl_error("Mix_Init", error))
+                .ok(),
+            #[cfg(feature = "ttf")]
+            ttf: ttf::init()
+                .map(|context| &*Box::leak(Box::new(context)))
+                .map_err(|error| trace::sdl_error("TTF_Init", error.to_string()))
+                .ok(),
+        }
+    }
+}
+
+#[cfg(any(feature = "image", feature = "mixer", feature = "ttf"))]
+impl Sdl2Mt {
+    /// Whether `SDL2_image` was initialized on the UI thread, so that lambdas can load
+    /// images with `image::LoadTexture` and `image::LoadSurface`. Needs the `image` feature.
+    ///
+    /// This function executes synchronously.
+    #[cfg(feature = "image")]
+    pub fn image_initialized(&self) -> Result<bool, Sdl2MtError> {
+        self.call_builtin(|_sdl, _windows, state| state.satellites.image.is_some())
+    }
+
+    /// Whether `SDL2_mixer` was initialized on the UI thread. Lambdas still open the audio
+    /// device themselves, with `sdl.audio()` and `mixer::open_audio`. Needs the `mixer` feature.
+    ///
+    /// This function executes synchronously.
+    #[cfg(feature = "mixer")]
+    pub fn mixer_initialized(&self) -> Result<bool, Sdl2MtError> {
+        self.call_builtin(|_sdl, _windows, state| state.satellites.mixer.is_some())
+    }
+
+    /// Runs a function on the UI thread with the `SDL2_ttf` context, e.g. to load fonts and
+    /// render text, and hands its return value back. The context lives as long as the
+    /// program, so fonts loaded from it can be kept between lambdas on the UI thread.
+    /// Returns `Sdl2MtError::SdlError` if `SDL2_ttf` couldn't be initialized. Needs the
+    /// `ttf` feature.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// function has completed.
+    #[cfg(feature = "ttf")]
+    pub fn with_ttf<T, F>(&self, function: F) -> Result<T, Sdl2MtError>
+        where T: Send + 'static,
+              F: FnOnce(&'static Sdl2TtfContext, &mut HashMap<WindowId, WindowCanvas>) -> T + Send + 'static
+    {
+        self.call_builtin(move |_sdl, windows, state| match state.satellites.ttf {
+            Some(ttf) => Ok(function(ttf, windows)),
+            None => Err(error::sdl_error("with_ttf", "SDL2_ttf isn't initialized")),
+        }).and_then(|result| result)
+    }
+}
@@ -0,0 +1,293 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use camera::Camera;
+use dispatch;
+use event::Event;
+#[cfg(target_os = "ios")]
+use ios;
+use render::WindowCanvas;
+use state::UiState;
+use timers::{TimerAction, TimerId};
+use trace;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How often scenes are updated and rendered, unless `set_scene_frame_interval` says otherwise.
+pub const DEFAULT_SCENE_FRAME_INTERVAL: Duration = Duration::from_nanos(16_666_667);
+
+/// One screen of an application, like a menu, the game itself, or a pause overlay, pushed
+/// onto a window's scene stack with `Sdl2Mt::push_scene`. Only the scene on top of the stack
+/// gets events and updates. All hooks run on the UI thread.
+pub trait Scene: Send {
+    /// Called for every event sent to the scene's window, and for every event that isn't
+    /// sent to a window, like `Quit`. Events still reach event handlers and forwarders.
+    fn event(&mut self, _event: &Event) -> SceneAction {
+        SceneAction::None
+    }
+
+    /// Called once per frame, with the time since the previous frame.
+    fn update(&mut self, _elapsed: Duration) -> SceneAction {
+        SceneAction::None
+    }
+
+    /// Draws the scene. The canvas is presented after all visible scenes have been drawn.
+    fn render(&mut self, canvas: &mut WindowCanvas);
+
+    /// Whether the scene below this one is drawn first, e.g. for a pause menu over the game.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// Called with the window's camera when the scene is pushed, and whenever
+    /// `Sdl2Mt::set_camera` changes it, for scenes that draw world coordinates.
+    fn camera_changed(&mut self, _camera: &Camera) {}
+}
+
+/// What happens to the scene stack after a scene's hook returns.
+pub enum SceneAction {
+    None,
+    /// puts a new scene on top, which pauses this one until it is popped
+    Push(Box<dyn Scene>),
+    /// removes this scene, resuming the one below
+    Pop,
+    /// removes this scene, and puts a new one in its place
+    Replace(Box<dyn Scene>),
+}
+
+/// What runs a window's scene frames.
+enum FrameDriver {
+    Timer(TimerId),
+    /// UIKit's display link, through `SDL_iPhoneSetAnimationCallback`
+    #[cfg(target_os = "ios")]
+    DisplayLink,
+}
+
+/// The scenes of a window, kept in its `WindowState`.
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+    /// what runs the frames, while there are scenes
+    driver: Option<FrameDriver>,
+    last_frame: Option<Instant>,
+    interval: Duration,
+    /// the window's camera, for scenes that are pushed later
+    camera: Camera,
+}
+
+impl Default for SceneStack {
+    fn default() -> SceneStack {
+        SceneStack {
+            scenes: vec![],
+            driver: None,
+            last_frame: None,
+            interval: DEFAULT_SCENE_FRAME_INTERVAL,
+            camera: Camera::default(),
+        }
+    }
+}
+
+impl SceneStack {
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    pub fn camera_changed(&mut self, camera: &Camera) {
+        self.camera = *camera;
+        for scene in &mut self.scenes {
+            scene.camera_changed(camera);
+        }
+    }
+
+    fn apply(&mut self, action: SceneAction) {
+        match action {
+            SceneAction::None => {},
+            SceneAction::Push(mut scene) => {
+                scene.camera_changed(&self.camera);
+                self.scenes.push(scene);
+            },
+            SceneAction::Pop => {
+                self.scenes.pop();
+            },
+            SceneAction::Replace(mut scene) => {
+                scene.camera_changed(&self.camera);
+                self.scenes.pop();
+                self.scenes.push(scene);
+            },
+        }
+    }
+
+    fn render(&mut self, canvas: &mut WindowCanvas) {
+        let mut bottom = self.scenes.len();
+        while bottom > 0 {
+            bottom -= 1;
+            if !self.scenes[bottom].is_overlay() {
+                break;
+            }
+        }
+        for scene in &mut self.scenes[bottom..] {
+            scene.render(canvas);
+        }
+    }
+}
+
+impl UiState {
+    /// Changes a window's scene stack, and starts running frames if it was empty.
+    fn change_scenes(&mut self, canvases: &HashMap<WindowId, WindowCanvas>, window_id: WindowId, action: SceneAction) {
+        if !canvases.contains_key(&window_id) {
+            return;
+        }
+        let now = self.clock.now();
+        let stack = &mut self.window(window_id).scenes;
+        stack.apply(action);
+        if stack.scenes.is_empty() || stack.driver.is_some() {
+            return;
+        }
+        let interval = stack.interval;
+        stack.last_frame = Some(now);
+
+        // frames follow the display's refreshes, and stop while the application is in the background
+        #[cfg(target_os = "ios")]
+        match ios::start_frames(&canvases[&window_id], window_id, interval) {
+            Ok(()) => {
+                stack.driver = Some(FrameDriver::DisplayLink);
+                return;
+            },
+            Err(error) => trace::sdl_error("SDL_iPhoneSetAnimationCallback", error),
+        }
+
+        let id = TimerId::next();
+        stack.driver = Some(FrameDriver::Timer(id));
+        self.timers.add(id, now + interval, Some(interval), TimerAction::Builtin(Box::new(move |_sdl, windows, state| {
+            if !state.run_scene_frame(windows, window_id) {
+                state.timers.cancel(id);
+            }
+        })));
+    }
+
+    /// Updates, renders and presents the scenes of a window. Returns false once the
+    /// window has no scenes left, or is gone, which stops its frames.
+    fn run_scene_frame(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, window_id: WindowId) -> bool {
+        let now = self.clock.now();
+        let paused = self.paused || self.auto_pause.paused;
+        {
+            let stack = match (canvases.get_mut(&window_id), self.windows.get_mut(&window_id)) {
+                (Some(_), Some(settings)) => &mut settings.scenes,
+                _ => return false,
+            };
+            if stack.scenes.is_empty() {
+                stack.driver = None;
+                return false;
+            }
+            let elapsed = stack.last_frame.map_or(Duration::from_secs(0), |last| now - last);
+            stack.last_frame = Some(now);
+            // the time spent in the background doesn't count towards the next update
+            if paused {
+                return true;
+            }
+
+            let action = match stack.scenes.last_mut() {
+                Some(scene) => scene.update(elapsed),
+                None => SceneAction::None,
+            };
+            stack.apply(action);
+            if let Some(canvas) = canvases.get_mut(&window_id) {
+                stack.render(canvas);
+            }
+        }
+        self.present(canvases, window_id);
+        true
+    }
+
+    /// Runs a scene frame for UIKit's display link, and stops the display link once the
+    /// window has no scenes left.
+    #[cfg(target_os = "ios")]
+    pub fn run_display_link_frame(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, window_id: WindowId) {
+        if !self.run_scene_frame(canvases, window_id) {
+            if let Some(canvas) = canvases.get(&window_id) {
+                ios::stop_frames(canvas);
+            }
+        }
+    }
+
+    /// Hands an event to the top scene of the window it was sent to, or of every window
+    /// if it wasn't sent to one.
+    pub fn scene_event(&mut self, event: &Event) {
+        let target = dispatch::event_window_id(event);
+        for (&window_id, settings) in &mut self.windows {
+            if target.is_some_and(|target| target != window_id) {
+                continue;
+            }
+            let stack = &mut settings.scenes;
+            let action = match stack.scenes.last_mut() {
+                Some(scene) => scene.event(event),
+                None => continue,
+            };
+            stack.apply(action);
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Puts a scene on top of the window's scene stack. While a window has scenes, the UI
+    /// thread updates, renders and presents the top scene once per frame, so that small games
+    /// can be structured as a stack of screens, like menu → game → pause menu. Scenes can
+    /// change the stack from their hooks by returning a `SceneAction`.
+    ///
+    /// Frames are driven by a timer on the UI thread, and stop when the stack is empty. On iOS,
+    /// UIKit's display link drives them instead, through `SDL_iPhoneSetAnimationCallback`, so
+    /// that they line up with the display's refreshes and stop while the application is in the
+    /// background. UIKit calls it on the main thread, so the UI loop has to run there, with
+    /// `Sdl2MtBuilder::synchronous`.
+    /// Windows that are unknown when this message is handled are ignored.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn push_scene(&self, window_id: WindowId, scene: Box<dyn Scene>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| state.change_scenes(windows, window_id, SceneAction::Push(scene)))
+    }
+
+    /// Removes the top scene from the window's scene stack.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn pop_scene(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| state.change_scenes(windows, window_id, SceneAction::Pop))
+    }
+
+    /// Replaces the top scene of the window's scene stack, or pushes the scene if it is empty.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn replace_scene(&self, window_id: WindowId, scene: Box<dyn Scene>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| state.change_scenes(windows, window_id, SceneAction::Replace(scene)))
+    }
+
+    /// Returns how many scenes the window's scene stack holds.
+    ///
+    /// This function executes synchronously.
+    pub fn scene_count(&self, window_id: WindowId) -> Result<usize, Sdl2MtError> {
+        self.call_builtin(move |_sdl, _windows, state| {
+            state.windows.get(&window_id).map_or(0, |settings| settings.scenes.len())
+        })
+    }
+
+    /// Sets how often the window's scenes are updated and rendered. The default is
+    /// 60 times per second.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_scene_frame_interval(&self, window_id: WindowId, interval: Duration) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            if !windows.contains_key(&window_id) {
+                return trace::window_not_found(window_id);
+            }
+            // restarts the frames at the new interval
+            let driver = {
+                let stack = &mut state.window(window_id).scenes;
+                stack.interval = interval;
+                stack.driver.take()
+            };
+            if let Some(FrameDriver::Timer(timer)) = driver {
+                state.timers.cancel(timer);
+            }
+            state.change_scenes(windows, window_id, SceneAction::None);
+        })
+    }
+}
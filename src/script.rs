@@ -0,0 +1,183 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use keyboard::Keycode;
+use mouse::MouseButton;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long `wait_frames` waits for the frames before giving up.
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum Step {
+    Key(Keycode, bool),
+    MouseMove(i32, i32),
+    MouseButton(MouseButton, bool, i32, i32),
+    Resize(u32, u32),
+    Quit,
+    Wait(Duration),
+    WaitFrames(u64),
+}
+
+/// A scripted sequence of input for integration tests, built step by step and played back
+/// with `run`:
+///
+/// ```no_run
+/// # use sdl2_mt::Script;
+/// # use sdl2_mt::keyboard::Keycode;
+/// # let sdlh = sdl2_mt::init();
+/// Script::new().key_press(Keycode::Escape).wait_frames(2).resize(800, 600).run(&sdlh).unwrap();
+/// ```
+///
+/// Every input step is injected into SDL's event queue and pumped before the next step starts,
+/// so event forwarders have seen it by then, and `handle_ui_events` will find it.
+pub struct Script {
+    window: Option<WindowId>,
+    steps: Vec<Step>,
+}
+
+impl Script {
+    pub fn new() -> Script {
+        Script {
+            window: None,
+            steps: vec![],
+        }
+    }
+
+    /// The window the script sends its input to. By default, this is the only window
+    /// there is when the script runs.
+    pub fn window(mut self, window_id: WindowId) -> Script {
+        self.window = Some(window_id);
+        self
+    }
+
+    pub fn key_down(mut self, keycode: Keycode) -> Script {
+        self.steps.push(Step::Key(keycode, true));
+        self
+    }
+
+    pub fn key_up(mut self, keycode: Keycode) -> Script {
+        self.steps.push(Step::Key(keycode, false));
+        self
+    }
+
+    /// Presses and releases a key.
+    pub fn key_press(self, keycode: Keycode) -> Script {
+        self.key_down(keycode).key_up(keycode)
+    }
+
+    pub fn mouse_move(mut self, x: i32, y: i32) -> Script {
+        self.steps.push(Step::MouseMove(x, y));
+        self
+    }
+
+    pub fn mouse_down(mut self, button: MouseButton, x: i32, y: i32) -> Script {
+        self.steps.push(Step::MouseButton(button, true, x, y));
+        self
+    }
+
+    pub fn mouse_up(mut self, button: MouseButton, x: i32, y: i32) -> Script {
+        self.steps.push(Step::MouseButton(button, false, x, y));
+        self
+    }
+
+    /// Moves the mouse to `(x, y)`, then presses and releases a button there.
+    pub fn click(self, button: MouseButton, x: i32, y: i32) -> Script {
+        self.mouse_move(x, y).mouse_down(button, x, y).mouse_up(button, x, y)
+    }
+
+    /// Resizes the window, which makes SDL send the same window events as when the user does it.
+    pub fn resize(mut self, width: u32, height: u32) -> Script {
+        self.steps.push(Step::Resize(width, height));
+        self
+    }
+
+    pub fn quit(mut self) -> Script {
+        self.steps.push(Step::Quit);
+        self
+    }
+
+    /// Sleeps before the next step.
+    pub fn wait(mut self, duration: Duration) -> Script {
+        self.steps.push(Step::Wait(duration));
+        self
+    }
+
+    /// Waits until the window has presented `frames` more frames with `present`.
+    pub fn wait_frames(mut self, frames: u64) -> Script {
+        self.steps.push(Step::WaitFrames(frames));
+        self
+    }
+
+    /// Plays back the script. Returns `Sdl2MtError::Timeout` if a `wait_frames` step
+    /// doesn't see its frames within five seconds.
+    ///
+    /// This function executes synchronously.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no window was chosen with `window` and there isn't exactly one window.
+    pub fn run(self, sdlh: &Sdl2Mt) -> Result<(), Sdl2MtError> {
+        let window_id = match self.window {
+            Some(window_id) => window_id,
+            None => {
+                let windows = sdlh.call(|_sdl, windows| windows.keys().cloned().collect::<Vec<WindowId>>())?;
+                match windows.len() {
+                    1 => windows[0],
+                    count => panic!("sdl2_mt: the script has no window to send input to, and there are {} windows", count),
+                }
+            },
+        };
+
+        for step in self.steps {
+            match step {
+                Step::Key(keycode, pressed) => sdlh.inject_key(window_id, keycode, pressed)?,
+                Step::MouseMove(x, y) => sdlh.inject_mouse_move(window_id, x, y)?,
+                Step::MouseButton(button, pressed, x, y) => sdlh.inject_mouse_button(window_id, button, pressed, x, y)?,
+                Step::Quit => sdlh.inject_quit()?,
+                Step::Resize(width, height) => sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+                    if let Some(canvas) = windows.get_mut(&window_id) {
+                        let _ = canvas.window_mut().set_size(width, height);
+                    }
+                })?,
+                Step::Wait(duration) => {
+                    thread::sleep(duration);
+                    continue;
+                },
+                Step::WaitFrames(frames) => {
+                    wait_frames(sdlh, window_id, frames)?;
+                    continue;
+                },
+            }
+
+            // the injected events reach the forwarders and the unhandled events once they are pumped
+            sdlh.call_builtin(|sdl, windows, state| state.pump_events(sdl, windows))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Script {
+    fn default() -> Script {
+        Script::new()
+    }
+}
+
+fn frames_presented(sdlh: &Sdl2Mt, window_id: WindowId) -> Result<u64, Sdl2MtError> {
+    sdlh.call_builtin(move |_sdl, _windows, state| {
+        state.windows.get(&window_id).map_or(0, |window| window.frames_presented)
+    })
+}
+
+fn wait_frames(sdlh: &Sdl2Mt, window_id: WindowId, frames: u64) -> Result<(), Sdl2MtError> {
+    let target = frames_presented(sdlh, window_id)? + frames;
+    let deadline = Instant::now() + FRAME_TIMEOUT;
+    while frames_presented(sdlh, window_id)? < target {
+        if Instant::now() >= deadline {
+            return Err(Sdl2MtError::Timeout);
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use log_crate::{self, Level, LevelFilter};
+use sys::log::{self as sdl_log, SDL_LogPriority};
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+fn level(priority: SDL_LogPriority) -> Level {
+    match priority {
+        SDL_LogPriority::SDL_LOG_PRIORITY_VERBOSE => Level::Trace,
+        SDL_LogPriority::SDL_LOG_PRIORITY_DEBUG => Level::Debug,
+        SDL_LogPriority::SDL_LOG_PRIORITY_INFO => Level::Info,
+        SDL_LogPriority::SDL_LOG_PRIORITY_WARN => Level::Warn,
+        _ => Level::Error,
+    }
+}
+
+fn target(category: c_int) -> &'static str {
+    match category {
+        0 => "sdl2::application",
+        1 => "sdl2::error",
+        2 => "sdl2::assert",
+        3 => "sdl2::system",
+        4 => "sdl2::audio",
+        5 => "sdl2::video",
+        6 => "sdl2::render",
+        7 => "sdl2::input",
+        8 => "sdl2::test",
+        _ => "sdl2",
+    }
+}
+
+/// The lowest SDL priority that the logger currently lets through, so that SDL doesn't
+/// format messages that would be thrown away anyways.
+fn min_priority() -> SDL_LogPriority {
+    match log_crate::max_level() {
+        LevelFilter::Trace => SDL_LogPriority::SDL_LOG_PRIORITY_VERBOSE,
+        LevelFilter::Debug => SDL_LogPriority::SDL_LOG_PRIORITY_DEBUG,
+        LevelFilter::Info => SDL_LogPriority::SDL_LOG_PRIORITY_INFO,
+        LevelFilter::Warn => SDL_LogPriority::SDL_LOG_PRIORITY_WARN,
+        LevelFilter::Error => SDL_LogPriority::SDL_LOG_PRIORITY_ERROR,
+        LevelFilter::Off => SDL_LogPriority::SDL_LOG_PRIORITY_CRITICAL,
+    }
+}
+
+// SDL may call this from any thread that logs, not just the UI thread
+unsafe extern "C" fn log_output(_userdata: *mut c_void, category: c_int, priority: SDL_LogPriority, message: *const c_char) {
+    if message.is_null() {
+        return;
+    }
+    let message = CStr::from_ptr(message).to_string_lossy();
+    log_crate::log!(target: target(category), level(priority), "{}", message);
+}
+
+impl Sdl2Mt {
+    /// Sends SDL's own log messages, e.g. warnings from the video driver, to the `log` crate
+    /// instead of stderr. Messages are logged with targets like `sdl2::video`, one for each
+    /// SDL log category.
+    ///
+    /// SDL only produces messages at the levels the logger has enabled when this is called,
+    /// so set up the logger first.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn redirect_sdl_log(&self) -> Result<(), Sdl2MtError> {
+        self.run_builtin(|_sdl, _windows, _state| unsafe {
+            sdl_log::SDL_LogSetAllPriority(min_priority());
+            sdl_log::SDL_LogSetOutputFunction(Some(log_output), ptr::null_mut());
+        })
+    }
+}
@@ -0,0 +1,58 @@
+
+use Sdl2Message::Inline;
+use Sdl2Mt;
+use event::Event;
+use inject;
+use inline_fn::InlineFn;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+
+/// Whether a shutdown signal was received, and who wants to hear about it.
+pub struct Shutdown {
+    requested: AtomicBool,
+    listeners: Mutex<Vec<mpsc::Sender<()>>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Shutdown {
+        Shutdown {
+            requested: AtomicBool::new(false),
+            listeners: Mutex::new(vec![]),
+        }
+    }
+}
+
+/// Called by `Sdl2MtBuilder::build` with `quit_on_signals`.
+pub fn install(sdlh: Sdl2Mt) {
+    let result = ctrlc::set_handler(move || {
+        sdlh.shared.shutdown.requested.store(true, Ordering::SeqCst);
+        sdlh.shared.shutdown.listeners.lock().unwrap().retain(|listener| listener.send(()).is_ok());
+
+        // ahead of normal work, so that a busy UI thread doesn't keep the window up for long
+        let _ = sdlh.send_control(Inline(InlineFn::new(|sdl, _windows, _state| {
+            inject::push_event(sdl, Event::Quit { timestamp: inject::timestamp() });
+        })));
+    });
+    if let Err(error) = result {
+        panic!("sdl2_mt: couldn't install the signal handler: {}", error);
+    }
+}
+
+impl Sdl2Mt {
+    /// Whether SIGINT or SIGTERM, or Ctrl+C on Windows, has been received since
+    /// the UI thread was started with `Sdl2MtBuilder::quit_on_signals`.
+    pub fn shutdown_requested(&self) -> bool {
+        self.shared.shutdown.requested.load(Ordering::SeqCst)
+    }
+
+    /// Sends `()` to a channel for every shutdown signal received after this call, for
+    /// threads that should stop their work and drop their handles before the process exits.
+    /// Only sent with `Sdl2MtBuilder::quit_on_signals`.
+    pub fn shutdown_signals(&self) -> mpsc::Receiver<()> {
+        let (sender, receiver) = mpsc::channel();
+        self.shared.shutdown.listeners.lock().unwrap().push(sender);
+        receiver
+    }
+}
@@ -0,0 +1,118 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use error;
+use trace;
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Where a window creator leaves the reason it failed, for the thread waiting on the window.
+pub type CreateFailure = Arc<Mutex<Option<Sdl2MtError>>>;
+
+/// Hands a window creator's failed SDL call to the thread waiting on the window, or reports
+/// it through `errors()` if nobody is waiting for the reason.
+pub fn create_failed<E: fmt::Display>(failure: &Option<CreateFailure>, operation: &'static str, error: E) {
+    match *failure {
+        Some(ref failure) => *failure.lock().unwrap() = Some(error::sdl_error(operation, error)),
+        None => trace::sdl_error(operation, error.to_string()),
+    }
+}
+
+/// The result of creating a window with a window creator that reports to `failure`.
+pub fn created(window_id: Option<WindowId>, failure: &CreateFailure, helper: &str) -> Result<WindowId, Sdl2MtError> {
+    match window_id {
+        Some(window_id) => Ok(window_id),
+        None => Err(failure.lock().unwrap().take().unwrap_or_else(|| {
+            error::sdl_error(helper, "the window wasn't created")
+        })),
+    }
+}
+
+/// Everything `create_simple_window_with` needs to make a window, as plain data, so that
+/// it can be filled in from a config file.
+///
+/// The default is an 800 by 600 resizable window with a software renderer, centered on screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimpleWindowOptions {
+    pub title: String,
+    pub size: (u32, u32),
+    /// where the top left corner goes, or None to center the window
+    pub position: Option<(i32, i32)>,
+    pub resizable: bool,
+    /// covers the desktop, without changing the display mode
+    pub fullscreen: bool,
+    /// synchronizes presenting with the display's refresh rate; this needs `accelerated`
+    pub vsync: bool,
+    /// uses a hardware accelerated renderer instead of the software one
+    pub accelerated: bool,
+    /// the window starts out hidden, until it is shown with `canvas.window_mut().show()`
+    pub hidden: bool,
+}
+
+impl Default for SimpleWindowOptions {
+    fn default() -> SimpleWindowOptions {
+        SimpleWindowOptions {
+            title: String::new(),
+            size: (800, 600),
+            position: None,
+            resizable: true,
+            fullscreen: false,
+            vsync: false,
+            accelerated: false,
+            hidden: false,
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Creates a window and its canvas from `options`, and returns its ID. Unlike
+    /// `create_simple_window`, this doesn't use the builder's default window profile.
+    /// Returns `Sdl2MtError::SdlError` if SDL can't make the window.
+    ///
+    /// This function executes synchronously. It will block until the
+    /// window has been created.
+    pub fn create_simple_window_with(&self, options: SimpleWindowOptions) -> Result<WindowId, Sdl2MtError> {
+        let failure = Arc::new(Mutex::new(None));
+        let creator_failure = Some(failure.clone());
+        let window_id = self.create_window(Box::new(move |_sdl, video_subsystem| {
+            let (width, height) = options.size;
+            let mut builder = video_subsystem.window(&options.title, width, height);
+            match options.position {
+                Some((x, y)) => builder.position(x, y),
+                None => builder.position_centered(),
+            };
+            if options.resizable {
+                builder.resizable();
+            }
+            if options.fullscreen {
+                builder.fullscreen_desktop();
+            }
+            if options.hidden {
+                builder.hidden();
+            }
+            let window = match builder.build() {
+                Ok(window) => window,
+                Err(error) => {
+                    create_failed(&creator_failure, "SDL_CreateWindow", error);
+                    return None;
+                },
+            };
+
+            let mut canvas = window.into_canvas();
+            canvas = if options.accelerated { canvas.accelerated() } else { canvas.software() };
+            if options.vsync {
+                canvas = canvas.present_vsync();
+            }
+            match canvas.build() {
+                Ok(canvas) => Some(canvas),
+                Err(error) => {
+                    create_failed(&creator_failure, "SDL_CreateRenderer", error);
+                    None
+                },
+            }
+        }))?;
+
+        created(window_id, &failure, "create_simple_window_with")
+    }
+}
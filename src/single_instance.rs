@@ -0,0 +1,173 @@
+use Sdl2Mt;
+use WindowId;
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::Duration;
+
+/// How long a second instance waits for the first one to answer.
+const ACTIVATE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where the first instance announces the port it listens on.
+fn lock_path(app_id: &str) -> PathBuf {
+    env::temp_dir().join(format!("{}.sdl2_mt.lock", app_id))
+}
+
+fn activate_request(app_id: &str) -> String {
+    format!("activate {}\n", app_id)
+}
+
+/// Asks the instance that wrote the lock file to activate itself. Returns false if the lock
+/// file is missing or stale, i.e. there is no instance answering on its port.
+fn activate_running(app_id: &str) -> bool {
+    let mut contents = String::new();
+    let port = match fs::File::open(lock_path(app_id)).and_then(|mut file| file.read_to_string(&mut contents)) {
+        Ok(_) => match contents.trim().parse::<u16>() {
+            Ok(port) => port,
+            Err(_) => return false,
+        },
+        Err(_) => return false,
+    };
+
+    let address = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), port));
+    let result = TcpStream::connect_timeout(&address, ACTIVATE_TIMEOUT).and_then(|mut stream| {
+        stream.set_read_timeout(Some(ACTIVATE_TIMEOUT))?;
+        stream.write_all(activate_request(app_id).as_bytes())?;
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply)?;
+        Ok(reply)
+    });
+    // anything else listening on a reused port doesn't know the request
+    match result {
+        Ok(reply) => reply == "ok\n",
+        Err(_) => false,
+    }
+}
+
+/// Held by the first instance of an application, returned by `Sdl2Mt::single_instance`.
+/// Later instances ask it to activate the application, which raises and focuses the main
+/// window, instead of opening another one. Dropping it lets the next instance be the first.
+pub struct SingleInstance {
+    lock_path: PathBuf,
+    port: u16,
+    stopped: Arc<AtomicBool>,
+    main_window: Arc<Mutex<Option<WindowId>>>,
+    listeners: Arc<Mutex<Vec<mpsc::Sender<()>>>>,
+}
+
+impl SingleInstance {
+    /// The window that activation raises. By default, it is the first window that was created.
+    pub fn set_main_window(&self, window_id: WindowId) {
+        *self.main_window.lock().unwrap() = Some(window_id);
+    }
+
+    /// Sends `()` to a channel whenever another instance was launched and activated this one.
+    pub fn activations(&self) -> mpsc::Receiver<()> {
+        let (sender, receiver) = mpsc::channel();
+        self.listeners.lock().unwrap().push(sender);
+        receiver
+    }
+}
+
+impl Drop for SingleInstance {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        let _ = fs::remove_file(&self.lock_path);
+        // wakes the listening thread up, so that it sees it has been stopped
+        let _ = TcpStream::connect((Ipv4Addr::new(127, 0, 0, 1), self.port));
+    }
+}
+
+impl Sdl2Mt {
+    /// Makes sure only one instance of the application runs at a time, for the current user.
+    /// `app_id` names the application, and should be unique to it, e.g. "com.example.viewer".
+    ///
+    /// Returns None if another instance is running, after asking it to raise and focus its
+    /// main window; this instance should exit without opening a window. Otherwise, returns a
+    /// `SingleInstance`, which answers later instances until it is dropped.
+    ///
+    /// The first instance writes the port of a local socket into a lock file in the temporary
+    /// directory. A lock file left behind by an instance that crashed is taken over.
+    ///
+    /// This function executes synchronously, but doesn't need the UI thread.
+    pub fn single_instance(&self, app_id: &str) -> io::Result<Option<SingleInstance>> {
+        let path = lock_path(app_id);
+        let mut lock_file = None;
+        for _ in 0..2 {
+            if activate_running(app_id) {
+                return Ok(None);
+            }
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => {
+                    lock_file = Some(file);
+                    break;
+                },
+                // a stale lock file, or another instance that is just starting up
+                Err(ref error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                    thread::sleep(Duration::from_millis(100));
+                    if activate_running(app_id) {
+                        return Ok(None);
+                    }
+                    fs::remove_file(&path)?;
+                },
+                Err(error) => return Err(error),
+            }
+        }
+        let mut lock_file = match lock_file {
+            Some(lock_file) => lock_file,
+            None => return Err(io::Error::new(io::ErrorKind::AlreadyExists, "another instance keeps taking the lock file")),
+        };
+
+        let listener = TcpListener::bind((Ipv4Addr::new(127, 0, 0, 1), 0))?;
+        let port = listener.local_addr()?.port();
+        lock_file.write_all(port.to_string().as_bytes())?;
+        drop(lock_file);
+
+        let instance = SingleInstance {
+            lock_path: path,
+            port,
+            stopped: Arc::new(AtomicBool::new(false)),
+            main_window: Arc::new(Mutex::new(None)),
+            listeners: Arc::new(Mutex::new(vec![])),
+        };
+        let sdlh = self.clone();
+        let request = activate_request(app_id);
+        let stopped = instance.stopped.clone();
+        let main_window = instance.main_window.clone();
+        let listeners = instance.listeners.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::SeqCst) {
+                    return;
+                }
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let mut line = String::new();
+                let _ = stream.set_read_timeout(Some(ACTIVATE_TIMEOUT));
+                if BufReader::new(&stream).read_line(&mut line).is_err() || line != request {
+                    continue;
+                }
+
+                let main_window = *main_window.lock().unwrap();
+                let _ = sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+                    let window_id = main_window.or_else(|| windows.keys().min().cloned());
+                    if let Some(canvas) = window_id.and_then(|window_id| windows.get_mut(&window_id)) {
+                        canvas.window_mut().restore();
+                        canvas.window_mut().raise();
+                    }
+                });
+                listeners.lock().unwrap().retain(|activations| activations.send(()).is_ok());
+                let _ = stream.write_all(b"ok\n");
+            }
+        });
+        Ok(Some(instance))
+    }
+}
@@ -0,0 +1,118 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use error;
+use ffi;
+use rwops::RWops;
+use surface::Surface;
+use timers::{TimerAction, TimerId};
+
+use std::time::{Duration, Instant};
+
+/// How long the splash screen takes to fade out.
+const FADE_DURATION: Duration = Duration::from_millis(200);
+const FADE_STEP: Duration = Duration::from_millis(16);
+
+/// A splash screen opened with `Sdl2Mt::show_splash`. It closes when this is dropped, or
+/// `close` is called, but not before it has been shown for its minimum duration.
+pub struct Splash {
+    sdlh: Sdl2Mt,
+    window_id: WindowId,
+    close_at: Instant,
+    closed: bool,
+}
+
+impl Splash {
+    pub fn window_id(&self) -> WindowId {
+        self.window_id
+    }
+
+    /// Fades the splash screen out and closes it, once it has been shown for its minimum
+    /// duration. Call this when the main window is ready.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn close(mut self) -> Result<(), Sdl2MtError> {
+        self.start_closing()
+    }
+
+    fn start_closing(&mut self) -> Result<(), Sdl2MtError> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+
+        let window_id = self.window_id;
+        let close_at = self.close_at;
+        self.sdlh.run_builtin(move |_sdl, _windows, state| {
+            let id = TimerId::next();
+            let deadline = if close_at > state.clock.now() { close_at } else { state.clock.now() };
+            let mut fade_started = None;
+            state.timers.add(id, deadline, Some(FADE_STEP), TimerAction::Builtin(Box::new(move |_sdl, windows, state| {
+                let now = state.clock.now();
+                let fade = now - *fade_started.get_or_insert(now);
+                let opacity = 1.0 - fade.as_secs_f32() / FADE_DURATION.as_secs_f32();
+                let done = match windows.get_mut(&window_id) {
+                    // windows that can't be made translucent are closed at once
+                    Some(canvas) => opacity <= 0.0 || unsafe { ffi::SDL_SetWindowOpacity(canvas.window().raw(), opacity) } != 0,
+                    None => true,
+                };
+                if done {
+                    windows.remove(&window_id);
+                    state.timers.cancel(id);
+                }
+            })));
+        })
+    }
+}
+
+impl Drop for Splash {
+    fn drop(&mut self) {
+        let _ = self.start_closing();
+    }
+}
+
+impl Sdl2Mt {
+    /// Opens a borderless window in the middle of the screen right away, showing an image
+    /// while the application initializes, and returns a `Splash` that closes it again.
+    /// The splash screen stays up for at least `min_duration`, so that it doesn't just flash
+    /// by on fast machines, and then fades out.
+    ///
+    /// The image is a BMP file, which SDL can load without SDL_image. The window has the
+    /// size of the image. Returns `Sdl2MtError::SdlError` if the image can't be loaded.
+    ///
+    /// This function executes synchronously.
+    pub fn show_splash(&self, image_bmp: Vec<u8>, min_duration: Duration) -> Result<Splash, Sdl2MtError> {
+        let window_id = self.call(move |sdl, windows| {
+            let surface = RWops::from_bytes(&image_bmp)
+                .and_then(|mut rwops| Surface::load_bmp_rw(&mut rwops))
+                .map_err(|error| error::sdl_error("SDL_LoadBMP_RW", error))?;
+            let video = sdl.video().map_err(|error| error::sdl_error("SDL_InitSubSystem", error))?;
+            let window = video.window("", surface.width(), surface.height())
+                .position_centered()
+                .borderless()
+                .build()
+                .map_err(|error| error::sdl_error("SDL_CreateWindow", error))?;
+            let mut canvas = window.into_canvas()
+                .software()
+                .build()
+                .map_err(|error| error::sdl_error("SDL_CreateRenderer", error))?;
+
+            let texture_creator = canvas.texture_creator();
+            let texture = texture_creator.create_texture_from_surface(&surface)
+                .map_err(|error| error::sdl_error("SDL_CreateTextureFromSurface", error))?;
+            canvas.copy(&texture, None, None).map_err(|error| error::sdl_error("SDL_RenderCopy", error))?;
+            canvas.present();
+
+            let id = WindowId::from_raw(canvas.window().id());
+            windows.insert(id, canvas);
+            Ok(id)
+        }).and_then(|result| result)?;
+
+        Ok(Splash {
+            sdlh: self.clone(),
+            window_id,
+            close_at: self.shared.clock.now() + min_duration,
+            closed: false,
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use Sdl2Mt;
+use watchdog::WorkKind;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Identifies the handle a message was submitted through. Every clone of a handle,
+/// including the ones returned by `init()`, gets its own ID.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HandleId(usize);
+
+static NEXT_HANDLE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The ID stored in a handle, which hands out a new ID when the handle is cloned.
+pub struct OwnId(pub HandleId);
+
+impl OwnId {
+    pub fn new() -> OwnId {
+        OwnId(HandleId(NEXT_HANDLE_ID.fetch_add(1, Ordering::Relaxed)))
+    }
+}
+
+impl Clone for OwnId {
+    fn clone(&self) -> OwnId {
+        OwnId::new()
+    }
+}
+
+/// A message handled by the UI thread while `spy_messages` was on.
+#[derive(Copy, Clone, Debug)]
+pub struct SpiedMessage {
+    pub kind: WorkKind,
+    /// the handle the message was submitted through
+    pub origin: HandleId,
+    pub queued_at: Instant,
+    /// how long the message waited in the queue
+    pub queue_time: Duration,
+    /// how long the UI thread took to handle it
+    pub execution_time: Duration,
+}
+
+/// The log that `spy_messages` fills.
+pub struct MessageSpy {
+    enabled: AtomicBool,
+    log: Mutex<Vec<SpiedMessage>>,
+}
+
+impl MessageSpy {
+    pub fn new() -> MessageSpy {
+        MessageSpy {
+            enabled: AtomicBool::new(false),
+            log: Mutex::new(vec![]),
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Called by the UI thread after handling a message, if the spy is enabled.
+    pub fn record(&self, kind: WorkKind, origin: HandleId, queued_at: Instant, started: Instant) {
+        self.log.lock().unwrap().push(SpiedMessage {
+            kind,
+            origin,
+            queued_at,
+            queue_time: started - queued_at,
+            execution_time: started.elapsed(),
+        });
+    }
+}
+
+impl Sdl2Mt {
+    /// The ID that messages submitted through this handle are recorded with by `spy_messages`.
+    pub fn handle_id(&self) -> HandleId {
+        self.id.0
+    }
+
+    /// Starts or stops recording every message the UI thread handles, for tests that assert
+    /// on what reached the UI thread, e.g. that three redraws were coalesced into one. Starting
+    /// clears whatever was recorded before. Timers and idle callbacks are not messages, so
+    /// they are not recorded, but the messages that scheduled them are.
+    pub fn spy_messages(&self, enabled: bool) {
+        let spy = &self.shared.spy;
+        if enabled {
+            spy.log.lock().unwrap().clear();
+        }
+        spy.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns the messages recorded since spying started or this was last called,
+    /// in the order the UI thread handled them.
+    pub fn spied_messages(&self) -> Vec<SpiedMessage> {
+        let mut log = self.shared.spy.log.lock().unwrap();
+        log.drain(..).collect()
+    }
+}
@@ -0,0 +1,196 @@
+use auto_pause::AutoPauseState;
+use camera::Camera;
+use clock::Clock;
+use dispatch::EventQueue;
+use dpi::ScaleChanged;
+use event::{Event, WindowEvent};
+use ffi;
+use frame_times::FrameTimeTracker;
+use high_water::HighWater;
+use idle::IdleCallbacks;
+use inject::InjectedMouse;
+use kiosk::KioskGroup;
+use latency::LatencyTracker;
+use layers::Layers;
+use present::SdlPresent;
+use rate_limit::RateLimits;
+use rect::Rect;
+use render_target::{RenderTarget, TargetId};
+use satellites::Satellites;
+use scene::SceneStack;
+use submitter::Submitters;
+use subtitles::Subtitles;
+use text_grid::TextGridView;
+use ticks::FrameCounter;
+use tiling::Tiling;
+use timers::Timers;
+use trace;
+use render::WindowCanvas;
+use EventPump;
+use WindowId;
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::Instant;
+
+/// Bookkeeping that lives on the UI thread next to the windows themselves.
+///
+/// Only the UI thread ever touches this, so nothing in here needs to be `Send`.
+pub struct UiState {
+    pub events: EventQueue,
+    pub windows: HashMap<WindowId, WindowState>,
+    pub kiosk: Option<KioskGroup>,
+    pub tiling: Option<Tiling>,
+    pub timers: Timers,
+    pub idle: IdleCallbacks,
+    pub rate_limits: RateLimits,
+    pub submitters: Submitters,
+    pub latency: LatencyTracker,
+    pub high_water: HighWater,
+    pub injected_mouse: InjectedMouse,
+    /// the time timers are scheduled against
+    pub clock: Clock,
+    /// set while the application is in the background, which stops rendering
+    pub paused: bool,
+    /// everyone listening to `scale_changes`
+    pub scale_listeners: Vec<mpsc::Sender<ScaleChanged>>,
+    pub auto_pause: AutoPauseState,
+    /// counts the frames presented, for `frame_number`
+    pub frames: FrameCounter,
+    /// the SDL2 satellite libraries enabled through features
+    pub satellites: Satellites,
+}
+
+/// Per-window settings used by the built-in helpers.
+#[derive(Default)]
+pub struct WindowState {
+    /// keep the canvas' logical size equal to the window size, so that drawing
+    /// code works in window coordinates while rendering at the drawable resolution.
+    pub auto_dpi_scaling: bool,
+
+    /// a fixed logical size requested by the application, which takes precedence
+    /// over `auto_dpi_scaling`.
+    pub logical_size: Option<(u32, u32)>,
+
+    /// only scale the logical size by whole factors, centering the result.
+    pub integer_scale: bool,
+
+    /// named regions of the window defined with `define_viewport`.
+    pub viewports: HashMap<String, Rect>,
+
+    /// callbacks registered with `on_present`.
+    pub present_callbacks: Vec<Box<SdlPresent>>,
+    pub frames_presented: u64,
+    pub last_present: Option<Instant>,
+
+    /// frame time statistics, and callbacks registered with `on_jank`.
+    pub frame_times: FrameTimeTracker,
+
+    /// the scale factor the window had when it was last shown, moved or resized.
+    pub scale: Option<f32>,
+
+    /// scenes pushed with `push_scene`.
+    pub scenes: SceneStack,
+
+    /// set between the window being minimized or hidden, and it being restored or shown.
+    pub minimized: bool,
+    pub focused: bool,
+
+    /// the subtitle track shown with `show_subtitles`.
+    pub subtitles: Option<Subtitles>,
+
+    /// offscreen textures created with `create_render_target`, and the one that drawing
+    /// goes to, set with `set_render_target`.
+    pub targets: HashMap<TargetId, RenderTarget>,
+    pub active_target: Option<TargetId>,
+
+    /// the layers drawn to with `draw_layer`, composited on `present`.
+    pub layers: Option<Layers>,
+
+    /// the pan and zoom set with `set_camera`.
+    pub camera: Camera,
+
+    /// the grid shown with `show_text_grid`.
+    pub text_grid: Option<TextGridView>,
+}
+
+impl UiState {
+    pub fn new(event_pump: EventPump, unhandled_capacity: usize) -> UiState {
+        UiState {
+            events: EventQueue::new(event_pump, unhandled_capacity),
+            windows: HashMap::new(),
+            kiosk: None,
+            tiling: None,
+            timers: Timers::new(),
+            idle: IdleCallbacks::new(),
+            rate_limits: RateLimits::new(),
+            submitters: Submitters::new(),
+            latency: LatencyTracker::new(),
+            high_water: HighWater::new(),
+            injected_mouse: InjectedMouse::default(),
+            clock: Clock::Real,
+            paused: false,
+            scale_listeners: vec![],
+            auto_pause: AutoPauseState::default(),
+            frames: FrameCounter::default(),
+            satellites: Satellites::init(),
+        }
+    }
+
+    pub fn window(&mut self, id: WindowId) -> &mut WindowState {
+        self.windows.entry(id).or_default()
+    }
+
+    /// Called for every event pulled from the event pump, before any user
+    /// event handler sees it.
+    pub fn observe_event(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, event: &Event) {
+        // windows can be removed from the canvas map by user code at any time,
+        // so forget about any state that no longer has a window attached.
+        self.windows.retain(|id, _| canvases.contains_key(id));
+        self.observe_lifecycle(event);
+        self.observe_kiosk(event);
+        self.observe_visibility(canvases, event);
+        self.scene_event(event);
+
+        if let Event::Window { window_id, ref win_event, .. } = *event {
+            let window_id = WindowId::from_raw(window_id);
+            match *win_event {
+                WindowEvent::Shown |
+                WindowEvent::Moved(..) |
+                WindowEvent::SizeChanged(..) => {
+                    self.update_logical_size(canvases, window_id);
+                    self.check_scale(canvases, window_id);
+                },
+                _ => {}
+            }
+        }
+    }
+
+    /// Reapplies the logical size settings of a window. Moving a window between
+    /// monitors with different DPI changes the ratio between the window size and
+    /// the drawable size, so this is done whenever the window moves or is resized.
+    pub fn update_logical_size(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, window_id: WindowId) {
+        let (canvas, settings) = match (canvases.get_mut(&window_id), self.windows.get(&window_id)) {
+            (Some(canvas), Some(settings)) => (canvas, settings),
+            _ => return,
+        };
+
+        let logical_size = match settings.logical_size {
+            Some(size) => Some(size),
+            None if settings.auto_dpi_scaling => Some(canvas.window().size()),
+            None => None,
+        };
+
+        if let Some((width, height)) = logical_size {
+            if width > 0 && height > 0 && canvas.logical_size() != (width, height) {
+                if let Err(error) = canvas.set_logical_size(width, height) {
+                    trace::sdl_error("set_logical_size", error.to_string());
+                }
+            }
+        }
+
+        unsafe {
+            ffi::SDL_RenderSetIntegerScale(canvas.raw(), settings.integer_scale as i32);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use Sdl2Mt;
+use latency::EventLatency;
+
+use std::mem;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// A snapshot of how busy the UI thread is, returned by `Sdl2Mt::stats`.
+///
+/// Apart from `queue_depth`, everything covers the time since the previous call
+/// to `stats()`, or since the UI thread started if there was none.
+#[derive(Copy, Clone, Debug)]
+pub struct Sdl2MtStats {
+    /// how many messages are waiting for the UI thread right now
+    pub queue_depth: usize,
+    /// the most messages the UI thread found waiting at once when it woke up
+    pub max_queue_depth: usize,
+    /// the most events that were waiting for an event handler at once
+    pub max_unhandled_events: usize,
+    /// how many messages the UI thread handled
+    pub messages_processed: u64,
+    pub messages_per_second: f64,
+    /// how long the UI thread spent handling a message, e.g. running a lambda
+    pub average_execution_time: Duration,
+    pub max_execution_time: Duration,
+    /// how long synchronous calls like `call` and `handle_ui_events` took from
+    /// sending the message to receiving the reply
+    pub average_reply_latency: Duration,
+    pub max_reply_latency: Duration,
+    /// how long it took from SDL receiving an event until it was handled
+    pub event_latency: EventLatency,
+}
+
+/// How many of the most recent event latencies are kept for computing percentiles.
+const MAX_LATENCY_SAMPLES: usize = 4096;
+
+/// Totals since the previous snapshot. Recording only touches atomics, so
+/// neither the UI thread nor the calling threads ever wait on each other.
+pub struct StatsCollector {
+    messages: AtomicU64,
+    execution_nanos: AtomicU64,
+    max_execution_nanos: AtomicU64,
+    replies: AtomicU64,
+    reply_nanos: AtomicU64,
+    max_reply_nanos: AtomicU64,
+    max_queue_depth: AtomicUsize,
+    max_unhandled_events: AtomicUsize,
+    /// the number of events handled, and the latencies of the most recent of them in milliseconds
+    event_latencies: Mutex<(u64, Vec<u32>)>,
+    last_snapshot: Mutex<Instant>,
+}
+
+fn average(total_nanos: u64, count: u64) -> Duration {
+    Duration::from_nanos(total_nanos.checked_div(count).unwrap_or(0))
+}
+
+impl StatsCollector {
+    pub fn new() -> StatsCollector {
+        StatsCollector {
+            messages: AtomicU64::new(0),
+            execution_nanos: AtomicU64::new(0),
+            max_execution_nanos: AtomicU64::new(0),
+            replies: AtomicU64::new(0),
+            reply_nanos: AtomicU64::new(0),
+            max_reply_nanos: AtomicU64::new(0),
+            max_queue_depth: AtomicUsize::new(0),
+            max_unhandled_events: AtomicUsize::new(0),
+            event_latencies: Mutex::new((0, vec![])),
+            last_snapshot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Called by the UI thread after handling a message.
+    pub fn record_message(&self, execution_time: Duration) {
+        let nanos = execution_time.as_nanos() as u64;
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        self.execution_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_execution_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Called by a calling thread once it has received a reply from the UI thread.
+    pub fn record_reply(&self, latency: Duration) {
+        let nanos = latency.as_nanos() as u64;
+        self.replies.fetch_add(1, Ordering::Relaxed);
+        self.reply_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_reply_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Called by the UI thread after every batch of messages.
+    pub fn record_depths(&self, queue_depth: usize, unhandled_events: usize) {
+        self.max_queue_depth.fetch_max(queue_depth, Ordering::Relaxed);
+        self.max_unhandled_events.fetch_max(unhandled_events, Ordering::Relaxed);
+    }
+
+    /// Called by the UI thread with the latencies of the events it handled recently.
+    pub fn record_event_latencies(&self, samples: &mut Vec<u32>) {
+        let mut latencies = self.event_latencies.lock().unwrap();
+        let (ref mut count, ref mut recent) = *latencies;
+        for sample in samples.drain(..) {
+            if recent.len() < MAX_LATENCY_SAMPLES {
+                recent.push(sample);
+            } else {
+                recent[(*count % MAX_LATENCY_SAMPLES as u64) as usize] = sample;
+            }
+            *count += 1;
+        }
+    }
+
+    /// Returns the totals since the previous snapshot, and starts counting from zero.
+    pub fn snapshot(&self, queue_depth: usize) -> Sdl2MtStats {
+        let elapsed = {
+            let mut last_snapshot = self.last_snapshot.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = now - *last_snapshot;
+            *last_snapshot = now;
+            elapsed
+        };
+
+        let messages = self.messages.swap(0, Ordering::Relaxed);
+        let replies = self.replies.swap(0, Ordering::Relaxed);
+        let seconds = elapsed.as_secs_f64();
+        let (events_handled, mut latencies) = mem::take(&mut *self.event_latencies.lock().unwrap());
+
+        Sdl2MtStats {
+            queue_depth,
+            max_queue_depth: self.max_queue_depth.swap(0, Ordering::Relaxed),
+            max_unhandled_events: self.max_unhandled_events.swap(0, Ordering::Relaxed),
+            messages_processed: messages,
+            messages_per_second: if seconds > 0.0 { messages as f64 / seconds } else { 0.0 },
+            average_execution_time: average(self.execution_nanos.swap(0, Ordering::Relaxed), messages),
+            max_execution_time: Duration::from_nanos(self.max_execution_nanos.swap(0, Ordering::Relaxed)),
+            average_reply_latency: average(self.reply_nanos.swap(0, Ordering::Relaxed), replies),
+            max_reply_latency: Duration::from_nanos(self.max_reply_nanos.swap(0, Ordering::Relaxed)),
+            event_latency: EventLatency::from_samples(events_handled, &mut latencies),
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Returns statistics about the message queue and how long the UI thread spends on
+    /// each message, to help find out whether handing work to the UI thread is what
+    /// limits the frame rate. Statistics are shared by every handle, and each call starts
+    /// a new measurement interval for all of them.
+    ///
+    /// This does not involve the UI thread, so it returns right away even while the UI
+    /// thread is busy.
+    pub fn stats(&self) -> Sdl2MtStats {
+        self.shared.stats.snapshot(self.queue.len())
+    }
+}
@@ -0,0 +1,235 @@
+use Sdl2Message::DrainSubmitters;
+use Sdl2Mt;
+use Sdl2MtError;
+use inline_fn::InlineFn;
+use queue::MessageQueue;
+use render::WindowCanvas;
+use spy::HandleId;
+use state::UiState;
+use Sdl;
+use WindowId;
+
+use std::cell::UnsafeCell;
+use std::collections::HashMap;
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+/// A fixed size ring buffer with exactly one producer, a `Submitter`, and one consumer,
+/// the UI thread. `head` and `tail` only ever increase; slot `i % capacity` holds item `i`.
+struct Ring {
+    slots: Box<[UnsafeCell<MaybeUninit<InlineFn>>]>,
+    /// the next item the UI thread will take, only written by the UI thread
+    head: AtomicUsize,
+    /// the next item the producer will write, only written by the producer
+    tail: AtomicUsize,
+    /// whether a `DrainSubmitters` message is on its way to the UI thread
+    scheduled: AtomicBool,
+    producer_gone: AtomicBool,
+    consumer_gone: AtomicBool,
+}
+
+// slots are only accessed by the producer before publishing them through `tail`,
+// and by the consumer after seeing them published, and `InlineFn` is `Send`.
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn new(capacity: usize) -> Ring {
+        let slots: Vec<_> = (0..capacity).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect();
+        Ring {
+            slots: slots.into_boxed_slice(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            scheduled: AtomicBool::new(false),
+            producer_gone: AtomicBool::new(false),
+            consumer_gone: AtomicBool::new(false),
+        }
+    }
+
+    /// Only called by the producer. Hands the item back if the ring is full.
+    fn push(&self, item: InlineFn) -> Result<(), InlineFn> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        if tail - self.head.load(Ordering::Acquire) == self.slots.len() {
+            return Err(item);
+        }
+
+        unsafe {
+            ptr::write((*self.slots[tail % self.slots.len()].get()).as_mut_ptr(), item);
+        }
+        // pairs with the `scheduled` handshake, see `UiState::drain_submitters`
+        self.tail.store(tail + 1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Only called by the consumer, for items up to `tail`.
+    fn pop(&self, tail: usize) -> Option<InlineFn> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == tail {
+            return None;
+        }
+
+        let item = unsafe { ptr::read((*self.slots[head % self.slots.len()].get()).as_ptr()) };
+        self.head.store(head + 1, Ordering::Release);
+        Some(item)
+    }
+}
+
+impl Drop for Ring {
+    fn drop(&mut self) {
+        let tail = self.tail.load(Ordering::Acquire);
+        while let Some(item) = self.pop(tail) {
+            drop(item);
+        }
+    }
+}
+
+/// A dedicated channel to the UI thread for a single producer thread that submits lots of
+/// small lambdas, e.g. thousands of drawing commands per second. Lambdas go into a fixed size,
+/// lock-free ring buffer that the UI thread drains, bypassing the shared message queue;
+/// lambdas that capture no more than a few words are submitted without any heap allocation.
+///
+/// Lambdas submitted through a `Submitter` run in the order they were submitted, but in no
+/// particular order relative to work submitted any other way. Anything that must happen in
+/// between them, like presenting the canvas, should be submitted through the same `Submitter`.
+///
+/// Create one with `Sdl2Mt::submitter`. Dropping the `Submitter` unregisters it once the
+/// UI thread has run the lambdas that are still waiting.
+pub struct Submitter {
+    ring: Arc<Ring>,
+    queue: Arc<MessageQueue>,
+    /// the handle that created the submitter, which its messages are recorded with
+    origin: HandleId,
+}
+
+impl Submitter {
+    /// Executes a lambda function on the UI thread, waiting for space in the ring buffer if it is full.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// unless the ring buffer is full.
+    pub fn submit<F>(&mut self, lambda: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) + Send + 'static
+    {
+        let mut item = InlineFn::new(move |sdl, windows, _state| lambda(sdl, windows));
+        loop {
+            match self.push(item) {
+                Err((Sdl2MtError::QueueFull, Some(rejected))) => {
+                    item = rejected;
+                    thread::yield_now();
+                },
+                result => return result.map_err(|(err, _)| err),
+            }
+        }
+    }
+
+    /// Like `submit`, but returns `Sdl2MtError::QueueFull` right away instead of waiting
+    /// when the ring buffer is full.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn try_submit<F>(&mut self, lambda: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) + Send + 'static
+    {
+        self.push(InlineFn::new(move |sdl, windows, _state| lambda(sdl, windows))).map_err(|(err, _)| err)
+    }
+
+    fn push(&self, item: InlineFn) -> Result<(), (Sdl2MtError, Option<InlineFn>)> {
+        if self.ring.consumer_gone.load(Ordering::Acquire) {
+            return Err((Sdl2MtError::UiThreadExited, None));
+        }
+
+        self.ring.push(item).map_err(|item| (Sdl2MtError::QueueFull, Some(item)))?;
+        self.wake().map_err(|err| (err, None))
+    }
+
+    /// Makes sure the UI thread gets around to draining the ring buffer. The message
+    /// is plain data and skips the capacity of the queue, so this never allocates or blocks.
+    fn wake(&self) -> Result<(), Sdl2MtError> {
+        if self.ring.scheduled.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        self.queue.push_control(self.origin, DrainSubmitters)
+    }
+}
+
+impl Drop for Submitter {
+    fn drop(&mut self) {
+        self.ring.producer_gone.store(true, Ordering::Release);
+        // the UI thread forgets about the ring the next time it drains it
+        self.ring.scheduled.store(false, Ordering::SeqCst);
+        let _ = self.wake();
+    }
+}
+
+/// The ring buffers of every registered `Submitter`, owned by the UI thread.
+pub struct Submitters {
+    rings: Vec<Arc<Ring>>,
+}
+
+impl Submitters {
+    pub fn new() -> Submitters {
+        Submitters { rings: vec![] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rings.len()
+    }
+}
+
+impl Drop for Submitters {
+    fn drop(&mut self) {
+        // also runs if the UI thread panics while draining
+        for ring in &self.rings {
+            ring.consumer_gone.store(true, Ordering::Release);
+        }
+    }
+}
+
+impl UiState {
+    /// Runs the lambdas waiting in every `Submitter`'s ring buffer. Only the lambdas that
+    /// were already waiting when draining started are run, so that a fast producer can't
+    /// keep the UI thread busy forever.
+    pub fn drain_submitters(&mut self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>) {
+        // the lambdas need the state too, so take the rings out while they run
+        let mut submitters = mem::replace(&mut self.submitters, Submitters::new());
+        for ring in &submitters.rings {
+            // lambdas submitted after this point send a new message, since
+            // `scheduled` is cleared before `tail` is read.
+            ring.scheduled.store(false, Ordering::SeqCst);
+            let tail = ring.tail.load(Ordering::SeqCst);
+            while let Some(item) = ring.pop(tail) {
+                item.call(sdl, windows, self);
+            }
+        }
+
+        submitters.rings.retain(|ring| {
+            !ring.producer_gone.load(Ordering::Acquire) ||
+                ring.head.load(Ordering::Relaxed) != ring.tail.load(Ordering::Acquire)
+        });
+        submitters.rings.append(&mut self.submitters.rings);
+        self.submitters = submitters;
+    }
+}
+
+impl Sdl2Mt {
+    /// Registers a dedicated channel to the UI thread for a single, high-frequency producer,
+    /// with room for `capacity` lambdas. See `Submitter` for details.
+    ///
+    /// Returns `Sdl2MtError::InvalidArgument` if `capacity` is zero.
+    ///
+    /// This function executes synchronously.
+    pub fn submitter(&self, capacity: usize) -> Result<Submitter, Sdl2MtError> {
+        if capacity == 0 {
+            return Err(Sdl2MtError::InvalidArgument("submitter capacity must be greater than zero"));
+        }
+        let ring = Arc::new(Ring::new(capacity));
+        let ui_ring = ring.clone();
+        self.call_builtin(move |_sdl, _windows, state| state.submitters.rings.push(ui_ring))?;
+
+        Ok(Submitter {
+            ring,
+            queue: self.queue.clone(),
+            origin: self.id.0,
+        })
+    }
+}
@@ -0,0 +1,188 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use av_clock::AvClock;
+use font;
+use pixels::Color;
+use rect::Rect;
+use render::{BlendMode, WindowCanvas};
+use trace;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A line of timed text, shown while the clock is between `start` and `end`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cue {
+    /// may span several lines, separated by '\n'
+    pub text: String,
+    pub start: Duration,
+    pub end: Duration,
+}
+
+impl Cue {
+    pub fn new<S: Into<String>>(text: S, start: Duration, end: Duration) -> Cue {
+        Cue {
+            text: text.into(),
+            start,
+            end,
+        }
+    }
+}
+
+/// Where cues are shown in the window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CuePosition {
+    Top,
+    Bottom,
+}
+
+/// How cues are drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CueStyle {
+    pub color: Color,
+    /// a box drawn behind the text, to keep it readable over bright video
+    pub background: Option<Color>,
+    /// the size of a pixel of the built-in 8x8 font
+    pub scale: u32,
+    pub position: CuePosition,
+    /// the distance from the edge of the window, in pixels
+    pub margin: u32,
+}
+
+impl Default for CueStyle {
+    fn default() -> CueStyle {
+        CueStyle {
+            color: Color::RGB(255, 255, 255),
+            background: Some(Color::RGBA(0, 0, 0, 160)),
+            scale: 2,
+            position: CuePosition::Bottom,
+            margin: 24,
+        }
+    }
+}
+
+struct Track {
+    cues: Vec<Cue>,
+    style: CueStyle,
+}
+
+/// A subtitle track, timed against an `AvClock`. Cues can be pushed from any thread, e.g.
+/// by a decoder as it comes across them, and are drawn over the video by the UI thread
+/// once the track is shown in a window with `Sdl2Mt::show_subtitles`.
+///
+/// Tracks can be cloned and sent to other threads freely; clones share the same cues.
+#[derive(Clone)]
+pub struct Subtitles {
+    clock: AvClock,
+    track: Arc<Mutex<Track>>,
+}
+
+impl Subtitles {
+    pub fn new(clock: &AvClock) -> Subtitles {
+        Subtitles {
+            clock: clock.clone(),
+            track: Arc::new(Mutex::new(Track {
+                cues: vec![],
+                style: CueStyle::default(),
+            })),
+        }
+    }
+
+    /// Adds a cue. Cues that overlap in time are stacked, in the order they were pushed.
+    pub fn push(&self, cue: Cue) {
+        self.track.lock().unwrap().cues.push(cue);
+    }
+
+    /// Removes all cues, e.g. when seeking.
+    pub fn clear(&self) {
+        self.track.lock().unwrap().cues.clear();
+    }
+
+    pub fn set_style(&self, style: CueStyle) {
+        self.track.lock().unwrap().style = style;
+    }
+
+    /// The text of the cues showing at the clock's current time.
+    pub fn active(&self) -> Vec<String> {
+        let now = self.clock.now();
+        let track = self.track.lock().unwrap();
+        track.cues.iter()
+            .filter(|cue| cue.start <= now && now < cue.end)
+            .map(|cue| cue.text.clone())
+            .collect()
+    }
+
+    /// Draws the active cues, and forgets about the cues that are over.
+    pub fn draw(&self, canvas: &mut WindowCanvas) -> Result<(), String> {
+        let now = self.clock.now();
+        let (text, style) = {
+            let mut track = self.track.lock().unwrap();
+            track.cues.retain(|cue| cue.end > now);
+            let active: Vec<&str> = track.cues.iter()
+                .filter(|cue| cue.start <= now)
+                .map(|cue| cue.text.as_str())
+                .collect();
+            (active.join("\n"), track.style)
+        };
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let (width, height) = font::text_size(&text, style.scale);
+        let viewport = canvas.viewport();
+        let x = (viewport.width() as i32 - width as i32) / 2;
+        let y = match style.position {
+            CuePosition::Top => style.margin as i32,
+            CuePosition::Bottom => viewport.height() as i32 - height as i32 - style.margin as i32,
+        };
+
+        if let Some(background) = style.background {
+            let padding = style.scale * 2;
+            let blend_mode = canvas.blend_mode();
+            canvas.set_blend_mode(BlendMode::Blend);
+            canvas.set_draw_color(background);
+            let result = canvas.fill_rect(Rect::new(x - padding as i32, y - padding as i32, width + padding * 2, height + padding * 2));
+            canvas.set_blend_mode(blend_mode);
+            result?;
+        }
+        // each line is centered on its own
+        for (row, line) in text.lines().enumerate() {
+            let (line_width, _) = font::text_size(line, style.scale);
+            let line_x = (viewport.width() as i32 - line_width as i32) / 2;
+            let line_y = y + (row as u32 * font::GLYPH_SIZE * style.scale) as i32;
+            font::draw_text(canvas, line, line_x, line_y, style.scale, style.color)?;
+        }
+        Ok(())
+    }
+}
+
+impl Sdl2Mt {
+    /// Shows a subtitle track in the window. Its active cues are drawn over whatever was
+    /// rendered, right before each frame of the window is presented with `present`, so they
+    /// stay on top of the video and change with the frames. A window shows one track at a
+    /// time; showing another replaces it.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn show_subtitles(&self, window_id: WindowId, subtitles: &Subtitles) -> Result<(), Sdl2MtError> {
+        let subtitles = subtitles.clone();
+        self.run_builtin(move |_sdl, windows, state| {
+            if windows.contains_key(&window_id) {
+                state.window(window_id).subtitles = Some(subtitles);
+            } else {
+                trace::window_not_found(window_id);
+            }
+        })
+    }
+
+    /// Stops showing subtitles in the window.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn hide_subtitles(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            if let Some(settings) = state.windows.get_mut(&window_id) {
+                settings.subtitles = None;
+            }
+        })
+    }
+}
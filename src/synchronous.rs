@@ -0,0 +1,108 @@
+use ON_UI_THREAD;
+use Sdl2Mt;
+use Sdl2MtError;
+use UiLoop;
+use on_ui_thread;
+
+use std::cell::{Cell, RefCell};
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// The UI loop of a synchronous `Sdl2Mt`, on the thread that built it.
+    static SYNC_LOOP: RefCell<Option<UiLoop>> = const { RefCell::new(None) };
+    /// Set once the synchronous UI loop on this thread has exited.
+    static SYNC_EXITED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Called by `Sdl2MtBuilder::build` in synchronous mode.
+pub fn install(ui: UiLoop) {
+    SYNC_LOOP.with(|sync_loop| *sync_loop.borrow_mut() = Some(ui));
+}
+
+/// Turns the synchronous UI loop owned by this thread once, without waiting. Returns None if
+/// this thread doesn't own one, or is already turning it, and Some(false) once it has exited.
+pub fn turn() -> Option<bool> {
+    SYNC_LOOP.with(|sync_loop| {
+        let mut sync_loop = match sync_loop.try_borrow_mut() {
+            Ok(sync_loop) => sync_loop,
+            Err(_) => return None,
+        };
+        let running = {
+            let ui = match *sync_loop {
+                Some(ref mut ui) => ui,
+                None => return None,
+            };
+            ON_UI_THREAD.with(|on_ui_thread| on_ui_thread.set(true));
+            let running = ui.turn(false);
+            ON_UI_THREAD.with(|on_ui_thread| on_ui_thread.set(false));
+            running
+        };
+        if !running {
+            // closes the queue, like the UI thread exiting does
+            *sync_loop = None;
+            SYNC_EXITED.with(|exited| exited.set(true));
+        }
+        Some(running)
+    })
+}
+
+fn owns_loop() -> bool {
+    SYNC_LOOP.with(|sync_loop| sync_loop.try_borrow().map(|ui| ui.is_some()).unwrap_or(false))
+}
+
+/// Waits for a reply from the UI thread. On the thread that owns a synchronous UI loop,
+/// nobody else is going to produce the reply, so the loop is turned until it arrives.
+pub fn recv<T>(rx: &mpsc::Receiver<T>, timeout: Option<Duration>) -> Result<T, Sdl2MtError> {
+    if !owns_loop() {
+        return match timeout {
+            Some(timeout) => rx.recv_timeout(timeout).map_err(Sdl2MtError::from),
+            None => rx.recv().map_err(|_| Sdl2MtError::UiThreadExited),
+        };
+    }
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        match rx.try_recv() {
+            Ok(reply) => return Ok(reply),
+            Err(TryRecvError::Disconnected) => return Err(Sdl2MtError::UiThreadExited),
+            Err(TryRecvError::Empty) => {},
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(Sdl2MtError::Timeout);
+        }
+        match turn() {
+            Some(true) => {},
+            _ => return rx.try_recv().map_err(|_| Sdl2MtError::UiThreadExited),
+        }
+        // the reply may depend on work from other threads, which is now their turn
+        thread::yield_now();
+    }
+}
+
+impl Sdl2Mt {
+    /// Handles the messages that are waiting for the UI thread, runs due timers and pumps
+    /// events, once, then returns without waiting for more. Only for handles built with
+    /// `Sdl2MtBuilder::synchronous`, and only on the thread that built them.
+    ///
+    /// Synchronous calls like `call` or `create_window` made on that thread turn the loop
+    /// by themselves until their reply arrives, so `turn` is only needed to get asynchronous
+    /// work, like `run_on_ui_thread`, done. Returns `Sdl2MtError::UiThreadExited` once the
+    /// loop has handled `exit`.
+    ///
+    /// This function executes synchronously.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle isn't synchronous, or this is not the thread that built it.
+    pub fn turn(&self) -> Result<(), Sdl2MtError> {
+        match turn() {
+            Some(true) => Ok(()),
+            Some(false) => Err(Sdl2MtError::UiThreadExited),
+            None if SYNC_EXITED.with(|exited| exited.get()) => Err(Sdl2MtError::UiThreadExited),
+            None if on_ui_thread() => Err(Sdl2MtError::WouldDeadlock),
+            None => panic!("sdl2_mt: turn() can only be called on the thread that built a synchronous Sdl2Mt"),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use SdlLambda;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub const PENDING: usize = 0;
+pub const RUNNING: usize = 1;
+pub const FINISHED: usize = 2;
+pub const CANCELLED: usize = 3;
+
+/// Where a lambda submitted with `run_cancelable`, or a job submitted with
+/// `spawn_background`, is in its life.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TaskState {
+    /// still waiting in the queue
+    Pending,
+    /// currently running on the UI thread
+    Running,
+    /// has run to completion
+    Finished,
+    /// was cancelled before it started, and will never run
+    Cancelled,
+}
+
+impl TaskState {
+    pub fn from_atomic(state: &AtomicUsize) -> TaskState {
+        match state.load(Ordering::SeqCst) {
+            PENDING => TaskState::Pending,
+            RUNNING => TaskState::Running,
+            FINISHED => TaskState::Finished,
+            _ => TaskState::Cancelled,
+        }
+    }
+}
+
+/// Refers to a lambda submitted with `run_cancelable`. Tokens can be cloned
+/// and sent to other threads freely.
+#[derive(Clone, Debug)]
+pub struct TaskToken(Arc<AtomicUsize>);
+
+impl TaskToken {
+    /// Prevents the lambda from running, if it hasn't started yet. Returns true
+    /// if the lambda was cancelled, or false if it has already started, finished,
+    /// or been cancelled before.
+    pub fn cancel(&self) -> bool {
+        self.0.compare_exchange(PENDING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+
+    pub fn state(&self) -> TaskState {
+        TaskState::from_atomic(&self.0)
+    }
+
+    /// Returns true once the lambda has run to completion.
+    pub fn has_run(&self) -> bool {
+        self.state() == TaskState::Finished
+    }
+}
+
+impl Sdl2Mt {
+    /// Executes a lambda function on the UI thread, returning a `TaskToken` that can
+    /// cancel the lambda as long as it hasn't started running yet, and tells whether it ran.
+    /// This keeps rapidly changing UI state from executing work that is already outdated.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn run_cancelable(&self, mut lambda: Box<SdlLambda>) -> Result<TaskToken, Sdl2MtError> {
+        let token = TaskToken(Arc::new(AtomicUsize::new(PENDING)));
+        let state = token.0.clone();
+        self.run_on_ui_thread(Box::new(move |sdl, windows| {
+            if state.compare_exchange(PENDING, RUNNING, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+                lambda(sdl, windows);
+                state.store(FINISHED, Ordering::SeqCst);
+            }
+        }))?;
+        Ok(token)
+    }
+}
@@ -0,0 +1,295 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use font::{self, GLYPH_SIZE};
+use pixels::Color;
+use rect::Rect;
+use render::{BlendMode, WindowCanvas};
+use render_target::RenderTarget;
+use scene::DEFAULT_SCENE_FRAME_INTERVAL;
+use state::UiState;
+use sys;
+use timers::{TimerAction, TimerId};
+use trace;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A character cell of a `TextGrid`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GridCell {
+    pub character: char,
+    pub foreground: Color,
+    pub background: Color,
+}
+
+impl Default for GridCell {
+    fn default() -> GridCell {
+        GridCell {
+            character: ' ',
+            foreground: Color::RGB(204, 204, 204),
+            background: Color::RGB(0, 0, 0),
+        }
+    }
+}
+
+struct Grid {
+    rows: usize,
+    columns: usize,
+    cells: Vec<GridCell>,
+    /// set when a cell changes, until the grid has been redrawn
+    dirty: bool,
+}
+
+impl Grid {
+    fn cell_mut(&mut self, row: usize, column: usize) -> Option<&mut GridCell> {
+        if row < self.rows && column < self.columns {
+            self.dirty = true;
+            Some(&mut self.cells[row * self.columns + column])
+        } else {
+            None
+        }
+    }
+}
+
+/// A grid of monospace character cells, like a terminal, shown in a window with
+/// `Sdl2Mt::show_text_grid`. Cells can be changed from any thread; the UI thread redraws
+/// the window when they have changed. Cells outside the grid are ignored.
+///
+/// Grids can be cloned and sent to other threads freely; clones share the same cells.
+#[derive(Clone)]
+pub struct TextGrid(Arc<Mutex<Grid>>);
+
+impl TextGrid {
+    pub fn new(rows: usize, columns: usize) -> TextGrid {
+        TextGrid(Arc::new(Mutex::new(Grid {
+            rows,
+            columns,
+            cells: vec![GridCell::default(); rows * columns],
+            dirty: true,
+        })))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.0.lock().unwrap().rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.0.lock().unwrap().columns
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<GridCell> {
+        let grid = self.0.lock().unwrap();
+        if row < grid.rows && column < grid.columns {
+            Some(grid.cells[row * grid.columns + column])
+        } else {
+            None
+        }
+    }
+
+    pub fn set_cell(&self, row: usize, column: usize, cell: GridCell) {
+        if let Some(target) = self.0.lock().unwrap().cell_mut(row, column) {
+            *target = cell;
+        }
+    }
+
+    /// Sets the character and its color, keeping the background of the cell.
+    pub fn set(&self, row: usize, column: usize, character: char, foreground: Color) {
+        if let Some(cell) = self.0.lock().unwrap().cell_mut(row, column) {
+            cell.character = character;
+            cell.foreground = foreground;
+        }
+    }
+
+    /// Writes `text` into a row from `column` on, cutting it off at the end of the row.
+    pub fn print(&self, row: usize, column: usize, text: &str, foreground: Color) {
+        let mut grid = self.0.lock().unwrap();
+        for (offset, character) in text.chars().enumerate() {
+            match grid.cell_mut(row, column + offset) {
+                Some(cell) => {
+                    cell.character = character;
+                    cell.foreground = foreground;
+                },
+                None => break,
+            }
+        }
+    }
+
+    /// Moves every row up by `lines`, filling the bottom with empty cells, like a terminal
+    /// does when it runs out of rows.
+    pub fn scroll_up(&self, lines: usize) {
+        let mut grid = self.0.lock().unwrap();
+        let shift = lines.min(grid.rows) * grid.columns;
+        grid.cells.drain(..shift);
+        grid.cells.extend(vec![GridCell::default(); shift]);
+        grid.dirty = true;
+    }
+
+    /// Empties every cell.
+    pub fn clear(&self) {
+        let mut grid = self.0.lock().unwrap();
+        for cell in &mut grid.cells {
+            *cell = GridCell::default();
+        }
+        grid.dirty = true;
+    }
+
+    /// The cells, if they have changed since the last call.
+    fn take_changes(&self) -> Option<(usize, Vec<GridCell>)> {
+        let mut grid = self.0.lock().unwrap();
+        if !grid.dirty {
+            return None;
+        }
+        grid.dirty = false;
+        Some((grid.columns, grid.cells.clone()))
+    }
+
+    fn mark_dirty(&self) {
+        self.0.lock().unwrap().dirty = true;
+    }
+}
+
+/// A grid shown in a window, kept in its `WindowState`.
+pub struct TextGridView {
+    grid: TextGrid,
+    scale: u32,
+    timer: TimerId,
+    /// every glyph of the built-in font in white, drawn once and tinted per cell
+    atlas: Option<RenderTarget>,
+}
+
+impl TextGridView {
+    fn atlas(&mut self, canvas: &mut WindowCanvas, previous: *mut sys::render::SDL_Texture) -> Result<&RenderTarget, String> {
+        if self.atlas.is_none() {
+            let glyphs: String = (b' '..=b'~').map(|byte| byte as char).collect();
+            let atlas = RenderTarget::new(canvas, glyphs.len() as u32 * GLYPH_SIZE, GLYPH_SIZE)?;
+            atlas.with_texture(|texture| texture.set_blend_mode(BlendMode::Blend));
+            let mut result = Ok(());
+            atlas.draw(canvas, previous, |canvas| {
+                canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                canvas.clear();
+                result = font::draw_text(canvas, &glyphs, 0, 0, 1, Color::RGB(255, 255, 255));
+            })?;
+            result?;
+            self.atlas = Some(atlas);
+        }
+        Ok(self.atlas.as_ref().unwrap())
+    }
+
+    fn draw(&mut self, canvas: &mut WindowCanvas, previous: *mut sys::render::SDL_Texture, columns: usize, cells: &[GridCell]) -> Result<(), String> {
+        let size = GLYPH_SIZE * self.scale;
+        let cell_rect = |index: usize| {
+            let (row, column) = (index / columns, index % columns);
+            Rect::new((column as u32 * size) as i32, (row as u32 * size) as i32, size, size)
+        };
+
+        canvas.set_draw_color(GridCell::default().background);
+        canvas.clear();
+        for (index, cell) in cells.iter().enumerate() {
+            if cell.background != GridCell::default().background {
+                canvas.set_draw_color(cell.background);
+                canvas.fill_rect(cell_rect(index))?;
+            }
+        }
+
+        let atlas = self.atlas(canvas, previous)?;
+        atlas.with_texture(|texture| {
+            for (index, cell) in cells.iter().enumerate() {
+                let glyph = match cell.character {
+                    ' ' => continue,
+                    character @ '!'..='~' => character as u32 - ' ' as u32,
+                    _ => '?' as u32 - ' ' as u32,
+                };
+                texture.set_color_mod(cell.foreground.r, cell.foreground.g, cell.foreground.b);
+                let source = Rect::new((glyph * GLYPH_SIZE) as i32, 0, GLYPH_SIZE, GLYPH_SIZE);
+                canvas.copy(texture, source, cell_rect(index))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl UiState {
+    /// Redraws and presents the window's grid if it has changed. Returns false once the
+    /// window is gone, or shows another grid, which stops the timer.
+    fn refresh_text_grid(&mut self, canvases: &mut HashMap<WindowId, WindowCanvas>, window_id: WindowId, timer: TimerId) -> bool {
+        let previous = self.active_target_raw(window_id);
+        {
+            let (canvas, view) = match (canvases.get_mut(&window_id), self.windows.get_mut(&window_id)) {
+                (Some(canvas), Some(settings)) => match settings.text_grid {
+                    Some(ref mut view) if view.timer == timer => (canvas, view),
+                    _ => return false,
+                },
+                _ => return false,
+            };
+            let (columns, cells) = match view.grid.take_changes() {
+                Some(changes) => changes,
+                None => return true,
+            };
+            if let Err(error) = view.draw(canvas, previous, columns, &cells) {
+                trace::sdl_error("text grid", error);
+            }
+        }
+        self.present(canvases, window_id);
+        true
+    }
+}
+
+impl Sdl2Mt {
+    /// Shows a text grid in the window, drawn with the built-in 8x8 font at `scale` times its
+    /// size, e.g. as the console of a debugger or emulator. The UI thread checks the grid
+    /// for changes once per frame, and redraws and presents the window when there are any.
+    /// A window shows one grid at a time; showing another replaces it.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn show_text_grid(&self, window_id: WindowId, grid: &TextGrid, scale: u32) -> Result<(), Sdl2MtError> {
+        let grid = grid.clone();
+        self.run_builtin(move |_sdl, windows, state| {
+            if !windows.contains_key(&window_id) {
+                return trace::window_not_found(window_id);
+            }
+            let timer = TimerId::next();
+            if let Some(old) = state.window(window_id).text_grid.take() {
+                state.timers.cancel(old.timer);
+            }
+            grid.mark_dirty();
+            state.window(window_id).text_grid = Some(TextGridView {
+                grid,
+                scale: scale.max(1),
+                timer,
+                atlas: None,
+            });
+
+            let now = state.clock.now();
+            let interval = DEFAULT_SCENE_FRAME_INTERVAL;
+            state.timers.add(timer, now, Some(interval), TimerAction::Builtin(Box::new(move |_sdl, windows, state| {
+                if !state.refresh_text_grid(windows, window_id, timer) {
+                    state.timers.cancel(timer);
+                }
+            })));
+        })
+    }
+
+    /// Opens a window that fits `grid` at `scale` times the size of the built-in font, and
+    /// shows the grid in it.
+    ///
+    /// This function executes synchronously.
+    pub fn create_text_grid_window<IntoString: Into<String>>(&self, name: IntoString, grid: &TextGrid, scale: u32) -> Result<WindowId, Sdl2MtError> {
+        let size = GLYPH_SIZE * scale.max(1);
+        let window_id = self.create_simple_window(name, grid.columns() as u32 * size, grid.rows() as u32 * size)?;
+        self.show_text_grid(window_id, grid, scale)?;
+        Ok(window_id)
+    }
+
+    /// Stops showing a text grid in the window.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn hide_text_grid(&self, window_id: WindowId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            let view = state.windows.get_mut(&window_id).and_then(|settings| settings.text_grid.take());
+            if let Some(view) = view {
+                state.timers.cancel(view.timer);
+            }
+        })
+    }
+}
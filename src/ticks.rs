@@ -0,0 +1,57 @@
+use Sdl2Mt;
+use sys;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Counts the frames presented through `Sdl2Mt::present`, across all windows.
+#[derive(Clone, Default)]
+pub struct FrameCounter(Arc<AtomicUsize>);
+
+impl FrameCounter {
+    pub fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed) as u64
+    }
+}
+
+impl Sdl2Mt {
+    /// The milliseconds since SDL was initialized, the same clock as the `timestamp` of events.
+    /// It wraps around after about 49 days.
+    ///
+    /// This function executes synchronously, but doesn't need the UI thread.
+    pub fn ticks(&self) -> u32 {
+        unsafe { sys::timer::SDL_GetTicks() }
+    }
+
+    /// The current value of SDL's high resolution counter, which counts
+    /// `performance_frequency()` times per second.
+    ///
+    /// This function executes synchronously, but doesn't need the UI thread.
+    pub fn performance_counter(&self) -> u64 {
+        unsafe { sys::timer::SDL_GetPerformanceCounter() }
+    }
+
+    /// How often `performance_counter()` counts per second.
+    pub fn performance_frequency(&self) -> u64 {
+        unsafe { sys::timer::SDL_GetPerformanceFrequency() }
+    }
+
+    /// The time since `earlier`, a value returned by `performance_counter()`, on any thread.
+    pub fn performance_elapsed(&self, earlier: u64) -> Duration {
+        let counts = self.performance_counter().saturating_sub(earlier);
+        let frequency = self.performance_frequency();
+        let nanos = counts as u128 * 1_000_000_000 / frequency as u128;
+        Duration::new((nanos / 1_000_000_000) as u64, (nanos % 1_000_000_000) as u32)
+    }
+
+    /// The number of frames presented through `present`, across all windows. It only ever
+    /// goes up, so worker threads can tag their work with the frame it was done for.
+    pub fn frame_number(&self) -> u64 {
+        self.shared.frames.get()
+    }
+}
@@ -0,0 +1,148 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use VideoSubsystem;
+use error;
+use ffi;
+use rect::Rect;
+use render::WindowCanvas;
+use state::UiState;
+use sys;
+use trace;
+use video::WindowPos;
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+
+/// How `tile_windows` arranges windows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// rows and columns of equal size, as close to square as they fit
+    Grid,
+    /// side by side, each as tall as the display
+    Columns,
+    /// stacked on top of each other, each as wide as the display
+    Rows,
+}
+
+/// The windows arranged by `tile_windows`, and what they were last arranged for.
+pub struct Tiling {
+    windows: Vec<WindowId>,
+    layout: Layout,
+    display_index: i32,
+    arranged_windows: Vec<WindowId>,
+    arranged_bounds: Option<Rect>,
+}
+
+/// The part of a display that isn't taken by task bars and docks, or the whole display
+/// if SDL can't tell.
+fn usable_bounds(video: &VideoSubsystem, display_index: i32) -> Result<Rect, String> {
+    let mut rect = sys::rect::SDL_Rect { x: 0, y: 0, w: 0, h: 0 };
+    if unsafe { ffi::SDL_GetDisplayUsableBounds(display_index, &mut rect) } == 0 {
+        Ok(Rect::new(rect.x, rect.y, rect.w as u32, rect.h as u32))
+    } else {
+        video.display_bounds(display_index)
+    }
+}
+
+/// Splits `bounds` into `count` cells, in reading order.
+pub fn layout_cells(layout: Layout, bounds: Rect, count: usize) -> Vec<Rect> {
+    if count == 0 {
+        return vec![];
+    }
+    let (columns, rows) = match layout {
+        Layout::Columns => (count, 1),
+        Layout::Rows => (1, count),
+        Layout::Grid => {
+            let mut columns = 1;
+            while columns * columns < count {
+                columns += 1;
+            }
+            (columns, count.div_ceil(columns))
+        },
+    };
+
+    let width = bounds.width() / columns as u32;
+    let height = bounds.height() / rows as u32;
+    (0..count).map(|index| {
+        let column = (index % columns) as i32;
+        let row = (index / columns) as i32;
+        Rect::new(bounds.x() + column * width as i32, bounds.y() + row * height as i32, width, height)
+    }).collect()
+}
+
+impl UiState {
+    /// Arranges the tiled windows again if some of them were closed, or the display changed.
+    /// This is called before every event poll, like `sync_kiosk`.
+    pub fn sync_tiling(&mut self, sdl: &mut Sdl, canvases: &mut HashMap<WindowId, WindowCanvas>) {
+        let tiling = match self.tiling {
+            Some(ref mut tiling) => tiling,
+            None => return,
+        };
+        let video = match sdl.video() {
+            Ok(video) => video,
+            Err(_) => return,
+        };
+
+        tiling.windows.retain(|id| canvases.contains_key(id));
+        let bounds = usable_bounds(&video, tiling.display_index).ok();
+        if tiling.windows == tiling.arranged_windows && bounds == tiling.arranged_bounds {
+            return;
+        }
+        if let Err(error) = tiling.arrange(&video, canvases) {
+            trace::sdl_error("tiling windows", error);
+        }
+    }
+}
+
+impl Tiling {
+    fn arrange(&mut self, video: &VideoSubsystem, canvases: &mut HashMap<WindowId, WindowCanvas>) -> Result<(), String> {
+        self.arranged_windows = self.windows.clone();
+        self.arranged_bounds = None;
+        let bounds = usable_bounds(video, self.display_index)?;
+        self.arranged_bounds = Some(bounds);
+
+        let cells = layout_cells(self.layout, bounds, self.windows.len());
+        for (id, cell) in self.windows.iter().zip(cells) {
+            if let Some(canvas) = canvases.get_mut(id) {
+                let window = canvas.window_mut();
+                window.set_position(WindowPos::Positioned(cell.x()), WindowPos::Positioned(cell.y()));
+                window.set_size(cell.width(), cell.height()).map_err(|error| error.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Sdl2Mt {
+    /// Arranges windows next to each other on a display, e.g. for a dashboard with a window
+    /// per plot. The windows are arranged again whenever one of them is closed, or the
+    /// display changes resolution. Windows that don't exist are left out. Tiling another set
+    /// of windows stops arranging the previous ones.
+    ///
+    /// Returns `Sdl2MtError::SdlError` if there is no display with that index.
+    ///
+    /// This function executes synchronously.
+    pub fn tile_windows(&self, window_ids: &[WindowId], layout: Layout, display_index: i32) -> Result<(), Sdl2MtError> {
+        let window_ids = window_ids.to_vec();
+        self.call_builtin(move |sdl, windows, state| {
+            let mut tiling = Tiling {
+                windows: window_ids.into_iter().filter(|id| windows.contains_key(id)).collect(),
+                layout,
+                display_index,
+                arranged_windows: vec![],
+                arranged_bounds: None,
+            };
+            let result = sdl.video().and_then(|video| tiling.arrange(&video, windows));
+            state.tiling = Some(tiling);
+            result.map_err(|error| error::sdl_error("tile_windows", error))
+        }).and_then(|result| result)
+    }
+
+    /// Stops arranging the windows tiled with `tile_windows`. They keep their place and size.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn untile_windows(&self) -> Result<(), Sdl2MtError> {
+        self.run_builtin(|_sdl, _windows, state| state.tiling = None)
+    }
+}
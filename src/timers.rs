@@ -0,0 +1,169 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use SdlBuiltin;
+use SdlLambda;
+use render::WindowCanvas;
+use state::UiState;
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Identifies a timer created with `schedule` or `schedule_repeating`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(usize);
+
+// timer IDs are handed out on the calling thread, so that scheduling doesn't need to block.
+static NEXT_TIMER_ID: AtomicUsize = AtomicUsize::new(0);
+
+impl TimerId {
+    pub fn next() -> TimerId {
+        TimerId(NEXT_TIMER_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// What a timer does when it becomes due.
+pub enum TimerAction {
+    Lambda(Box<SdlLambda>),
+    /// used by the helpers in this crate, which need access to the UI thread's state
+    Builtin(Box<SdlBuiltin>),
+}
+
+struct Timer {
+    id: TimerId,
+    deadline: Instant,
+    interval: Option<Duration>,
+    action: TimerAction,
+}
+
+/// The timers waiting to run on the UI thread.
+///
+/// There are rarely more than a handful of timers, so a plain list is used.
+pub struct Timers {
+    pending: Vec<Timer>,
+    /// timers cancelled while the due timers were running, and so not in `pending`
+    cancelled: Vec<TimerId>,
+}
+
+impl Timers {
+    pub fn new() -> Timers {
+        Timers { pending: vec![], cancelled: vec![] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn add(&mut self, id: TimerId, deadline: Instant, interval: Option<Duration>, action: TimerAction) {
+        self.pending.push(Timer { id, deadline, interval, action });
+    }
+
+    pub fn cancel(&mut self, id: TimerId) {
+        let count = self.pending.len();
+        self.pending.retain(|timer| timer.id != id);
+        if self.pending.len() == count {
+            self.cancelled.push(id);
+        }
+    }
+
+    /// The time at which the next timer is due, if any timers are pending.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.iter().map(|timer| timer.deadline).min()
+    }
+
+    /// Removes every timer whose deadline has passed, earliest first.
+    fn take_due(&mut self, now: Instant) -> Vec<Timer> {
+        let mut due = vec![];
+        let mut index = 0;
+        while index < self.pending.len() {
+            if self.pending[index].deadline <= now {
+                due.push(self.pending.swap_remove(index));
+            } else {
+                index += 1;
+            }
+        }
+
+        due.sort_by_key(|timer| (timer.deadline, timer.id.0));
+        due
+    }
+}
+
+impl UiState {
+    /// Runs every timer whose deadline has passed, earliest first.
+    pub fn run_due_timers(&mut self, sdl: &mut Sdl, windows: &mut HashMap<WindowId, WindowCanvas>) {
+        let now = self.clock.now();
+        let due = self.timers.take_due(now);
+        if due.is_empty() {
+            return;
+        }
+
+        self.timers.cancelled.clear();
+        for mut timer in due {
+            // an earlier timer in this batch may have cancelled this one
+            if self.timers.cancelled.contains(&timer.id) {
+                continue;
+            }
+
+            match timer.action {
+                TimerAction::Lambda(ref mut lambda) => lambda(sdl, windows),
+                TimerAction::Builtin(ref mut builtin) => builtin(sdl, windows, self),
+            }
+
+            if let Some(interval) = timer.interval {
+                if self.timers.cancelled.contains(&timer.id) {
+                    continue;
+                }
+
+                // if the UI thread fell behind, skip the missed runs instead of
+                // running the timer several times in a row to catch up.
+                timer.deadline += interval;
+                if timer.deadline <= now {
+                    timer.deadline = now + interval;
+                }
+                self.timers.pending.push(timer);
+            }
+        }
+        self.timers.cancelled.clear();
+    }
+}
+
+impl Sdl2Mt {
+    /// Runs a lambda function on the UI thread once `delay` has passed, measured
+    /// from the time of this call. Timers are driven by the UI thread's message loop,
+    /// so no extra thread is involved, but a timer can run late if the UI thread is
+    /// busy with other work when it becomes due.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn schedule(&self, delay: Duration, lambda: Box<SdlLambda>) -> Result<TimerId, Sdl2MtError> {
+        let id = TimerId::next();
+        let deadline = self.shared.clock.now() + delay;
+        self.run_builtin(move |_sdl, _windows, state| {
+            state.timers.add(id, deadline, None, TimerAction::Lambda(lambda))
+        })?;
+        Ok(id)
+    }
+
+    /// Runs a lambda function on the UI thread every `interval`, starting one interval
+    /// from now, until the timer is cancelled with `cancel_timer`. If the UI thread is
+    /// too busy to keep up, missed runs are skipped rather than queued up.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn schedule_repeating(&self, interval: Duration, lambda: Box<SdlLambda>) -> Result<TimerId, Sdl2MtError> {
+        let id = TimerId::next();
+        let deadline = self.shared.clock.now() + interval;
+        self.run_builtin(move |_sdl, _windows, state| {
+            state.timers.add(id, deadline, Some(interval), TimerAction::Lambda(lambda))
+        })?;
+        Ok(id)
+    }
+
+    /// Cancels a timer. A one-shot timer that has already run, or a timer that
+    /// was already cancelled, is ignored.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn cancel_timer(&self, id: TimerId) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| state.timers.cancel(id))
+    }
+}
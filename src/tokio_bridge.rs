@@ -0,0 +1,83 @@
+//! Glue for applications that run a tokio runtime next to the UI thread.
+
+use Sdl2Mt;
+use Sdl2MtError;
+use dispatch::{Forwarded, SendEvent};
+use render::WindowCanvas;
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+impl Sdl2Mt {
+    /// Executes a function on the UI thread, returning a tokio oneshot receiver that
+    /// completes with the function's return value. If the UI thread exits before running
+    /// the function, the receiver completes with an error.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn run_on_ui_thread_oneshot<T, F>(&self, function: F) -> oneshot::Receiver<T>
+        where T: Send + 'static,
+              F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) -> T + Send + 'static
+    {
+        let (tx, rx) = oneshot::channel();
+        let mut work = Some((function, tx));
+        // if the send fails, tx is dropped along with the lambda and rx reports an error
+        let _ = self.run_on_ui_thread(Box::new(move |sdl, windows| {
+            if let Some((function, tx)) = work.take() {
+                let _ = tx.send(function(sdl, windows));
+            }
+        }));
+        rx
+    }
+
+    /// Sends every new event to a tokio mpsc channel, as soon as the UI thread sees it.
+    /// When the channel is full, events are kept for `handle_ui_events` instead. Forwarding
+    /// stops once the receiver is dropped.
+    ///
+    /// See `forward_events` for how events are pumped while they are being forwarded.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn forward_events_tokio(&self, sender: mpsc::Sender<SendEvent>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, _windows, state| {
+            state.add_event_forwarder(Box::new(move |event| {
+                match sender.try_send(SendEvent::new(event)) {
+                    Ok(()) => Forwarded::Handled,
+                    Err(mpsc::error::TrySendError::Full(_)) => Forwarded::Unhandled,
+                    Err(mpsc::error::TrySendError::Closed(_)) => Forwarded::Disconnected,
+                }
+            }));
+        })
+    }
+
+    /// Broadcasts every new event to any number of tokio tasks. More receivers can be
+    /// created with `resubscribe()` on the returned receiver. Broadcasting stops once
+    /// every receiver has been dropped.
+    ///
+    /// Returns `Sdl2MtError::InvalidArgument` if `capacity` is zero, or larger than tokio's
+    /// broadcast channels allow.
+    ///
+    /// See `forward_events` for how events are pumped while they are being forwarded.
+    ///
+    /// This function executes synchronously.
+    pub fn broadcast_events(&self, capacity: usize) -> Result<broadcast::Receiver<SendEvent>, Sdl2MtError> {
+        // tokio panics on these, which would take the UI thread down with it
+        if capacity == 0 {
+            return Err(Sdl2MtError::InvalidArgument("broadcast capacity must be greater than zero"));
+        }
+        if capacity > usize::MAX / 2 {
+            return Err(Sdl2MtError::InvalidArgument("broadcast capacity is too large"));
+        }
+        self.call_builtin(move |_sdl, _windows, state| {
+            let (sender, receiver) = broadcast::channel(capacity);
+            state.add_event_forwarder(Box::new(move |event| {
+                match sender.send(SendEvent::new(event)) {
+                    Ok(_) => Forwarded::Handled,
+                    Err(_) => Forwarded::Disconnected,
+                }
+            }));
+            receiver
+        })
+    }
+}
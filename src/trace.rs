@@ -0,0 +1,173 @@
+use Priority;
+use Sdl2Message;
+use Sdl2MtError;
+use WindowId;
+use error;
+use spy::HandleId;
+use watchdog::WorkKind;
+
+use std::time::Instant;
+#[cfg(feature = "tracing")]
+use tracing::{self, Span};
+
+/// A message waiting in the queue. With the `tracing` feature, each message carries a span
+/// that is created when it is submitted, as a child of whatever span the submitting thread
+/// is in, and that the UI thread enters while it works on the message. The span records
+/// how long the message waited in the queue and how long it took to run.
+pub struct Queued {
+    pub message: Sdl2Message,
+    pub trace: Trace,
+    /// the handle the message was submitted through, for `spy_messages`
+    pub origin: HandleId,
+    pub queued_at: Instant,
+}
+
+impl Queued {
+    pub fn new(priority: Priority, origin: HandleId, message: Sdl2Message) -> Queued {
+        let trace = Trace::submitted(priority, WorkKind::of(&message));
+        Queued {
+            message,
+            trace,
+            origin,
+            queued_at: Instant::now(),
+        }
+    }
+}
+
+pub struct Trace {
+    #[cfg(feature = "tracing")]
+    span: Span,
+    #[cfg(feature = "tracing")]
+    queued_at: Instant,
+}
+
+impl Trace {
+    #[cfg(feature = "tracing")]
+    fn submitted(priority: Priority, kind: WorkKind) -> Trace {
+        let span = trace_span!(
+            "sdl2_mt::message",
+            kind = ?kind,
+            priority = ?priority,
+            queue_time_us = tracing::field::Empty,
+            execution_time_us = tracing::field::Empty,
+        );
+        span.in_scope(|| trace!("message submitted"));
+        Trace {
+            span,
+            queued_at: Instant::now(),
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline]
+    fn submitted(_priority: Priority, _kind: WorkKind) -> Trace {
+        Trace {}
+    }
+
+    /// Called by the UI thread when it starts working on the message. The work is
+    /// over when the returned value is dropped.
+    #[cfg(feature = "tracing")]
+    pub fn executing(self) -> Executing {
+        let started = Instant::now();
+        self.span.record("queue_time_us", (started - self.queued_at).as_micros() as u64);
+        Executing {
+            span: self.span.entered(),
+            started,
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline]
+    pub fn executing(self) -> Executing {
+        Executing {}
+    }
+}
+
+pub struct Executing {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Executing {
+    fn drop(&mut self) {
+        self.span.record("execution_time_us", self.started.elapsed().as_micros() as u64);
+    }
+}
+
+/// Reports messages that were dropped without running, e.g. to make room in a full queue
+/// or because the UI thread exited.
+#[inline]
+pub fn dropped(_reason: &'static str, _count: usize) {
+    if _count == 0 {
+        return;
+    }
+    #[cfg(feature = "tracing")]
+    warn!(count = _count, reason = _reason, "sdl2_mt dropped messages");
+    #[cfg(feature = "log")]
+    log_crate::warn!("sdl2_mt dropped {} messages because {}", _count, _reason);
+    error::report(Sdl2MtError::MessagesDropped(_count));
+}
+
+/// Reports a message that was refused, e.g. because the queue was full.
+#[inline]
+pub fn rejected(_error: &Sdl2MtError) {
+    #[cfg(feature = "tracing")]
+    warn!(error = ?_error, "sdl2_mt refused a message");
+    #[cfg(feature = "log")]
+    log_crate::debug!("sdl2_mt refused a message: {}", _error);
+}
+
+/// Reports a reply that nobody was waiting for anymore, e.g. after a timeout.
+#[inline]
+pub fn reply_discarded() {
+    #[cfg(feature = "tracing")]
+    debug!("sdl2_mt discarded a reply nobody was waiting for");
+    #[cfg(feature = "log")]
+    log_crate::debug!("sdl2_mt discarded a reply nobody was waiting for");
+    error::report(Sdl2MtError::ReplyDiscarded);
+}
+
+/// Reports work for a window that doesn't exist, which has nobody to return the error to.
+#[inline]
+pub fn window_not_found(window_id: WindowId) {
+    #[cfg(feature = "tracing")]
+    debug!(window_id = window_id.raw(), "sdl2_mt has no window with this ID");
+    #[cfg(feature = "log")]
+    log_crate::debug!("sdl2_mt has no window with ID {}", window_id);
+    error::report(Sdl2MtError::WindowNotFound(window_id));
+}
+
+/// Reports an SDL call that failed inside one of this crate's helpers, which have
+/// nobody to return the error to.
+#[inline]
+pub fn sdl_error(operation: &'static str, error: String) {
+    #[cfg(feature = "tracing")]
+    warn!(operation = operation, error = %error, "SDL call failed on the sdl2_mt UI thread");
+    #[cfg(feature = "log")]
+    log_crate::warn!("{} failed on the sdl2_mt UI thread: {}", operation, error);
+    error::report(error::sdl_error(operation, error));
+}
+
+/// Reports an error returned by a closure of the application's, which has nobody else
+/// to return it to.
+#[inline]
+pub fn callback_error(callback: &'static str, error: String) {
+    #[cfg(feature = "tracing")]
+    warn!(callback = callback, error = %error, "callback failed on the sdl2_mt UI thread");
+    #[cfg(feature = "log")]
+    log_crate::warn!("{} failed on the sdl2_mt UI thread: {}", callback, error);
+    error::report(error::callback_error(callback, error));
+}
+
+/// Reports a failed send or reply, for handles with `FailurePolicy::Report`.
+#[inline]
+pub fn call_failed(error: &Sdl2MtError) {
+    #[cfg(feature = "tracing")]
+    warn!(error = %error, "sdl2_mt call failed");
+    #[cfg(feature = "log")]
+    log_crate::warn!("sdl2_mt call failed: {}", error);
+    error::report(error.clone());
+}
@@ -0,0 +1,115 @@
+use Sdl2Message;
+use Sdl2Message::{Inline, Lambda};
+use Sdl2MtError;
+#[cfg(target_os = "ios")]
+use Sdl;
+use SdlLambda;
+#[cfg(target_os = "ios")]
+use WindowId;
+use clock::Clock;
+use inline_fn::InlineFn;
+use queue::MessageQueue;
+#[cfg(target_os = "ios")]
+use render::WindowCanvas;
+use spy::{HandleId, OwnId};
+#[cfg(target_os = "ios")]
+use state::UiState;
+use timers::{TimerAction, TimerId};
+
+use std::cell::RefCell;
+#[cfg(target_os = "ios")]
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+thread_local! {
+    /// Set on the thread that runs the UI loop, for the `UiContext`s created there.
+    static CURRENT: RefCell<Option<UiHandle>> = const { RefCell::new(None) };
+}
+
+/// Queues more work from code that is already running on the UI thread, such as a `ui!`
+/// block, through `UiContext::handle`. A render callback can use it to schedule a texture
+/// upload or a timer.
+///
+/// Unlike `Sdl2Mt`, it only has asynchronous methods, and they never wait for room in the
+/// queue, so they can't deadlock the UI thread: when the queue is full, they return
+/// `Sdl2MtError::QueueFull` whatever the backpressure policy. The work is queued with normal
+/// priority, behind the messages that are already waiting.
+#[derive(Clone)]
+pub struct UiHandle {
+    queue: Arc<MessageQueue>,
+    clock: Clock,
+    origin: HandleId,
+}
+
+impl UiHandle {
+    /// Makes a handle available on the calling thread, which runs the UI loop.
+    pub fn install(queue: Arc<MessageQueue>, clock: Clock) {
+        let handle = UiHandle {
+            queue,
+            clock,
+            origin: OwnId::new().0,
+        };
+        CURRENT.with(|current| *current.borrow_mut() = Some(handle));
+    }
+
+    /// The handle installed on the calling thread. Only the UI thread has one.
+    pub fn current() -> Option<UiHandle> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Executes a lambda function on the UI thread, after the current one has returned.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// even when the UI thread's queue is full.
+    pub fn run_on_ui_thread(&self, lambda: Box<SdlLambda>) -> Result<(), Sdl2MtError> {
+        self.send(Lambda(lambda))
+    }
+
+    /// Like `Sdl2Mt::schedule`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// even when the UI thread's queue is full.
+    pub fn schedule(&self, delay: Duration, lambda: Box<SdlLambda>) -> Result<TimerId, Sdl2MtError> {
+        let id = TimerId::next();
+        let deadline = self.clock.now() + delay;
+        self.send(Inline(InlineFn::new(move |_sdl, _windows, state| {
+            state.timers.add(id, deadline, None, TimerAction::Lambda(lambda))
+        })))?;
+        Ok(id)
+    }
+
+    /// Like `Sdl2Mt::schedule_repeating`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// even when the UI thread's queue is full.
+    pub fn schedule_repeating(&self, interval: Duration, lambda: Box<SdlLambda>) -> Result<TimerId, Sdl2MtError> {
+        let id = TimerId::next();
+        let deadline = self.clock.now() + interval;
+        self.send(Inline(InlineFn::new(move |_sdl, _windows, state| {
+            state.timers.add(id, deadline, Some(interval), TimerAction::Lambda(lambda))
+        })))?;
+        Ok(id)
+    }
+
+    /// Like `Sdl2Mt::cancel_timer`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread,
+    /// even when the UI thread's queue is full.
+    pub fn cancel_timer(&self, id: TimerId) -> Result<(), Sdl2MtError> {
+        self.send(Inline(InlineFn::new(move |_sdl, _windows, state| state.timers.cancel(id))))
+    }
+
+    /// Queues one of the crate's own functions, which work with the UI thread's bookkeeping.
+    #[cfg(target_os = "ios")]
+    pub(crate) fn run_builtin<F>(&self, function: F) -> Result<(), Sdl2MtError>
+        where F: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>, &mut UiState) + Send + 'static
+    {
+        self.send(Inline(InlineFn::new(function)))
+    }
+
+    // the UI thread would wait for itself to make room, so a full queue is an error instead
+    fn send(&self, message: Sdl2Message) -> Result<(), Sdl2MtError> {
+        self.queue.try_push(self.origin, message)
+    }
+}
@@ -0,0 +1,63 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use Shared;
+use error;
+use on_ui_thread;
+
+use std::any::Any;
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+/// What the panic hook found out about a panic on the UI thread.
+pub struct UiPanic {
+    /// set by `Sdl2MtBuilder::panic_hook`
+    pub enabled: bool,
+    pub message: Mutex<Option<String>>,
+}
+
+impl UiPanic {
+    pub fn new(enabled: bool) -> UiPanic {
+        UiPanic {
+            enabled,
+            message: Mutex::new(None),
+        }
+    }
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<Any>"
+    }
+}
+
+/// Installs a panic hook that records panics on the UI thread, in front of whatever
+/// hook was installed before, which still runs for every panic.
+pub fn install_hook(shared: Arc<Shared>) {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if on_ui_thread() {
+            let message = match info.location() {
+                Some(location) => format!("{} at {}:{}", payload_message(info.payload()), location.file(), location.line()),
+                None => payload_message(info.payload()).to_owned(),
+            };
+            if let Ok(mut recorded) = shared.panic.message.lock() {
+                *recorded = Some(message.clone());
+            }
+            error::report(Sdl2MtError::UiThreadPanicked(message));
+        }
+        previous(info);
+    }));
+}
+
+impl Sdl2Mt {
+    /// Returns the message and location of the panic that ended the UI thread, if it
+    /// panicked. Panics are only recorded when the UI thread was started with
+    /// `Sdl2MtBuilder::panic_hook(true)`.
+    pub fn ui_thread_panic(&self) -> Option<String> {
+        self.shared.panic.message.lock().unwrap().clone()
+    }
+}
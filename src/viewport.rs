@@ -0,0 +1,78 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use error;
+use rect::Rect;
+use render::WindowCanvas;
+use trace;
+
+impl Sdl2Mt {
+    /// Defines a named region of a window that can later be drawn into with
+    /// `draw_in_viewport`. Defining a viewport with an existing name replaces it.
+    ///
+    /// This lets several threads each own a panel of the same window without
+    /// having to know where on the window that panel lives.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn define_viewport<IntoString: Into<String>>(&self, window_id: WindowId, name: IntoString, rect: Rect) -> Result<(), Sdl2MtError> {
+        let name = name.into();
+        self.run_builtin(move |_sdl, windows, state| {
+            if windows.contains_key(&window_id) {
+                state.window(window_id).viewports.insert(name, rect);
+            } else {
+                trace::window_not_found(window_id);
+            }
+        })
+    }
+
+    /// Removes a viewport previously created with `define_viewport`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn remove_viewport<IntoString: Into<String>>(&self, window_id: WindowId, name: IntoString) -> Result<(), Sdl2MtError> {
+        let name = name.into();
+        self.run_builtin(move |_sdl, _windows, state| {
+            if let Some(settings) = state.windows.get_mut(&window_id) {
+                settings.viewports.remove(&name);
+            }
+        })
+    }
+
+    /// Runs a drawing function on the UI thread with the canvas restricted to a named
+    /// viewport. Coordinates inside the function are relative to the top-left corner of
+    /// the viewport, and nothing can be drawn outside of it. The previous viewport and
+    /// clip rectangle are restored afterwards.
+    ///
+    /// If the window or the viewport does not exist, the function is not run, and
+    /// `Sdl2MtError::WindowNotFound` or `Sdl2MtError::InvalidArgument` is reported
+    /// through `errors()`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn draw_in_viewport<IntoString, F>(&self, window_id: WindowId, name: IntoString, draw: F) -> Result<(), Sdl2MtError>
+        where IntoString: Into<String>,
+              F: FnOnce(&mut WindowCanvas) + Send + 'static
+    {
+        let name = name.into();
+        self.run_builtin(move |_sdl, windows, state| {
+            let canvas = match windows.get_mut(&window_id) {
+                Some(canvas) => canvas,
+                None => return trace::window_not_found(window_id),
+            };
+            let rect = match state.windows.get(&window_id).and_then(|settings| settings.viewports.get(&name)) {
+                Some(rect) => *rect,
+                None => return error::report(Sdl2MtError::InvalidArgument("there is no viewport with that name")),
+            };
+
+            let old_viewport = canvas.viewport();
+            let old_clip = canvas.clip_rect();
+
+            canvas.set_viewport(rect);
+            // the clip rectangle is relative to the viewport
+            canvas.set_clip_rect(Rect::new(0, 0, rect.width(), rect.height()));
+
+            draw(canvas);
+
+            canvas.set_viewport(old_viewport);
+            canvas.set_clip_rect(old_clip);
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use event::Event;
+use sys;
+
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Instant;
+
+/// Lets the UI thread sleep in SDL's own event wait while it has nothing to do, so that
+/// it wakes up right away for both new events and new messages. Whoever queues a message
+/// while the UI thread is waiting pushes an SDL user event of a type registered just
+/// for this, which ends the wait.
+pub struct Wakeup {
+    /// the registered event type, or 0 until the UI thread has registered one
+    event_type: AtomicU32,
+    /// set by the UI thread while it waits for events
+    waiting: AtomicBool,
+}
+
+impl Wakeup {
+    pub fn new() -> Wakeup {
+        Wakeup {
+            event_type: AtomicU32::new(0),
+            waiting: AtomicBool::new(false),
+        }
+    }
+
+    /// Called once by the UI thread, after SDL has been initialized.
+    pub fn register(&self) -> u32 {
+        let event_type = unsafe { sys::event::SDL_RegisterEvents(1) };
+        // SDL returns u32::MAX when it has run out of user event types
+        if event_type != !0 {
+            self.event_type.store(event_type, Ordering::SeqCst);
+        }
+        self.event_type.load(Ordering::SeqCst)
+    }
+
+    /// Called after a message has been queued.
+    pub fn wake(&self) {
+        if !self.waiting.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        let event_type = self.event_type.load(Ordering::SeqCst);
+        if event_type == 0 {
+            return;
+        }
+
+        // SDL_PushEvent may be called from any thread
+        let mut event = sys::event::SDL_Event { data: [0; 56] };
+        unsafe {
+            *event.type_() = event_type;
+            sys::event::SDL_PushEvent(&mut event);
+        }
+    }
+
+    /// Called by the UI thread when it has nothing to do. Returns once there is an SDL event,
+    /// a message has been queued, or `deadline` has passed. `has_messages` is checked after
+    /// the UI thread has started waiting, so it can also check for other work that wakes it.
+    ///
+    /// Until the wakeup event type has been registered, this only waits for SDL events.
+    pub fn wait<F: Fn() -> bool>(&self, has_messages: F, deadline: Option<Instant>) {
+        // a message queued before `waiting` is set is seen by has_messages,
+        // and one queued after it pushes a wakeup event.
+        self.waiting.store(true, Ordering::SeqCst);
+        if !has_messages() {
+            unsafe {
+                match deadline {
+                    Some(deadline) => {
+                        let timeout = deadline.saturating_duration_since(Instant::now());
+                        // round up, so that the timer is actually due once the wait is over
+                        let millis = timeout.as_micros().div_ceil(1000);
+                        sys::event::SDL_WaitEventTimeout(ptr::null_mut(), millis.min(i32::MAX as u128) as i32);
+                    },
+                    None => {
+                        sys::event::SDL_WaitEvent(ptr::null_mut());
+                    },
+                }
+            }
+        }
+        self.waiting.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Whether an event is one of the wakeup events, which nobody but the UI thread needs to see.
+pub fn is_wakeup_event(event_type: u32, event: &Event) -> bool {
+    match *event {
+        Event::User { type_, .. } => event_type != 0 && type_ == event_type,
+        _ => false,
+    }
+}
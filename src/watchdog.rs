@@ -0,0 +1,181 @@
+use Sdl2Message::{self, *};
+use Sdl2Mt;
+
+use std::cmp;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Called by the watchdog when the UI thread has been busy with one piece of work for too long.
+pub type SdlWatchdog = dyn FnMut(&SlowWork) + Send;
+
+/// What the UI thread was doing when the watchdog noticed it was stuck.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WorkKind {
+    /// a lambda, including the functions passed to `call` and to a `Submitter`
+    Lambda,
+    CreateWindow,
+    /// an event handler passed to `handle_ui_events`
+    HandleEvents,
+    /// presenting a frame with `present`, including its `on_present` callbacks
+    Present,
+    /// lambdas scheduled with `schedule` and friends
+    Timers,
+    IdleCallbacks,
+    /// work done by this crate's helpers, e.g. `create_kiosk_windows`
+    Internal,
+}
+
+const WORK_KINDS: [WorkKind; 7] = [
+    WorkKind::Lambda,
+    WorkKind::CreateWindow,
+    WorkKind::HandleEvents,
+    WorkKind::Present,
+    WorkKind::Timers,
+    WorkKind::IdleCallbacks,
+    WorkKind::Internal,
+];
+
+impl WorkKind {
+    pub fn of(message: &Sdl2Message) -> WorkKind {
+        match *message {
+            Lambda(..) | Inline(..) | DrainSubmitters => WorkKind::Lambda,
+            CreateWindow(..) => WorkKind::CreateWindow,
+            HandleEvent(..) => WorkKind::HandleEvents,
+            Present(..) | PresentColor(..) => WorkKind::Present,
+            Builtin(..) | Clear(..) | Exit => WorkKind::Internal,
+        }
+    }
+}
+
+/// Passed to the watchdog callback.
+#[derive(Copy, Clone, Debug)]
+pub struct SlowWork {
+    pub kind: WorkKind,
+    /// how long the work had been running when the watchdog noticed; it may still be running
+    pub running_for: Duration,
+}
+
+struct Config {
+    threshold: Duration,
+    callback: Box<SdlWatchdog>,
+}
+
+struct Settings {
+    config: Option<Config>,
+    thread_running: bool,
+}
+
+/// Keeps track of what the UI thread is doing, so that a separate watchdog thread can
+/// notice when a single piece of work takes too long. A stuck UI thread can't report
+/// that it is stuck, which is why the watchdog needs a thread of its own.
+pub struct Watchdog {
+    base: Instant,
+    /// when the current work started, in nanoseconds since `base` plus one, or 0 while idle
+    started: AtomicU64,
+    kind: AtomicUsize,
+    /// counts pieces of work, so that each of them is reported at most once
+    sequence: AtomicU64,
+    ui_thread_exited: AtomicBool,
+    settings: Mutex<Settings>,
+}
+
+impl Watchdog {
+    pub fn new() -> Watchdog {
+        Watchdog {
+            base: Instant::now(),
+            started: AtomicU64::new(0),
+            kind: AtomicUsize::new(0),
+            sequence: AtomicU64::new(0),
+            ui_thread_exited: AtomicBool::new(false),
+            settings: Mutex::new(Settings {
+                config: None,
+                thread_running: false,
+            }),
+        }
+    }
+
+    /// Called by the UI thread when it starts working on something.
+    pub fn begin(&self, kind: WorkKind, started: Instant) {
+        self.kind.store(kind as usize, Ordering::Relaxed);
+        self.sequence.fetch_add(1, Ordering::Relaxed);
+        self.started.store((started - self.base).as_nanos() as u64 + 1, Ordering::Release);
+    }
+
+    /// Called by the UI thread when it is done with the work passed to `begin`.
+    pub fn end(&self) {
+        self.started.store(0, Ordering::Release);
+    }
+
+    pub fn ui_thread_exited(&self) {
+        self.ui_thread_exited.store(true, Ordering::Release);
+    }
+
+    /// The watchdog thread, which runs for as long as a watchdog is configured.
+    fn watch(&self) {
+        let mut last_reported = 0;
+        loop {
+            let threshold = {
+                let mut settings = self.settings.lock().unwrap();
+                match settings.config {
+                    Some(ref config) if !self.ui_thread_exited.load(Ordering::Acquire) => config.threshold,
+                    _ => {
+                        settings.thread_running = false;
+                        return;
+                    },
+                }
+            };
+
+            // checking a few times per threshold keeps the report reasonably close to it
+            thread::sleep(cmp::min(cmp::max(threshold / 4, Duration::from_millis(1)), Duration::from_millis(100)));
+
+            let started = self.started.load(Ordering::Acquire);
+            let sequence = self.sequence.load(Ordering::Relaxed);
+            if started == 0 || sequence == last_reported {
+                continue;
+            }
+
+            let running_for = self.base.elapsed().checked_sub(Duration::from_nanos(started - 1)).unwrap_or_default();
+            let mut settings = self.settings.lock().unwrap();
+            if let Some(ref mut config) = settings.config {
+                if running_for >= config.threshold {
+                    last_reported = sequence;
+                    (config.callback)(&SlowWork {
+                        kind: WORK_KINDS[self.kind.load(Ordering::Relaxed)],
+                        running_for,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Sdl2Mt {
+    /// Starts a watchdog that calls `callback` whenever the UI thread spends longer than
+    /// `threshold` on a single lambda, event handler, timer or other piece of work. While
+    /// the UI thread is busy, all windows stop responding to input, so this helps to find
+    /// the lambda that froze them. Each piece of work is reported at most once, while it
+    /// is still running.
+    ///
+    /// The callback runs on a separate watchdog thread, and must not call `set_watchdog`
+    /// or `clear_watchdog`. Setting a new watchdog replaces the previous one.
+    pub fn set_watchdog(&self, threshold: Duration, callback: Box<SdlWatchdog>) {
+        let mut settings = self.shared.watchdog.settings.lock().unwrap();
+        settings.config = Some(Config {
+            threshold,
+            callback,
+        });
+
+        if !settings.thread_running {
+            settings.thread_running = true;
+            let shared = self.shared.clone();
+            thread::spawn(move || shared.watchdog.watch());
+        }
+    }
+
+    /// Stops the watchdog started by `set_watchdog`, if there is one.
+    pub fn clear_watchdog(&self) {
+        self.shared.watchdog.settings.lock().unwrap().config = None;
+    }
+}
@@ -0,0 +1,135 @@
+use Sdl2Mt;
+use Sdl2MtError;
+use WindowId;
+use dpi;
+use error;
+use ffi;
+use get_error;
+use render::RendererInfo;
+use trace;
+
+impl Sdl2Mt {
+    /// Returns the size of the window in screen coordinates, or `Sdl2MtError::WindowNotFound`
+    /// if there is no window with that ID.
+    ///
+    /// This function executes synchronously.
+    pub fn window_size(&self, window_id: WindowId) -> Result<(u32, u32), Sdl2MtError> {
+        self.with_canvas(window_id, |canvas| canvas.window().size())
+    }
+
+    /// Returns the size of the window's drawable area in pixels, or
+    /// `Sdl2MtError::WindowNotFound` if there is no window with that ID. On HiDPI displays
+    /// this can be larger than `window_size`, as long as the window was created with
+    /// `allow_highdpi()`.
+    ///
+    /// This function executes synchronously.
+    pub fn drawable_size(&self, window_id: WindowId) -> Result<(u32, u32), Sdl2MtError> {
+        self.with_canvas(window_id, |canvas| canvas.window().drawable_size())
+    }
+
+    /// Returns the ratio of drawable pixels to screen coordinates for the window, e.g. 2.0
+    /// on a typical "retina" display, or `Sdl2MtError::WindowNotFound` if there is no window
+    /// with that ID.
+    ///
+    /// This function executes synchronously.
+    pub fn scale_factor(&self, window_id: WindowId) -> Result<f32, Sdl2MtError> {
+        self.with_canvas(window_id, |canvas| dpi::window_scale(canvas))
+    }
+
+    /// When enabled, the UI thread keeps the window's logical render size equal
+    /// to its size in screen coordinates, updating it whenever the window is
+    /// resized or moved to a display with a different DPI. Drawing code can then
+    /// work in screen coordinates while SDL renders at the full drawable resolution.
+    ///
+    /// The UI thread pumps events itself, so the logical size follows the window as soon
+    /// as SDL reports it moved or resized, whether or not anyone calls `handle_ui_events`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_auto_dpi_scaling(&self, window_id: WindowId, enabled: bool) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            if !windows.contains_key(&window_id) {
+                return trace::window_not_found(window_id);
+            }
+            let settings = state.window(window_id);
+            settings.auto_dpi_scaling = enabled;
+            if !enabled && settings.logical_size.is_none() {
+                if let Some(canvas) = windows.get_mut(&window_id) {
+                    // a logical size of 0x0 turns logical scaling off again
+                    if let Err(error) = canvas.set_logical_size(0, 0) {
+                        trace::sdl_error("set_logical_size", error.to_string());
+                    }
+                }
+            }
+            state.update_logical_size(windows, window_id);
+        })
+    }
+
+    /// Turns vsync on or off for the window's renderer without recreating the canvas.
+    /// Returns `Sdl2MtError::WindowNotFound` if there is no window with that ID, and
+    /// `Sdl2MtError::SdlError` if the render backend does not support changing vsync at
+    /// runtime, or if the program runs with a version of SDL older than 2.0.18. Older
+    /// versions work with everything else.
+    ///
+    /// This function executes synchronously.
+    pub fn set_vsync(&self, window_id: WindowId, enabled: bool) -> Result<(), Sdl2MtError> {
+        self.with_canvas(window_id, move |canvas| {
+            let set_vsync = ffi::render_set_vsync()
+                .ok_or_else(|| error::sdl_error("SDL_RenderSetVSync", "requires SDL 2.0.18 or newer"))?;
+            match unsafe { set_vsync(canvas.raw(), enabled as i32) } {
+                0 => Ok(()),
+                _ => Err(error::sdl_error("SDL_RenderSetVSync", get_error())),
+            }
+        }).and_then(|result| result)
+    }
+
+    /// Returns information about the window's renderer: the driver name, flags,
+    /// supported texture formats and maximum texture size. Returns
+    /// `Sdl2MtError::WindowNotFound` if there is no window with that ID.
+    ///
+    /// This function executes synchronously.
+    pub fn renderer_info(&self, window_id: WindowId) -> Result<RendererInfo, Sdl2MtError> {
+        self.with_canvas(window_id, |canvas| canvas.info())
+    }
+
+    /// Sets a fixed, device independent resolution for the window's canvas. SDL scales
+    /// everything drawn to fit the window while keeping the aspect ratio, adding borders
+    /// as needed. `None` goes back to drawing in window pixels.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_logical_size(&self, window_id: WindowId, size: Option<(u32, u32)>) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            if !windows.contains_key(&window_id) {
+                return trace::window_not_found(window_id);
+            }
+            let settings = state.window(window_id);
+            settings.logical_size = size;
+            if size.is_none() && !settings.auto_dpi_scaling {
+                if let Some(canvas) = windows.get_mut(&window_id) {
+                    if let Err(error) = canvas.set_logical_size(0, 0) {
+                        trace::sdl_error("set_logical_size", error.to_string());
+                    }
+                }
+            }
+            state.update_logical_size(windows, window_id);
+        })
+    }
+
+    /// When enabled, the logical size of the window is only ever scaled by whole factors,
+    /// with the result centered in the window. This keeps pixel art crisp, with every
+    /// logical pixel covering the same number of screen pixels. The scaling is recomputed
+    /// by the UI thread whenever the window is resized.
+    ///
+    /// This only has an effect once a logical size has been set with `set_logical_size`
+    /// or `set_auto_dpi_scaling`.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn set_integer_scale(&self, window_id: WindowId, enabled: bool) -> Result<(), Sdl2MtError> {
+        self.run_builtin(move |_sdl, windows, state| {
+            if !windows.contains_key(&window_id) {
+                return trace::window_not_found(window_id);
+            }
+            state.window(window_id).integer_scale = enabled;
+            state.update_logical_size(windows, window_id);
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// Identifies a window created through sdl2_mt. It holds SDL's own window ID, which is what
+/// events carry in their `window_id`, but is a type of its own so that it can't be mixed up
+/// with other numbers, like texture or timer IDs. Compare it to an event's `window_id` with
+/// `WindowId::from_raw`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WindowId(u32);
+
+impl WindowId {
+    /// The window with SDL's window ID `id`, e.g. from the `window_id` of an event.
+    pub fn from_raw(id: u32) -> WindowId {
+        WindowId(id)
+    }
+
+    /// SDL's window ID, for calls into SDL itself.
+    pub fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for WindowId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,151 @@
+use SdlCreateWindow;
+use rwops::RWops;
+use simple_window::{CreateFailure, create_failed};
+use surface::Surface;
+use trace;
+
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Settings that every window made by `create_simple_window` starts with, set with
+/// `Sdl2MtBuilder::default_window_profile`, so that the windows of a tool look alike.
+///
+/// The default profile makes resizable windows with a software renderer, centered on screen.
+#[derive(Clone, Debug)]
+pub struct WindowProfile {
+    title_prefix: String,
+    icon_bmp: Option<Vec<u8>>,
+    size: Option<(u32, u32)>,
+    resizable: bool,
+    borderless: bool,
+    allow_highdpi: bool,
+    accelerated: bool,
+    vsync: bool,
+}
+
+impl WindowProfile {
+    pub fn new() -> WindowProfile {
+        WindowProfile {
+            title_prefix: String::new(),
+            icon_bmp: None,
+            size: None,
+            resizable: true,
+            borderless: false,
+            allow_highdpi: false,
+            accelerated: false,
+            vsync: false,
+        }
+    }
+
+    /// Put in front of every window title, e.g. "My Tool - ".
+    pub fn title_prefix<IntoString: Into<String>>(mut self, prefix: IntoString) -> WindowProfile {
+        self.title_prefix = prefix.into();
+        self
+    }
+
+    /// The window icon, as a BMP file. Icons that can't be loaded are left out.
+    pub fn icon_bmp(mut self, icon_bmp: Vec<u8>) -> WindowProfile {
+        self.icon_bmp = Some(icon_bmp);
+        self
+    }
+
+    /// The size of windows created with a width or height of 0.
+    pub fn size(mut self, width: u32, height: u32) -> WindowProfile {
+        self.size = Some((width, height));
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> WindowProfile {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn borderless(mut self) -> WindowProfile {
+        self.borderless = true;
+        self
+    }
+
+    /// Creates windows with a drawable size in pixels on high DPI displays.
+    pub fn allow_highdpi(mut self) -> WindowProfile {
+        self.allow_highdpi = true;
+        self
+    }
+
+    /// Uses a hardware accelerated renderer instead of the software one.
+    pub fn accelerated(mut self) -> WindowProfile {
+        self.accelerated = true;
+        self
+    }
+
+    /// Synchronizes presenting with the display's refresh rate. This needs `accelerated`.
+    pub fn vsync(mut self) -> WindowProfile {
+        self.vsync = true;
+        self
+    }
+
+    /// Makes the window creator that `create_simple_window` uses. Failed SDL calls go to
+    /// `failure`, or are reported through `errors()` if it is `None`.
+    pub fn window_creator(&self, name: String, width: u32, height: u32, failure: Option<CreateFailure>) -> Box<SdlCreateWindow> {
+        let profile = self.clone();
+        let (width, height) = match profile.size {
+            Some(size) if width == 0 || height == 0 => size,
+            _ => (width, height),
+        };
+        Box::new(move |_sdl, video_subsystem| {
+            let title = format!("{}{}", profile.title_prefix, name);
+            let mut builder = video_subsystem.window(&title, width, height);
+            builder.position_centered();
+            if profile.resizable {
+                builder.resizable();
+            }
+            if profile.borderless {
+                builder.borderless();
+            }
+            if profile.allow_highdpi {
+                builder.allow_highdpi();
+            }
+            let mut window = match builder.build() {
+                Ok(window) => window,
+                Err(error) => {
+                    create_failed(&failure, "SDL_CreateWindow", error);
+                    return None;
+                },
+            };
+
+            if let Some(ref icon_bmp) = profile.icon_bmp {
+                match RWops::from_bytes(icon_bmp).and_then(|mut rwops| Surface::load_bmp_rw(&mut rwops)) {
+                    Ok(icon) => window.set_icon(icon),
+                    Err(error) => trace::sdl_error("SDL_LoadBMP_RW", error),
+                }
+            }
+
+            let mut canvas = window.into_canvas();
+            if profile.accelerated {
+                canvas = canvas.accelerated();
+            } else {
+                canvas = canvas.software();
+            }
+            if profile.vsync {
+                canvas = canvas.present_vsync();
+            }
+            let canvas = match canvas.build() {
+                Ok(canvas) => canvas,
+                Err(error) => {
+                    create_failed(&failure, "SDL_CreateRenderer", error);
+                    return None;
+                },
+            };
+
+            // avoids some potential graphical glitches
+            sleep(Duration::from_millis(20));
+
+            Some(canvas)
+        })
+    }
+}
+
+impl Default for WindowProfile {
+    fn default() -> WindowProfile {
+        WindowProfile::new()
+    }
+}
@@ -0,0 +1,113 @@
+use Sdl2Mt;
+use render::WindowCanvas;
+use task::{self, TaskState};
+use Sdl;
+use WindowId;
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The threads that run `spawn_background` jobs, started when the first job is submitted.
+pub struct WorkerPool {
+    sender: Mutex<Option<mpsc::Sender<Job>>>,
+}
+
+impl WorkerPool {
+    pub fn new() -> WorkerPool {
+        WorkerPool { sender: Mutex::new(None) }
+    }
+
+    fn submit(&self, job: Job) {
+        let mut sender = self.sender.lock().unwrap();
+        if sender.is_none() {
+            *sender = Some(start_workers());
+        }
+        // the workers never exit, so they are always there to receive
+        let _ = sender.as_ref().unwrap().send(job);
+    }
+}
+
+fn start_workers() -> mpsc::Sender<Job> {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let count = thread::available_parallelism().map(|count| count.get()).unwrap_or(4);
+    for index in 0..count {
+        let receiver = receiver.clone();
+        let spawned = thread::Builder::new().name(format!("sdl2_mt worker {}", index)).spawn(move || loop {
+            let job = match receiver.lock().unwrap().recv() {
+                Ok(job) => job,
+                Err(_) => return,
+            };
+            job();
+        });
+        spawned.expect("sdl2_mt: couldn't start a worker thread");
+    }
+    sender
+}
+
+/// Refers to a job submitted with `spawn_background`. Handles can be cloned and sent to
+/// other threads freely.
+#[derive(Clone, Debug)]
+pub struct JobHandle(Arc<AtomicUsize>);
+
+impl JobHandle {
+    /// Stops the job from starting, or its result from being applied on the UI thread, if
+    /// that hasn't happened yet. A job that is already running is not interrupted, but its
+    /// result is thrown away. Returns false if the result was applied, or it was cancelled before.
+    pub fn cancel(&self) -> bool {
+        let state = &self.0;
+        state.compare_exchange(task::PENDING, task::CANCELLED, Ordering::SeqCst, Ordering::SeqCst).is_ok() ||
+            state.compare_exchange(task::RUNNING, task::CANCELLED, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+
+    /// `Pending` until a worker picks the job up, `Running` until its result has been
+    /// applied on the UI thread, and `Finished` after that. Jobs that panic are `Cancelled`.
+    pub fn state(&self) -> TaskState {
+        TaskState::from_atomic(&self.0)
+    }
+}
+
+impl Sdl2Mt {
+    /// Runs `job` on a pool of worker threads managed by this crate, then runs the closure it
+    /// returns on the UI thread, to apply its result, e.g. to upload a decoded image as a
+    /// texture. The pool has a thread per CPU, and is started by the first job.
+    ///
+    /// A job that panics is dropped, without taking its worker thread down with it. If the
+    /// UI thread has exited by the time the job finishes, the result is dropped too.
+    ///
+    /// This function executes asynchronously. It will *not* block the calling thread.
+    pub fn spawn_background<J, R>(&self, job: J) -> JobHandle
+        where J: FnOnce() -> R + Send + 'static,
+              R: FnOnce(&mut Sdl, &mut HashMap<WindowId, WindowCanvas>) + Send + 'static
+    {
+        let handle = JobHandle(Arc::new(AtomicUsize::new(task::PENDING)));
+        let state = handle.0.clone();
+        let sdlh = self.clone();
+        self.shared.workers.submit(Box::new(move || {
+            if state.compare_exchange(task::PENDING, task::RUNNING, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+                return;
+            }
+            let apply = match panic::catch_unwind(AssertUnwindSafe(job)) {
+                Ok(apply) => apply,
+                Err(_) => {
+                    state.store(task::CANCELLED, Ordering::SeqCst);
+                    return;
+                },
+            };
+            if state.load(Ordering::SeqCst) != task::RUNNING {
+                return;
+            }
+            let _ = sdlh.run_on_ui_thread_once(move |sdl, windows| {
+                if state.compare_exchange(task::RUNNING, task::FINISHED, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+                    apply(sdl, windows);
+                }
+            });
+        }));
+        handle
+    }
+}
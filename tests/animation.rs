@@ -0,0 +1,42 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Animation, Sdl2MtBuilder};
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn wait_until<F: Fn() -> bool>(condition: F) {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while !condition() {
+        assert!(Instant::now() < deadline, "timed out");
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Every frame is presented once, then the animation finishes, unless it loops
+#[test]
+fn play_animation() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("animation", 2, 2).unwrap();
+
+    let mut animation = Animation::new(2, 2);
+    assert!(!animation.push_frame(vec![0; 3], Duration::from_millis(10)));
+    assert!(animation.push_frame(vec![255; 16], Duration::from_millis(10)));
+    assert!(animation.push_frame(vec![0; 16], Duration::from_millis(10)));
+    assert_eq!(animation.duration(), Duration::from_millis(20));
+    let animation = Arc::new(animation);
+
+    let frame = sdlh.frame_number();
+    let once = sdlh.play_animation(window, animation.clone(), false).unwrap();
+    wait_until(|| once.is_finished());
+    assert_eq!(sdlh.frame_number(), frame + 2);
+
+    let looping = sdlh.play_animation(window, animation, true).unwrap();
+    wait_until(|| sdlh.frame_number() >= frame + 6);
+    assert!(!looping.is_finished());
+    looping.stop();
+    wait_until(|| looping.is_finished());
+}
@@ -0,0 +1,26 @@
+#![cfg(all(feature = "sdl", feature = "async"))]
+
+extern crate async_std;
+extern crate sdl2_mt;
+extern crate smol;
+
+/// The futures don't depend on any particular executor
+#[test]
+fn smol_block_on() {
+    let sdlh = sdl2_mt::init();
+
+    let answer = smol::block_on(sdlh.run_on_ui_thread_async(|_sdl, windows| windows.len() + 42));
+    assert_eq!(answer.unwrap(), 42);
+
+    smol::block_on(sdlh.flush_async()).unwrap();
+}
+
+#[test]
+fn async_std_block_on() {
+    let sdlh = sdl2_mt::init();
+
+    let answer = async_std::task::block_on(sdlh.run_on_ui_thread_async(|_sdl, windows| windows.len() + 42));
+    assert_eq!(answer.unwrap(), 42);
+
+    async_std::task::block_on(sdlh.handle_ui_events_async(Box::new(|_sdl, _windows, _event| false))).unwrap();
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{AutoPause, Sdl2MtBuilder, WindowId};
+use sdl2_mt::event::{Event, WindowEvent};
+
+use std::sync::{Arc, Mutex};
+
+fn push_window_event(sdlh: &sdl2_mt::Sdl2Mt, window_id: WindowId, win_event: WindowEvent) {
+    sdlh.call(move |sdl, _windows| {
+        sdl.event().unwrap().push_event(Event::Window {
+            timestamp: 0,
+            window_id: window_id.raw(),
+            win_event,
+        }).unwrap();
+    }).unwrap();
+    sdlh.handle_ui_events(Box::new(|_sdl, _windows, _event| false)).unwrap();
+}
+
+/// Rendering pauses while the only window is minimized, and callbacks hear about it
+#[test]
+fn auto_pause_when_minimized() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("auto pause", 32, 32).unwrap();
+    let changes = Arc::new(Mutex::new(vec![]));
+    let record = changes.clone();
+    sdlh.on_auto_pause(Box::new(move |paused| record.lock().unwrap().push(paused))).unwrap();
+    sdlh.set_auto_pause(AutoPause::WhenMinimized).unwrap();
+
+    push_window_event(&sdlh, window, WindowEvent::Minimized);
+    assert_eq!(sdlh.is_auto_paused(), Ok(true));
+    sdlh.present(window).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(sdlh.dump_state().unwrap().windows[0].frames_presented, 0);
+
+    push_window_event(&sdlh, window, WindowEvent::Restored);
+    assert_eq!(sdlh.is_auto_paused(), Ok(false));
+    assert_eq!(*changes.lock().unwrap(), vec![true, false]);
+}
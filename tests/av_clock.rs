@@ -0,0 +1,39 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{AvClock, Sdl2MtBuilder};
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Frames are presented when the clock reaches them, and dropped when they are too late
+#[test]
+fn present_frames_on_the_clock() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("av clock", 8, 8).unwrap();
+
+    let clock = AvClock::new();
+    clock.pause();
+    assert!(clock.is_paused());
+    clock.seek(Duration::from_secs(10));
+    assert_eq!(clock.now(), Duration::from_secs(10));
+
+    let (tx, rx) = mpsc::channel();
+    let late = tx.clone();
+    sdlh.present_frame_at(window, &clock, Duration::from_secs(1), move |_canvas| late.send("late").unwrap()).unwrap();
+    let due = tx.clone();
+    sdlh.present_frame_at(window, &clock, Duration::from_secs(10), move |_canvas| due.send("due").unwrap()).unwrap();
+    sdlh.present_frame_at(window, &clock, Duration::from_millis(10_050), move |_canvas| tx.send("next").unwrap()).unwrap();
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(1)).unwrap(), "due");
+    assert_eq!(clock.dropped_frames(), 1);
+    // the clock is paused, so the next frame waits
+    thread::sleep(Duration::from_millis(100));
+    assert!(rx.try_recv().is_err());
+
+    clock.resume();
+    assert_eq!(rx.recv_timeout(Duration::from_secs(1)).unwrap(), "next");
+    assert!(clock.now() >= Duration::from_millis(10_050));
+}
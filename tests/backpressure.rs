@@ -0,0 +1,50 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Backpressure, Priority, Sdl2MtBuilder, Sdl2MtError};
+use std::sync::mpsc::channel;
+
+/// Fills a small queue while the UI thread is busy
+#[test]
+fn queue_full_error() {
+    let sdlh = Sdl2MtBuilder::new()
+        .queue_capacity(1).unwrap()
+        .backpressure(Backpressure::Error)
+        .build();
+
+    // keep the UI thread busy until the queue has been filled
+    let (tx, rx) = channel::<()>();
+    let (started_tx, started_rx) = channel();
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = rx.recv();
+    })).unwrap();
+    started_rx.recv().unwrap();
+
+    sdlh.run_on_ui_thread(Box::new(|_sdl, _windows| {})).unwrap();
+    match sdlh.run_on_ui_thread(Box::new(|_sdl, _windows| {})) {
+        Err(Sdl2MtError::QueueFull) => {},
+        _ => panic!("expected the queue to be full"),
+    }
+
+    // the high priority lane is bounded just the same
+    sdlh.run_on_ui_thread_with_priority(Priority::High, Box::new(|_sdl, _windows| {})).unwrap();
+    match sdlh.run_on_ui_thread_with_priority(Priority::High, Box::new(|_sdl, _windows| {})) {
+        Err(Sdl2MtError::QueueFull) => {},
+        _ => panic!("expected the high priority lane to be full"),
+    }
+
+    tx.send(()).unwrap();
+    sdlh.flush().unwrap();
+    sdlh.exit().unwrap();
+}
+
+/// A queue with no room for any message is refused up front
+#[test]
+fn zero_queue_capacity() {
+    match Sdl2MtBuilder::new().queue_capacity(0) {
+        Err(Sdl2MtError::InvalidArgument(_)) => {},
+        _ => panic!("expected a queue capacity of zero to be refused"),
+    }
+}
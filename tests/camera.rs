@@ -0,0 +1,35 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Camera, Sdl2MtBuilder};
+use sdl2_mt::pixels::Color;
+use sdl2_mt::rect::Rect;
+
+/// Zooming keeps the point under the cursor in place, and drawing goes through the camera
+#[test]
+fn camera_transforms_drawing() {
+    let camera = Camera::new((10.0, 20.0), 2.0);
+    assert_eq!(camera.to_window(12.0, 21.0), (4.0, 2.0));
+    assert_eq!(camera.to_world(4.0, 2.0), (12.0, 21.0));
+    let zoomed = camera.zoom_at(4.0, 2.0, 4.0);
+    assert_eq!(zoomed.scale, 8.0);
+    assert_eq!(zoomed.to_world(4.0, 2.0), (12.0, 21.0));
+    assert_eq!(camera.pan_by(2.0, 0.0).to_world(6.0, 2.0), (12.0, 21.0));
+
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("camera", 16, 16).unwrap();
+    assert_eq!(sdlh.camera(window).unwrap(), Camera::default());
+
+    sdlh.set_camera(window, (2.0, 2.0), 2.0).unwrap();
+    assert_eq!(sdlh.camera(window).unwrap(), Camera::new((2.0, 2.0), 2.0));
+    sdlh.clear(window, Color::RGB(0, 0, 0)).unwrap();
+    // the world rect from (4, 4) to (6, 6) covers window pixels 4 to 7
+    sdlh.fill_rects(window, &[Rect::new(4, 4, 2, 2)], Color::RGB(255, 0, 0)).unwrap();
+
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(3, 3), (0, 0, 0));
+    assert_eq!(pixels.pixel(4, 4), (255, 0, 0));
+    assert_eq!(pixels.pixel(7, 7), (255, 0, 0));
+    assert_eq!(pixels.pixel(8, 8), (0, 0, 0));
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "sdl")]
+
+// a separate process from tests/fair_dispatch.rs, since the dispatch policy is process-wide
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{EventDispatch, Sdl2Mt, Sdl2MtBuilder};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use std::sync::mpsc;
+
+/// Takes every event the handle is offered, returning the keys that were pressed
+fn take_keys(sdlh: &Sdl2Mt) -> Vec<Keycode> {
+    let (tx, rx) = mpsc::channel();
+    sdlh.handle_ui_events(Box::new(move |_sdl, _windows, event| {
+        if let Event::KeyDown { keycode: Some(keycode), .. } = *event {
+            tx.send(keycode).unwrap();
+        }
+        true
+    })).unwrap();
+    rx.try_iter().collect()
+}
+
+/// Under by-window dispatch, events for a claimed window only go to the handle that claimed it
+#[test]
+fn by_window() {
+    let first = Sdl2MtBuilder::new().headless().build();
+    let second = first.clone();
+    let absent = first.clone();
+    let first_window = first.create_simple_window("first", 16, 16).unwrap();
+    let second_window = first.create_simple_window("second", 16, 16).unwrap();
+    let absent_window = first.create_simple_window("absent", 16, 16).unwrap();
+    first.set_event_dispatch(EventDispatch::ByWindow).unwrap();
+    first.claim_window_events(first_window).unwrap();
+    second.claim_window_events(second_window).unwrap();
+    absent.claim_window_events(absent_window).unwrap();
+
+    take_keys(&first);
+    take_keys(&second);
+
+    first.inject_key(second_window, Keycode::B, true).unwrap();
+    first.inject_key(first_window, Keycode::A, true).unwrap();
+    // the handle that claimed this window never handles events, so anyone may take them
+    first.inject_key(absent_window, Keycode::C, true).unwrap();
+    first.flush().unwrap();
+
+    assert_eq!(take_keys(&first), vec![Keycode::A, Keycode::C]);
+    assert_eq!(take_keys(&second), vec![Keycode::B]);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "sdl")]
+
+// a separate process from tests/event_order.rs, since claiming a window changes the dispatch policy
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{EventDispatch, Sdl2Mt, Sdl2MtBuilder};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use std::sync::mpsc;
+
+/// Offers every event to the handle, taking the keys in `wanted`, and returns the keys it saw
+fn offered_keys(sdlh: &Sdl2Mt, wanted: &'static [Keycode]) -> Vec<Keycode> {
+    let (tx, rx) = mpsc::channel();
+    sdlh.handle_ui_events(Box::new(move |_sdl, _windows, event| {
+        match *event {
+            Event::KeyDown { keycode: Some(keycode), .. } => {
+                tx.send(keycode).unwrap();
+                wanted.contains(&keycode)
+            },
+            _ => true,
+        }
+    })).unwrap();
+    rx.try_iter().collect()
+}
+
+/// A window's later events wait behind an earlier event that is kept for another handle
+#[test]
+fn later_events_wait_for_earlier_ones() {
+    let first = Sdl2MtBuilder::new().headless().build();
+    let second = first.clone();
+    let window = first.create_simple_window("order", 16, 16).unwrap();
+    first.set_event_dispatch(EventDispatch::ByWindow).unwrap();
+    first.claim_window_events(window).unwrap();
+    offered_keys(&first, &[]);
+    offered_keys(&second, &[]);
+
+    first.inject_key(window, Keycode::A, true).unwrap();
+    first.flush().unwrap();
+    // B arrives after the window changed hands, but A is still waiting for the first handle
+    second.claim_window_events(window).unwrap();
+    first.inject_key(window, Keycode::B, true).unwrap();
+    first.flush().unwrap();
+
+    assert_eq!(offered_keys(&second, &[Keycode::B]), vec![]);
+    assert_eq!(offered_keys(&first, &[Keycode::A]), vec![Keycode::A]);
+    assert_eq!(offered_keys(&second, &[Keycode::B]), vec![Keycode::B]);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::thread::sleep;
+
+/// Queues several lambdas with the same key while the UI thread is busy
+#[test]
+fn run_coalesced() {
+    let sdlh = sdl2_mt::init();
+
+    // keep the UI thread busy so that the lambdas pile up
+    sdlh.run_on_ui_thread(Box::new(|_sdl, _windows| sleep(Duration::from_millis(100)))).unwrap();
+
+    let ran = Arc::new(Mutex::new(vec![]));
+    for i in 0..10 {
+        let ran = ran.clone();
+        sdlh.run_coalesced("redraw", Box::new(move |_sdl, _windows| {
+            ran.lock().unwrap().push(i);
+        })).unwrap();
+    }
+
+    sdlh.flush().unwrap();
+    assert_eq!(*ran.lock().unwrap(), vec![9]);
+}
+
+/// Submits another coalesced lambda when dropped
+struct Resubmit(sdl2_mt::Sdl2Mt);
+
+impl Drop for Resubmit {
+    fn drop(&mut self) {
+        self.0.run_coalesced("other", Box::new(|_sdl, _windows| {})).unwrap();
+    }
+}
+
+/// A replaced lambda is dropped without holding the lock, so what it captured can use the handle
+#[test]
+fn replaced_lambda_drops_outside_lock() {
+    let sdlh = sdl2_mt::init();
+    sdlh.run_on_ui_thread(Box::new(|_sdl, _windows| sleep(Duration::from_millis(100)))).unwrap();
+
+    let resubmit = Resubmit(sdlh.clone());
+    sdlh.run_coalesced("replaced", Box::new(move |_sdl, _windows| {
+        let _ = &resubmit;
+    })).unwrap();
+    sdlh.run_coalesced("replaced", Box::new(|_sdl, _windows| {})).unwrap();
+    sdlh.flush().unwrap();
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{CallbackOn, CompletionQueue, Sdl2MtBuilder};
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The callback fires once with the lambda's result, either on the UI thread or from the queue
+#[test]
+fn callback_fires_once() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+
+    let results = Arc::new(Mutex::new(vec![]));
+    let on_ui = results.clone();
+    sdlh.run_on_ui_thread_with_callback(|_sdl, _windows| 6 * 7, CallbackOn::UiThread, move |value| {
+        on_ui.lock().unwrap().push(value);
+    }).unwrap();
+    sdlh.flush().unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(*results.lock().unwrap(), vec![42]);
+
+    let queue = CompletionQueue::new();
+    let queued = Arc::new(Mutex::new(vec![]));
+    let on_queue = queued.clone();
+    sdlh.run_on_ui_thread_with_callback(|_sdl, _windows| "done", CallbackOn::Queue(&queue), move |value| {
+        on_queue.lock().unwrap().push(value);
+    }).unwrap();
+
+    // nothing runs until the owner of the queue asks for it
+    sdlh.flush().unwrap();
+    assert!(queued.lock().unwrap().is_empty());
+    assert_eq!(queue.wait_and_run(Duration::from_secs(5)), 1);
+    assert_eq!(*queued.lock().unwrap(), vec!["done"]);
+
+    sdlh.flush().unwrap();
+    assert_eq!(queue.run_pending(), 0);
+    assert_eq!(queue.wait_and_run(Duration::from_millis(50)), 0);
+    assert_eq!(*queued.lock().unwrap(), vec!["done"]);
+}
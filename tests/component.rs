@@ -0,0 +1,32 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Script, Sdl2MtBuilder, TestClock};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+use sdl2_mt::pixels::Color;
+
+use std::time::Duration;
+
+/// A component's event handler and render callback share its state
+#[test]
+fn component_state() {
+    let clock = TestClock::new();
+    let sdlh = Sdl2MtBuilder::new().headless().test_clock(clock).build();
+    let window = sdlh.create_simple_window("component", 8, 8).unwrap();
+
+    sdlh.attach_component(window, 0u8, |presses, event| {
+        if let Event::KeyDown { .. } = *event {
+            *presses += 100;
+        }
+    }, |presses, canvas| {
+        canvas.set_draw_color(Color::RGB(*presses, 0, 0));
+        canvas.clear();
+    }).unwrap();
+
+    Script::new().key_press(Keycode::Space).run(&sdlh).unwrap();
+    sdlh.advance_clock(Duration::from_millis(20)).unwrap();
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(0, 0), (100, 0, 0));
+}
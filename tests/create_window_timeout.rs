@@ -0,0 +1,39 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError};
+
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// A busy UI thread makes `create_window_timeout` give up, the window is still made once
+/// the UI thread gets to it, and its late reply isn't mistaken for the next one
+#[test]
+fn create_window_timeout() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+
+    let (started_tx, started_rx) = channel();
+    let (release_tx, release_rx) = channel::<()>();
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+    })).unwrap();
+    started_rx.recv().unwrap();
+
+    let late = sdlh.create_window_timeout(Box::new(|_sdl, video| {
+        Some(video.window("late", 64, 48).hidden().build().unwrap().into_canvas().software().build().unwrap())
+    }), Duration::from_millis(50));
+    assert_eq!(late, Err(Sdl2MtError::Timeout));
+
+    release_tx.send(()).unwrap();
+    let window = sdlh.create_window(Box::new(|_sdl, video| {
+        Some(video.window("next", 32, 24).hidden().build().unwrap().into_canvas().software().build().unwrap())
+    })).unwrap().unwrap();
+    assert_eq!(sdlh.window_size(window), Ok((32, 24)));
+    assert_eq!(sdlh.call(|_sdl, windows| windows.len()).unwrap(), 2);
+
+    // an idle UI thread answers well within the timeout
+    let quick = sdlh.create_window_timeout(Box::new(|_sdl, _video| None), Duration::from_secs(5));
+    assert_eq!(quick, Ok(None));
+}
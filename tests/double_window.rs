@@ -1,3 +1,5 @@
+#![cfg(feature = "sdl")]
+
 extern crate sdl2_mt;
 
 /// Attempts to create two windows
@@ -0,0 +1,28 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{hint, DpiAwareness, Sdl2MtBuilder};
+
+/// The DPI options set their hints, and resizing a window doesn't change its scale
+#[test]
+fn dpi_hints_and_scale_changes() {
+    let sdlh = Sdl2MtBuilder::new()
+        .headless()
+        .synchronous()
+        .windows_dpi_awareness(DpiAwareness::PerMonitorV2)
+        .windows_dpi_scaling(true)
+        .build();
+    assert_eq!(hint::get("SDL_WINDOWS_DPI_AWARENESS"), Some("permonitorv2".to_owned()));
+    assert_eq!(hint::get("SDL_WINDOWS_DPI_SCALING"), Some("1".to_owned()));
+
+    let window = sdlh.create_simple_window("dpi", 32, 32).unwrap();
+    let scale_changes = sdlh.scale_changes().unwrap();
+    sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+        windows.get_mut(&window).unwrap().window_mut().set_size(64, 48).unwrap();
+    }).unwrap();
+    sdlh.flush().unwrap();
+    sdlh.turn().unwrap();
+    assert!(scale_changes.try_recv().is_err());
+    assert_eq!(sdlh.scale_factor(window), Ok(1.0));
+}
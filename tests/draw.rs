@@ -0,0 +1,29 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::pixels::Color;
+use sdl2_mt::rect::{Point, Rect};
+
+/// Drawing methods draw on the canvas in order, without closures
+#[test]
+fn draw_without_closures() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("draw", 16, 16).unwrap();
+
+    sdlh.fill_rects(window, &[Rect::new(0, 0, 16, 16)], Color::RGB(0, 0, 0)).unwrap();
+    sdlh.fill_rects(window, &[Rect::new(0, 0, 4, 4), Rect::new(8, 8, 4, 4)], Color::RGB(255, 0, 0)).unwrap();
+    sdlh.draw_lines(window, &[Point::new(0, 15), Point::new(15, 15)], Color::RGB(0, 255, 0)).unwrap();
+    sdlh.draw_points(window, &[Point::new(15, 0)], Color::RGB(0, 0, 255)).unwrap();
+    sdlh.draw_rects(window, &[Rect::new(4, 4, 4, 4)], Color::RGB(255, 255, 255)).unwrap();
+
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(1, 1), (255, 0, 0));
+    assert_eq!(pixels.pixel(9, 9), (255, 0, 0));
+    assert_eq!(pixels.pixel(5, 15), (0, 255, 0));
+    assert_eq!(pixels.pixel(15, 0), (0, 0, 255));
+    assert_eq!(pixels.pixel(4, 4), (255, 255, 255));
+    assert_eq!(pixels.pixel(5, 5), (0, 0, 0));
+    sdlh.present(window).unwrap();
+}
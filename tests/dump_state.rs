@@ -0,0 +1,22 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use std::time::Duration;
+
+/// The dump lists windows and registered timers
+#[test]
+fn dump_state() {
+    let sdlh = sdl2_mt::init();
+    let window = sdlh.create_simple_window("dump_state", 64, 48).unwrap();
+    let timer = sdlh.schedule(Duration::from_secs(60), Box::new(|_sdl, _windows| {})).unwrap();
+
+    let dump = sdlh.dump_state().unwrap();
+    let dumped = dump.windows.iter().find(|dumped| dumped.id == window).unwrap();
+    assert_eq!(dumped.title, "dump_state");
+    assert_eq!(dumped.size, (64, 48));
+    assert!(dump.timers >= 1);
+    assert!(dump.to_string().contains("\"dump_state\" 64x48"));
+
+    sdlh.cancel_timer(timer).unwrap();
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtError;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// A reply that arrives after the caller gave up is reported on the error stream
+#[test]
+fn reply_discarded() {
+    let sdlh = sdl2_mt::init();
+    let errors = sdlh.errors();
+
+    let (started_tx, started_rx) = channel();
+    let (tx, rx) = channel::<()>();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = rx.recv_timeout(Duration::from_secs(1));
+    }).unwrap();
+    started_rx.recv().unwrap();
+
+    let result = sdlh.handle_ui_events_timeout(Box::new(|_sdl, _windows, _event| false), Duration::from_millis(10));
+    assert_eq!(result, Err(Sdl2MtError::Timeout));
+    tx.send(()).unwrap();
+    sdlh.flush().unwrap();
+
+    assert_eq!(errors.recv_timeout(Duration::from_secs(5)), Ok(Sdl2MtError::ReplyDiscarded));
+}
+
+/// Lambdas can read SDL's description of the last error
+#[test]
+fn last_error() {
+    let sdlh = sdl2_mt::init();
+    let error = sdlh.call(|_sdl, _windows| {
+        sdl2_mt::set_error("something broke").unwrap();
+        sdl2_mt::last_error()
+    }).unwrap();
+    assert_eq!(error, Some("something broke".to_owned()));
+}
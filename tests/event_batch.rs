@@ -0,0 +1,41 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use std::sync::mpsc;
+
+/// The batch handler sees all pending events at once, and they are gone afterwards
+#[test]
+fn handle_ui_events_batch() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("batch", 16, 16).unwrap();
+
+    // left unhandled by a per-event handler first, so it comes from the queue
+    sdlh.inject_key(window, Keycode::A, true).unwrap();
+    sdlh.flush().unwrap();
+    sdlh.handle_ui_events(Box::new(|_sdl, _windows, _event| false)).unwrap();
+    sdlh.inject_key(window, Keycode::B, true).unwrap();
+    sdlh.inject_quit().unwrap();
+    sdlh.flush().unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    sdlh.handle_ui_events_batch(move |_sdl, _windows, events| {
+        let keys: Vec<Keycode> = events.iter().filter_map(|event| match *event {
+            Event::KeyDown { keycode, .. } => keycode,
+            _ => None,
+        }).collect();
+        let quit = events.iter().any(|event| matches!(*event, Event::Quit { .. }));
+        tx.send((keys, quit)).unwrap();
+    }).unwrap();
+    assert_eq!(rx.recv().unwrap(), (vec![Keycode::A, Keycode::B], true));
+
+    let (tx, rx) = mpsc::channel();
+    sdlh.handle_ui_events_batch(move |_sdl, _windows, events| {
+        tx.send(events.iter().any(|event| matches!(*event, Event::KeyDown { .. } | Event::Quit { .. }))).unwrap();
+    }).unwrap();
+    assert!(!rx.recv().unwrap());
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::event::Event;
+use std::sync::mpsc::channel;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Measures how long events waited before being handled
+#[test]
+fn event_latency() {
+    let sdlh = sdl2_mt::init();
+    let (tx, rx) = channel();
+    sdlh.set_event_latency_budget(Duration::from_millis(10), Box::new(move |_event, latency| {
+        let _ = tx.send(latency);
+    })).unwrap();
+
+    sdlh.call(|sdl, _windows| {
+        sdl.event().unwrap().push_event(Event::Quit { timestamp: 0 }).unwrap();
+    }).unwrap();
+    sleep(Duration::from_millis(30));
+    sdlh.stats();
+
+    sdlh.handle_ui_events(Box::new(|_sdl, _windows, event| {
+        matches!(*event, Event::Quit { .. })
+    })).unwrap();
+
+    assert!(rx.try_recv().unwrap() >= Duration::from_millis(30));
+    let latency = sdlh.stats().event_latency;
+    assert!(latency.events_handled >= 1);
+    assert!(latency.max >= Duration::from_millis(30));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2Mt, Sdl2MtBuilder};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use std::sync::mpsc;
+
+/// Offers every event to the handle, taking the keys in `wanted`, and returns the keys it saw
+fn offered_keys(sdlh: &Sdl2Mt, wanted: &'static [Keycode]) -> Vec<Keycode> {
+    let (tx, rx) = mpsc::channel();
+    sdlh.handle_ui_events(Box::new(move |_sdl, _windows, event| {
+        match *event {
+            Event::KeyDown { keycode: Some(keycode), .. } => {
+                tx.send(keycode).unwrap();
+                wanted.contains(&keycode)
+            },
+            _ => true,
+        }
+    })).unwrap();
+    rx.try_iter().collect()
+}
+
+/// Events left unhandled are replayed ahead of newer events for the same window
+#[test]
+fn replay_keeps_order() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("order", 16, 16).unwrap();
+
+    sdlh.inject_key(window, Keycode::A, true).unwrap();
+    sdlh.inject_key(window, Keycode::B, true).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(offered_keys(&sdlh, &[Keycode::B]), vec![Keycode::A, Keycode::B]);
+
+    sdlh.inject_key(window, Keycode::C, true).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(offered_keys(&sdlh, &[Keycode::A, Keycode::C]), vec![Keycode::A, Keycode::C]);
+}
@@ -0,0 +1,42 @@
+#![cfg(all(feature = "sdl", feature = "async"))]
+
+extern crate futures_core;
+extern crate sdl2_mt;
+extern crate smol;
+
+use futures_core::Stream;
+use sdl2_mt::{EventStream, SendEvent, Sdl2MtBuilder};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use std::future;
+use std::pin::Pin;
+
+fn next(stream: &mut EventStream) -> Option<SendEvent> {
+    smol::block_on(future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)))
+}
+
+/// The stream yields new events in order, without anyone handling events, and ends
+/// when the UI thread exits
+#[test]
+fn event_stream() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("stream", 16, 16).unwrap();
+    let mut stream = sdlh.event_stream();
+
+    sdlh.inject_key(window, Keycode::A, true).unwrap();
+    sdlh.inject_key(window, Keycode::B, true).unwrap();
+    let mut keys = vec![];
+    while keys.len() < 2 {
+        if let Event::KeyDown { keycode: Some(keycode), .. } = *next(&mut stream).unwrap() {
+            keys.push(keycode);
+        }
+    }
+    assert_eq!(keys, vec![Keycode::A, Keycode::B]);
+
+    // forwarded events count as handled
+    assert!(sdlh.take_unhandled_events().unwrap().iter().all(|event| !matches!(*event, Event::KeyDown { .. })));
+
+    sdlh.exit().unwrap();
+    while next(&mut stream).is_some() {}
+}
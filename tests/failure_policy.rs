@@ -0,0 +1,36 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{FailurePolicy, Sdl2MtError};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// Only the clone with the policy reports its failures
+#[test]
+fn report_policy() {
+    let sdlh = sdl2_mt::init();
+    let reporting = sdlh.with_failure_policy(FailurePolicy::Report);
+    assert_eq!(sdlh.failure_policy(), FailurePolicy::ReturnError);
+    assert_eq!(reporting.clone().failure_policy(), FailurePolicy::Report);
+    let errors = sdlh.errors();
+
+    let (started_tx, started_rx) = channel();
+    let (tx, rx) = channel::<()>();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = rx.recv_timeout(Duration::from_secs(1));
+    }).unwrap();
+    started_rx.recv().unwrap();
+
+    let quiet = sdlh.call_timeout(|_sdl, _windows| (), Duration::from_millis(1));
+    assert_eq!(quiet, Err(Sdl2MtError::Timeout));
+    assert!(errors.try_recv().is_err());
+
+    let reported = reporting.call_timeout(|_sdl, _windows| (), Duration::from_millis(1));
+    assert_eq!(reported, Err(Sdl2MtError::Timeout));
+    assert_eq!(errors.try_recv(), Ok(Sdl2MtError::Timeout));
+
+    tx.send(()).unwrap();
+    sdlh.flush().unwrap();
+}
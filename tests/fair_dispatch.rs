@@ -0,0 +1,51 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{EventDispatch, Sdl2Mt, Sdl2MtBuilder};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use std::sync::mpsc;
+
+/// Takes every event the handle is offered, returning the keys that were pressed
+fn take_keys(sdlh: &Sdl2Mt) -> Vec<Keycode> {
+    let (tx, rx) = mpsc::channel();
+    sdlh.handle_ui_events(Box::new(move |_sdl, _windows, event| {
+        if let Event::KeyDown { keycode: Some(keycode), .. } = *event {
+            tx.send(keycode).unwrap();
+        }
+        true
+    })).unwrap();
+    rx.try_iter().collect()
+}
+
+/// Under round-robin dispatch, each polling handle gets its share of the new events
+#[test]
+fn round_robin() {
+    let first = Sdl2MtBuilder::new().headless().build();
+    let second = first.clone();
+    let left = first.create_simple_window("left", 16, 16).unwrap();
+    let right = first.create_simple_window("right", 16, 16).unwrap();
+    first.set_event_dispatch(EventDispatch::RoundRobin).unwrap();
+
+    // both handles have to be polling to be dealt events
+    take_keys(&first);
+    take_keys(&second);
+
+    // a window's waiting events all go to the same handle, so this takes two windows
+    first.inject_key(left, Keycode::A, true).unwrap();
+    first.inject_key(right, Keycode::B, true).unwrap();
+    first.inject_key(left, Keycode::C, true).unwrap();
+    first.inject_key(right, Keycode::D, true).unwrap();
+    first.flush().unwrap();
+
+    // however often the first handle polls, it only gets one window's keys
+    let mut firsts = take_keys(&first);
+    firsts.extend(take_keys(&first));
+    assert_eq!(firsts.len(), 2);
+
+    let seconds = take_keys(&second);
+    assert_eq!(seconds.len(), 2);
+    assert!(firsts.iter().all(|keycode| !seconds.contains(keycode)));
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "fake-backend")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{DrawCommand, FakeEvent, FakeUi, Sdl2MtError, WindowId};
+
+/// Windows, events and drawing all work without SDL
+#[test]
+fn fake_backend() {
+    let ui = FakeUi::new();
+    let window = ui.create_simple_window("fake", 64, 48).unwrap();
+    assert_eq!(window, WindowId::from_raw(1));
+    assert_eq!(ui.window_size(window), Ok((64, 48)));
+
+    ui.clear(window, (0, 0, 0, 255)).unwrap();
+    ui.draw(window, DrawCommand::FillRect { x: 1, y: 2, width: 3, height: 4, color: (255, 0, 0, 255) }).unwrap();
+    ui.present(window).unwrap();
+    let snapshot = ui.window(window).unwrap();
+    assert_eq!(snapshot.frames, 1);
+    assert_eq!(snapshot.presented.len(), 2);
+    assert!(snapshot.pending.is_empty());
+
+    ui.resize_window(window, 32, 24).unwrap();
+    ui.push_event(FakeEvent::KeyDown { window_id: window, key: "Space".to_string() });
+    ui.push_event(FakeEvent::Quit);
+
+    // only key presses are handled, so the other events stay queued
+    let mut keys = vec![];
+    ui.handle_ui_events(|event| match *event {
+        FakeEvent::KeyDown { ref key, .. } => {
+            keys.push(key.clone());
+            true
+        },
+        _ => false,
+    }).unwrap();
+    assert_eq!(keys, vec!["Space".to_string()]);
+    assert_eq!(ui.poll_event(), Some(FakeEvent::WindowResized { window_id: window, width: 32, height: 24 }));
+    assert_eq!(ui.poll_event(), Some(FakeEvent::Quit));
+    assert_eq!(ui.poll_event(), None);
+
+    ui.close_window(window).unwrap();
+    assert!(ui.window_ids().is_empty());
+    assert_eq!(ui.present(window), Err(Sdl2MtError::WindowNotFound(window)));
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError};
+use sdl2_mt::event::Event;
+
+use std::io;
+
+/// Errors from `try_handle_ui_events` handlers reach the caller and the error stream
+#[test]
+fn fallible_handlers() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let errors = sdlh.errors();
+    sdlh.inject_quit().unwrap();
+    sdlh.flush().unwrap();
+
+    let result = sdlh.try_handle_ui_events(|_sdl, _windows, event| match *event {
+        Event::Quit { .. } => Err(io::Error::other("disk full")),
+        _ => Ok(false),
+    });
+    match result {
+        Err(Sdl2MtError::Callback(ref message)) => assert!(message.contains("disk full")),
+        ref other => panic!("expected a Callback error, got {:?}", other),
+    }
+    assert_eq!(errors.try_recv().ok(), result.err());
+
+    // the failed event counted as handled
+    sdlh.try_handle_ui_events(|_sdl, _windows, event| match *event {
+        Event::Quit { .. } => Err("handled twice"),
+        _ => Ok(false),
+    }).unwrap();
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{FailurePolicy, Sdl2MtBuilder, Sdl2MtError};
+
+use std::time::Duration;
+
+/// Each injected fault shows up as the error an application would see for the real thing
+#[test]
+fn injected_faults() {
+    let sdlh = Sdl2MtBuilder::new().headless().queue_capacity(16).unwrap().build();
+
+    sdlh.stall_ui_thread(Duration::from_millis(200)).unwrap();
+    assert_eq!(sdlh.call_timeout(|_sdl, _windows| (), Duration::from_millis(10)), Err(Sdl2MtError::Timeout));
+
+    {
+        let saturation = sdlh.saturate_queue().unwrap();
+        assert!(saturation.filler_messages > 0);
+        assert_eq!(sdlh.try_run_on_ui_thread(Box::new(|_sdl, _windows| {})), Err(Sdl2MtError::QueueFull));
+
+        // a full queue is expected by the `try_` methods, so the handle's policy ignores it
+        let errors = sdlh.errors();
+        let reporting = sdlh.with_failure_policy(FailurePolicy::Report);
+        assert_eq!(reporting.try_run_on_ui_thread(Box::new(|_sdl, _windows| {})), Err(Sdl2MtError::QueueFull));
+        assert!(errors.try_recv().is_err());
+        let panicking = sdlh.with_failure_policy(FailurePolicy::Panic);
+        assert_eq!(panicking.try_run_on_ui_thread(Box::new(|_sdl, _windows| {})), Err(Sdl2MtError::QueueFull));
+    }
+    sdlh.flush().unwrap();
+
+    sdlh.kill_ui_thread().unwrap();
+    assert_eq!(sdlh.flush(), Err(Sdl2MtError::UiThreadExited));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtError, WindowId};
+
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+/// Frames slower than the budget show up in the histogram and as jank
+#[test]
+fn frame_times_and_jank() {
+    let sdlh = sdl2_mt::init();
+    let window = sdlh.create_simple_window("frame_times", 64, 64).unwrap();
+
+    let (tx, rx) = channel();
+    sdlh.set_frame_budget(window, Some(Duration::from_millis(10))).unwrap();
+    sdlh.on_jank(window, Box::new(move |info, budget| {
+        let _ = tx.send((info.frame, budget));
+    })).unwrap();
+
+    sdlh.present(window).unwrap();
+    thread::sleep(Duration::from_millis(40));
+    sdlh.present(window).unwrap();
+
+    let times = sdlh.frame_times(window).unwrap();
+    assert_eq!(times.frames, 1);
+    assert_eq!(times.janky_frames, 1);
+    assert!(times.dropped_frames >= 2);
+    assert_eq!(times.histogram.iter().sum::<u64>(), 1);
+    assert_eq!(times.budget, Duration::from_millis(10));
+    assert_eq!(rx.recv().unwrap(), (1, Duration::from_millis(10)));
+
+    // counting starts over with every call
+    assert_eq!(sdlh.frame_times(window).unwrap().frames, 0);
+    let unknown = WindowId::from_raw(window.raw() + 1000);
+    assert_eq!(sdlh.frame_times(unknown).err(), Some(Sdl2MtError::WindowNotFound(unknown)));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Frame, FramePool};
+use sdl2_mt::pixels::PixelFormatEnum;
+use std::sync::Arc;
+
+/// Streams a frame and gets its buffer back from the pool
+#[test]
+fn stream_frame_recycles_buffer() {
+    let sdlh = sdl2_mt::init();
+    let window = sdlh.create_simple_window("stream_frame", 64, 64).unwrap();
+
+    let pool = FramePool::new(64 * 64 * 4);
+    let mut pixels = pool.acquire();
+    for byte in Arc::get_mut(&mut pixels).unwrap().iter_mut() {
+        *byte = 0xff;
+    }
+    let first = pixels.clone();
+
+    sdlh.stream_frame(window, Frame {
+        pixels,
+        format: PixelFormatEnum::ARGB8888,
+        width: 64,
+        height: 64,
+        pitch: 64 * 4,
+    }, Some(&pool)).unwrap();
+    sdlh.flush().unwrap();
+
+    // still shared with `first`, so the pool must not hand it out
+    assert!(!Arc::ptr_eq(&pool.acquire(), &first));
+    drop(first);
+
+    let mut recycled = pool.acquire();
+    assert!(Arc::get_mut(&mut recycled).is_some());
+    assert!(recycled.iter().all(|&byte| byte == 0xff));
+}
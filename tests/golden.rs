@@ -0,0 +1,44 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::pixels::Color;
+use sdl2_mt::rect::Rect;
+
+use std::env;
+
+/// A window matches an image of its own contents, and stops matching once it changes
+#[test]
+fn window_matches_golden() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("golden", 16, 16).unwrap();
+
+    let draw = |color: Color| {
+        sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+            let canvas = windows.get_mut(&window).unwrap();
+            canvas.set_draw_color(Color::RGB(0, 0, 0));
+            canvas.clear();
+            canvas.set_draw_color(color);
+            canvas.fill_rect(Rect::new(4, 4, 8, 8)).unwrap();
+        }).unwrap();
+        sdlh.read_window_pixels(window).unwrap()
+    };
+
+    let pixels = draw(Color::RGB(200, 40, 40));
+    assert_eq!((pixels.width, pixels.height), (16, 16));
+    assert_eq!(pixels.pixel(0, 0), (0, 0, 0));
+    assert_eq!(pixels.pixel(8, 8), (200, 40, 40));
+
+    let golden = env::temp_dir().join(format!("sdl2_mt_golden_{}.bmp", std::process::id()));
+    pixels.save_bmp(&golden).unwrap();
+    let loaded = sdl2_mt::WindowPixels::load_bmp(&golden).unwrap();
+    assert_eq!(loaded, pixels);
+
+    let slightly_off = draw(Color::RGB(202, 40, 40));
+    assert_eq!(slightly_off.count_differences(&loaded, 2), Ok(0));
+    assert_eq!(slightly_off.count_differences(&loaded, 1), Ok(64));
+    sdlh.assert_window_matches(window, &golden, 2);
+
+    let _ = std::fs::remove_file(&golden);
+}
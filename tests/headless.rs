@@ -0,0 +1,22 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+
+/// Windows work with the dummy video driver, without a display server
+#[test]
+fn headless_window() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("headless", 32, 32).unwrap();
+
+    let driver = sdlh.call(|sdl, _windows| sdl.video().unwrap().current_video_driver()).unwrap();
+    assert_eq!(driver, "dummy");
+
+    sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+        let canvas = windows.get_mut(&window).unwrap();
+        canvas.clear();
+    }).unwrap();
+    sdlh.present(window).unwrap();
+    assert_eq!(sdlh.window_size(window).unwrap(), (32, 32));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::HighWaterMark;
+use std::sync::mpsc::channel;
+
+/// A backlog that builds up while the UI thread is busy crosses the queue threshold once
+#[test]
+fn queue_depth_high_water_mark() {
+    let sdlh = sdl2_mt::init();
+    let (marks_tx, marks_rx) = channel();
+    sdlh.set_high_water_marks(3, 1000, Box::new(move |mark| {
+        let _ = marks_tx.send(mark);
+    })).unwrap();
+    sdlh.flush().unwrap();
+
+    let (started_tx, started_rx) = channel();
+    let (tx, rx) = channel::<()>();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = rx.recv();
+    }).unwrap();
+    started_rx.recv().unwrap();
+
+    for _ in 0..5 {
+        sdlh.run_on_ui_thread_once(|_sdl, _windows| {}).unwrap();
+    }
+    tx.send(()).unwrap();
+    sdlh.flush().unwrap();
+    sdlh.flush().unwrap();
+
+    match marks_rx.try_recv() {
+        Ok(HighWaterMark::QueueDepth(depth)) => assert!(depth > 3),
+        other => panic!("expected the queue threshold to be crossed, got {:?}", other),
+    }
+    assert!(marks_rx.try_recv().is_err());
+    assert!(sdlh.stats().max_queue_depth > 3);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Waits until `count` goes past `past`, failing the test after five seconds
+fn wait_past(count: &AtomicUsize, past: usize) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while count.load(Ordering::SeqCst) <= past {
+        assert!(Instant::now() < deadline, "idle callback didn't run");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Idle callbacks keep running until they are removed or return false
+#[test]
+fn idle_callbacks() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+
+    let removed = Arc::new(AtomicUsize::new(0));
+    let counter = removed.clone();
+    let id = sdlh.add_idle_callback(Box::new(move |_sdl, _windows| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        true
+    })).unwrap();
+    wait_past(&removed, 1);
+
+    sdlh.remove_idle_callback(id).unwrap();
+    sdlh.flush().unwrap();
+    let runs = removed.load(Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(removed.load(Ordering::SeqCst), runs);
+
+    // a callback that returns false unregisters itself after its first run
+    let once = Arc::new(AtomicUsize::new(0));
+    let counter = once.clone();
+    sdlh.add_idle_callback(Box::new(move |_sdl, _windows| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        false
+    })).unwrap();
+    wait_past(&once, 0);
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(once.load(Ordering::SeqCst), 1);
+
+    // the pause between idle passes doesn't hold up other work
+    let started = Instant::now();
+    let keep = sdlh.add_idle_callback(Box::new(|_sdl, _windows| true)).unwrap();
+    for _ in 0..100 {
+        sdlh.flush().unwrap();
+    }
+    assert!(started.elapsed() < Duration::from_secs(1));
+    sdlh.remove_idle_callback(keep).unwrap();
+}
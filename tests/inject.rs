@@ -0,0 +1,40 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, WindowId};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+use sdl2_mt::mouse::MouseButton;
+
+use std::sync::{Arc, Mutex};
+
+/// Injected events come out of the real event pump, in order
+#[test]
+fn injected_events_reach_handlers() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("inject", 64, 64).unwrap();
+
+    sdlh.inject_key(window, Keycode::Space, true).unwrap();
+    sdlh.inject_mouse_move(window, 10, 20).unwrap();
+    sdlh.inject_mouse_button(window, MouseButton::Left, true, 10, 20).unwrap();
+    sdlh.inject_mouse_move(window, 15, 20).unwrap();
+    sdlh.inject_quit().unwrap();
+
+    let seen = Arc::new(Mutex::new(vec![]));
+    let handler_seen = seen.clone();
+    sdlh.handle_ui_events(Box::new(move |_sdl, _windows, event| {
+        let description = match *event {
+            Event::KeyDown { keycode: Some(Keycode::Space), window_id, .. } if WindowId::from_raw(window_id) == window => "space",
+            Event::MouseMotion { x: 10, y: 20, .. } => "move",
+            Event::MouseButtonDown { mouse_btn: MouseButton::Left, .. } => "click",
+            Event::MouseMotion { xrel: 5, yrel: 0, mousestate, .. } if mousestate.left() => "drag",
+            Event::Quit { .. } => "quit",
+            _ => return false,
+        };
+        handler_seen.lock().unwrap().push(description);
+        true
+    })).unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), vec!["space", "move", "click", "drag", "quit"]);
+}
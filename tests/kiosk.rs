@@ -0,0 +1,61 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{KioskLayout, Sdl2Mt, Sdl2MtBuilder, Sdl2MtError, WindowId};
+use sdl2_mt::rect::Rect;
+use sdl2_mt::sys::video::SDL_WindowFlags::SDL_WINDOW_BORDERLESS;
+use sdl2_mt::video::{FullscreenType, WindowPos};
+
+use std::thread;
+use std::time::Duration;
+
+/// Whether the window is borderless, and how it is fullscreen
+fn window_mode(sdlh: &Sdl2Mt, window: WindowId) -> (bool, FullscreenType) {
+    sdlh.call(move |_sdl, windows| {
+        let window = windows[&window].window();
+        (window.window_flags() & SDL_WINDOW_BORDERLESS as u32 != 0, window.fullscreen_state())
+    }).unwrap()
+}
+
+/// A group of borderless windows covers the displays, and replacing or moving the group
+/// keeps the IDs it reports up to date
+#[test]
+fn kiosk_windows() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let displays: Vec<Rect> = sdlh.call(|sdl, _windows| {
+        let video = sdl.video().unwrap();
+        (0..video.num_video_displays().unwrap()).map(|index| video.display_bounds(index).unwrap()).collect()
+    }).unwrap();
+    assert!(!displays.is_empty());
+
+    // one fullscreen window per display
+    let per_display = sdlh.create_kiosk_windows("kiosk", KioskLayout::PerDisplay).unwrap();
+    assert_eq!(per_display.len(), displays.len());
+    assert_eq!(sdlh.kiosk_windows(), Ok(per_display.clone()));
+    for (&window, display) in per_display.iter().zip(&displays) {
+        assert_eq!(window_mode(&sdlh, window), (true, FullscreenType::Desktop));
+        assert_eq!(sdlh.window_size(window), Ok(display.size()));
+    }
+
+    // a single window over all of them, which replaces the previous group
+    let spanning = sdlh.create_kiosk_windows("kiosk", KioskLayout::Spanning).unwrap();
+    assert_eq!(spanning.len(), 1);
+    let window = spanning[0];
+    assert!(per_display.iter().all(|&old| sdlh.window_size(old) == Err(Sdl2MtError::WindowNotFound(old))));
+    assert_eq!(window_mode(&sdlh, window), (true, FullscreenType::Off));
+    let covered = displays.iter().fold(displays[0], |acc, display| acc.union(*display));
+    assert_eq!(sdlh.window_size(window), Ok(covered.size()));
+
+    // moving a kiosk window makes the UI thread check the displays, which haven't changed
+    sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+        windows.get_mut(&window).unwrap().window_mut().set_position(WindowPos::Positioned(10), WindowPos::Positioned(10));
+    }).unwrap();
+    thread::sleep(Duration::from_millis(50));
+    sdlh.flush().unwrap();
+    assert_eq!(sdlh.kiosk_windows(), Ok(spanning));
+
+    sdlh.close_kiosk_windows().unwrap();
+    assert_eq!(sdlh.kiosk_windows(), Ok(vec![]));
+    assert_eq!(sdlh.window_size(window), Err(Sdl2MtError::WindowNotFound(window)));
+}
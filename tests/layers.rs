@@ -0,0 +1,39 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Layer, Sdl2MtBuilder};
+use sdl2_mt::pixels::Color;
+use sdl2_mt::rect::Rect;
+
+use std::thread;
+
+/// Layers drawn from different threads are composited in order on present
+#[test]
+fn layers_are_composited() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("layers", 8, 8).unwrap();
+
+    let overlay = sdlh.clone();
+    thread::spawn(move || {
+        overlay.draw_layer(window, Layer::Overlay, |canvas| {
+            canvas.set_draw_color(Color::RGB(0, 255, 0));
+            canvas.fill_rect(Rect::new(0, 0, 2, 2)).unwrap();
+        }).unwrap();
+    }).join().unwrap();
+    sdlh.draw_layer(window, Layer::Background, |canvas| {
+        canvas.set_draw_color(Color::RGB(255, 0, 0));
+        canvas.clear();
+    }).unwrap();
+    sdlh.present(window).unwrap();
+
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(1, 1), (0, 255, 0));
+    assert_eq!(pixels.pixel(5, 5), (255, 0, 0));
+
+    sdlh.clear_layer(window, Layer::Overlay).unwrap();
+    sdlh.present(window).unwrap();
+    assert_eq!(sdlh.read_window_pixels(window).unwrap().pixel(1, 1), (255, 0, 0));
+    sdlh.remove_layers(window).unwrap();
+    sdlh.flush().unwrap();
+}
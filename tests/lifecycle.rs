@@ -0,0 +1,27 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{LifecycleEvent, Sdl2MtBuilder};
+
+/// Lifecycle events reach their channel, and pause rendering while in the background
+#[test]
+fn lifecycle_pauses_rendering() {
+    let sdlh = Sdl2MtBuilder::new().headless().synchronous().build();
+    let window = sdlh.create_simple_window("lifecycle", 32, 32).unwrap();
+    let lifecycle = sdlh.lifecycle_events().unwrap();
+
+    sdlh.inject_lifecycle(LifecycleEvent::WillEnterBackground).unwrap();
+    sdlh.turn().unwrap();
+    assert_eq!(lifecycle.try_recv(), Ok(LifecycleEvent::WillEnterBackground));
+    assert_eq!(sdlh.is_paused(), Ok(true));
+
+    sdlh.present(window).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(sdlh.dump_state().unwrap().windows[0].frames_presented, 0);
+
+    sdlh.inject_lifecycle(LifecycleEvent::DidEnterForeground).unwrap();
+    sdlh.turn().unwrap();
+    assert_eq!(lifecycle.try_recv(), Ok(LifecycleEvent::DidEnterForeground));
+    assert_eq!(sdlh.is_paused(), Ok(false));
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2Mt, Sdl2MtBuilder, WindowId};
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn logical_size(sdlh: &Sdl2Mt, window: WindowId) -> (u32, u32) {
+    sdlh.call(move |_sdl, windows| windows[&window].logical_size()).unwrap()
+}
+
+/// Waits for the UI thread to pump the events a change to the window caused
+fn wait_for_logical_size(sdlh: &Sdl2Mt, window: WindowId, expected: (u32, u32)) {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while logical_size(sdlh, window) != expected {
+        assert!(Instant::now() < deadline, "the logical size stayed at {:?}", logical_size(sdlh, window));
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Automatic DPI scaling keeps the logical size at the window size as the window is resized,
+/// without anyone handling events, and an explicit logical size takes precedence over it
+#[test]
+fn auto_dpi_scaling() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("logical size", 32, 24).unwrap();
+
+    // the dummy video driver has no HiDPI displays
+    assert_eq!(sdlh.window_size(window), Ok((32, 24)));
+    assert_eq!(sdlh.drawable_size(window), Ok((32, 24)));
+    assert_eq!(sdlh.scale_factor(window), Ok(1.0));
+    assert_eq!(logical_size(&sdlh, window), (0, 0));
+
+    sdlh.set_auto_dpi_scaling(window, true).unwrap();
+    assert_eq!(logical_size(&sdlh, window), (32, 24));
+
+    sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+        windows.get_mut(&window).unwrap().window_mut().set_size(64, 48).unwrap();
+    }).unwrap();
+    wait_for_logical_size(&sdlh, window, (64, 48));
+    assert_eq!(sdlh.window_size(window), Ok((64, 48)));
+    assert_eq!(sdlh.scale_factor(window), Ok(1.0));
+
+    sdlh.set_logical_size(window, Some((16, 12))).unwrap();
+    assert_eq!(logical_size(&sdlh, window), (16, 12));
+    sdlh.set_logical_size(window, None).unwrap();
+    assert_eq!(logical_size(&sdlh, window), (64, 48));
+
+    sdlh.set_auto_dpi_scaling(window, false).unwrap();
+    assert_eq!(logical_size(&sdlh, window), (0, 0));
+}
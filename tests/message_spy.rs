@@ -0,0 +1,30 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, WorkKind};
+
+/// The spy records which handle sent what, after coalescing
+#[test]
+fn spy_records_messages() {
+    let sdlh = Sdl2MtBuilder::new().headless().synchronous().build();
+    let renderer = sdlh.clone();
+    assert!(renderer.handle_id() != sdlh.handle_id());
+
+    sdlh.spy_messages(true);
+    sdlh.create_simple_window("spy", 32, 32).unwrap();
+    for _ in 0..3 {
+        renderer.run_coalesced("redraw", Box::new(|_sdl, _windows| {})).unwrap();
+    }
+    sdlh.turn().unwrap();
+
+    let spied: Vec<_> = sdlh.spied_messages().iter().map(|message| (message.kind, message.origin)).collect();
+    assert_eq!(spied, vec![
+        (WorkKind::CreateWindow, sdlh.handle_id()),
+        (WorkKind::Lambda, renderer.handle_id()),
+    ]);
+
+    sdlh.spy_messages(false);
+    sdlh.flush().unwrap();
+    assert!(sdlh.spied_messages().is_empty());
+}
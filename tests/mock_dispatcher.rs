@@ -0,0 +1,58 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{MockCall, MockDispatcher, Priority, Sdl2MtError, UiDispatcher, WindowId};
+
+use std::panic::{self, AssertUnwindSafe};
+
+/// Application code that only knows about the dispatcher trait
+fn open_and_draw<D: UiDispatcher>(dispatcher: &D) -> Result<u32, Sdl2MtError> {
+    let window = dispatcher.create_simple_window("app", 640, 480)?;
+    dispatcher.run_on_ui_thread(Box::new(|_sdl, _windows| {}))?;
+    dispatcher.present(window)?;
+    let presented: u64 = dispatcher.call(|_sdl, _windows| 1)?;
+    Ok(window.raw() + presented as u32)
+}
+
+/// The mock records calls and hands out queued results, without SDL
+#[test]
+fn mock_records_calls() {
+    let mock = MockDispatcher::new();
+    mock.push_window_id(Some(WindowId::from_raw(7)));
+    mock.push_call_result(3u64);
+
+    assert_eq!(open_and_draw(&mock), Ok(10));
+    assert_eq!(mock.calls(), vec![
+        MockCall::CreateSimpleWindow("app".to_owned(), 640, 480, Some(WindowId::from_raw(7))),
+        MockCall::RunOnUiThread(Priority::Normal),
+        MockCall::Present(WindowId::from_raw(7)),
+        MockCall::Call,
+    ]);
+
+    mock.clear_calls();
+    mock.fail_with(Some(Sdl2MtError::UiThreadExited));
+    assert_eq!(open_and_draw(&mock), Err(Sdl2MtError::UiThreadExited));
+    assert!(mock.calls().is_empty());
+
+    // failing to make the window is an error, like it is with SDL
+    mock.fail_with(None);
+    mock.push_window_id(None);
+    match open_and_draw(&mock) {
+        Err(Sdl2MtError::SdlError(_)) => {},
+        other => panic!("expected an SdlError, got {:?}", other),
+    }
+    assert_eq!(mock.calls(), vec![MockCall::CreateSimpleWindow("app".to_owned(), 640, 480, None)]);
+}
+
+/// A call without a queued result panics, but leaves the mock usable
+#[test]
+fn mock_survives_missing_result() {
+    let mock = MockDispatcher::new();
+    let missing = panic::catch_unwind(AssertUnwindSafe(|| mock.call(|_sdl, _windows| 1u8)));
+    assert!(missing.is_err());
+
+    mock.push_call_result(2u8);
+    assert_eq!(mock.call(|_sdl, _windows| 1u8), Ok(2));
+    assert_eq!(mock.calls(), vec![MockCall::Call, MockCall::Call]);
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError, WindowId};
+
+/// The dummy video driver has no native handles, and unknown windows have none either
+#[test]
+fn native_handle_headless() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("native handle", 32, 32).unwrap();
+
+    match sdlh.native_handle(window) {
+        Err(Sdl2MtError::SdlError(message)) => assert!(message.contains("SDL_GetWindowWMInfo")),
+        other => panic!("expected an SDL error, got {:?}", other),
+    }
+    let unknown = WindowId::from_raw(window.raw() + 1);
+    assert_eq!(sdlh.native_handle(unknown), Err(Sdl2MtError::WindowNotFound(unknown)));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{PresentInfo, Sdl2MtBuilder};
+use sdl2_mt::pixels::Color;
+
+use std::sync::{Arc, Mutex};
+
+/// The hook runs once for every frame presented through the handle, and stops once cleared
+#[test]
+fn hook_runs_on_each_present() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("on present", 8, 8).unwrap();
+
+    let seen: Arc<Mutex<Vec<PresentInfo>>> = Arc::new(Mutex::new(vec![]));
+    let hook = seen.clone();
+    sdlh.on_present(window, Box::new(move |info| hook.lock().unwrap().push(*info))).unwrap();
+
+    sdlh.present(window).unwrap();
+    sdlh.present(window).unwrap();
+    sdlh.present_color(window, Color::RGB(1, 2, 3)).unwrap();
+    // presenting the canvas directly bypasses the hook
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, windows| windows.get_mut(&window).unwrap().present())).unwrap();
+    sdlh.flush().unwrap();
+
+    {
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.iter().map(|info| info.frame).collect::<Vec<u64>>(), vec![0, 1, 2]);
+        assert!(seen.iter().all(|info| info.window_id == window));
+        assert!(seen[0].frame_interval.is_none());
+        assert!(seen[1..].iter().all(|info| info.frame_interval.is_some()));
+        assert!(seen.windows(2).all(|pair| pair[0].presented_at <= pair[1].presented_at));
+    }
+
+    sdlh.clear_present_callbacks(window).unwrap();
+    sdlh.present(window).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(seen.lock().unwrap().len(), 3);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{AxesConfig, Sdl2MtBuilder, draw_line_series};
+use sdl2_mt::pixels::Color;
+
+/// A series is drawn inside the axes, in the line color
+#[test]
+fn plot_line_series() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("plot", 200, 120).unwrap();
+
+    let points: Vec<(f64, f64)> = (0..=10).map(|x| (x as f64, 5.0)).collect();
+    let axes = AxesConfig {
+        y_range: Some((0.0, 10.0)),
+        line_color: Color::RGB(255, 0, 0),
+        ..AxesConfig::default()
+    };
+    sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+        draw_line_series(windows.get_mut(&window).unwrap(), &points, &axes).unwrap();
+    }).unwrap();
+
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(199, 0), (255, 255, 255));
+    let red_rows: Vec<u32> = (0..120).filter(|&y| pixels.pixel(150, y) == (255, 0, 0)).collect();
+    assert_eq!(red_rows.len(), 1);
+    // the middle of the y axis, which spans from 8 to 98
+    assert!((red_rows[0] as i32 - 53).abs() <= 1, "{:?}", red_rows);
+
+    sdlh.plot_line_series(window, &[(0.0, 1.0), (1.0, f64::NAN), (2.0, 3.0)], &AxesConfig::default()).unwrap();
+    sdlh.flush().unwrap();
+}
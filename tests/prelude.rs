@@ -0,0 +1,21 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::prelude::*;
+
+/// The prelude alone is enough for a small program
+#[test]
+fn prelude() {
+    let sdlh: Sdl2Mt = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window_with(SimpleWindowOptions {
+        size: (8, 8),
+        ..SimpleWindowOptions::default()
+    }).unwrap();
+    sdlh.fill_rects(window, &[Rect::new(0, 0, 4, 4)], Color::RGB(255, 0, 0)).unwrap();
+    sdlh.present(window).unwrap();
+    let result: Result<(), Sdl2MtError> = sdlh.handle_ui_events(Box::new(|_sdl, _windows, event: &Event| {
+        matches!(*event, Event::KeyDown { keycode: Some(Keycode::Escape), .. })
+    }));
+    result.unwrap();
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::pixels::Color;
+
+/// Clearing fills the canvas, and present_color fills and presents it in one message
+#[test]
+fn clear_and_present_color() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("present color", 8, 8).unwrap();
+
+    sdlh.clear(window, Color::RGB(10, 20, 30)).unwrap();
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(3, 3), (10, 20, 30));
+
+    let frame = sdlh.frame_number();
+    sdlh.present_color(window, Color::RGB(40, 50, 60)).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(sdlh.frame_number(), frame + 1);
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Priority, Sdl2MtBuilder};
+
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::channel;
+
+/// High priority lambdas overtake normal ones that are already waiting, and each lane
+/// keeps its own order
+#[test]
+fn high_priority_overtakes_normal() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let ran = Arc::new(Mutex::new(vec![]));
+
+    // keep the UI thread busy until everything has been queued
+    let (started_tx, started_rx) = channel();
+    let (release_tx, release_rx) = channel::<()>();
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+    })).unwrap();
+    started_rx.recv().unwrap();
+
+    for &(priority, name) in &[(Priority::Normal, "normal 1"), (Priority::Normal, "normal 2"), (Priority::High, "high 1"), (Priority::High, "high 2")] {
+        let ran = ran.clone();
+        sdlh.run_on_ui_thread_with_priority(priority, Box::new(move |_sdl, _windows| ran.lock().unwrap().push(name))).unwrap();
+    }
+    release_tx.send(()).unwrap();
+
+    sdlh.flush().unwrap();
+    assert_eq!(*ran.lock().unwrap(), vec!["high 1", "high 2", "normal 1", "normal 2"]);
+}
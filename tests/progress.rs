@@ -0,0 +1,33 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{ProgressReporter, Script, Sdl2MtBuilder, TestClock};
+use sdl2_mt::keyboard::Keycode;
+
+use std::thread;
+use std::time::Duration;
+
+/// The progress bar follows the reporter, and Escape cancels the task
+#[test]
+fn progress_bar() {
+    let clock = TestClock::new();
+    let sdlh = Sdl2MtBuilder::new().headless().test_clock(clock).build();
+    let window = sdlh.create_simple_window("progress", 100, 100).unwrap();
+    let frame = Duration::from_millis(20);
+
+    let reporter = ProgressReporter::new();
+    sdlh.show_progress(window, &reporter).unwrap();
+    let task = reporter.clone();
+    thread::spawn(move || task.set(0.5)).join().unwrap();
+    sdlh.advance_clock(frame).unwrap();
+
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(25, 50), (80, 160, 255));
+    assert_eq!(pixels.pixel(75, 50), (64, 64, 64));
+
+    Script::new().key_press(Keycode::Escape).run(&sdlh).unwrap();
+    assert!(reporter.is_cancelled());
+    sdlh.advance_clock(frame).unwrap();
+    assert_eq!(sdlh.scene_count(window), Ok(0));
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, WindowId};
+use sdl2_mt::sys;
+
+/// The raw pointers belong to the window, and can be used on the UI thread
+#[test]
+fn raw_sdl_pointers() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("raw pointers", 32, 32).unwrap();
+
+    let pointers = unsafe { sdlh.raw_sdl_pointers(window) }.unwrap().unwrap();
+    let raw_id = sdlh.call(move |_sdl, _windows| unsafe { sys::video::SDL_GetWindowID(pointers.window_ptr()) }).unwrap();
+    assert_eq!(WindowId::from_raw(raw_id), window);
+    assert!(!pointers.renderer_ptr().is_null());
+
+    assert_eq!(unsafe { sdlh.raw_sdl_pointers(WindowId::from_raw(window.raw() + 1)) }, Ok(None));
+}
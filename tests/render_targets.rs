@@ -0,0 +1,39 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, WindowId};
+use sdl2_mt::pixels::Color;
+use sdl2_mt::rect::Rect;
+
+/// Drawing goes to a target instead of the window, until the target is copied onto it
+#[test]
+fn render_targets() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("targets", 8, 8).unwrap();
+    let target = sdlh.create_render_target(window, 4, 4).unwrap();
+    assert!(sdlh.create_render_target(WindowId::from_raw(window.raw() + 1), 4, 4).is_err());
+
+    sdlh.clear(window, Color::RGB(0, 0, 0)).unwrap();
+    sdlh.with_render_target(target, |canvas| {
+        canvas.set_draw_color(Color::RGB(255, 0, 0));
+        canvas.clear();
+    }).unwrap();
+    assert_eq!(sdlh.read_window_pixels(window).unwrap().pixel(2, 2), (0, 0, 0));
+
+    sdlh.copy_target_to_window(target, Some(Rect::new(0, 0, 4, 4))).unwrap();
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(2, 2), (255, 0, 0));
+    assert_eq!(pixels.pixel(6, 6), (0, 0, 0));
+
+    // drawing methods follow set_render_target
+    sdlh.set_render_target(window, Some(target)).unwrap();
+    sdlh.clear(window, Color::RGB(0, 255, 0)).unwrap();
+    sdlh.copy_target_to_window(target, None).unwrap();
+    sdlh.set_render_target(window, None).unwrap();
+    assert_eq!(sdlh.read_window_pixels(window).unwrap().pixel(6, 6), (0, 255, 0));
+
+    sdlh.destroy_render_target(target).unwrap();
+    sdlh.present(window).unwrap();
+    sdlh.flush().unwrap();
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtError;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// Round-trips keep working when the reply channel is reused, including after a timeout
+#[test]
+fn reuse_reply_channel() {
+    let sdlh = sdl2_mt::init();
+
+    for _ in 0..100 {
+        sdlh.handle_ui_events(Box::new(|_sdl, _windows, _event| false)).unwrap();
+    }
+
+    // keep the UI thread busy, so that the next round-trip times out
+    let (started_tx, started_rx) = channel();
+    let (tx, rx) = channel::<()>();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = rx.recv_timeout(Duration::from_secs(1));
+    }).unwrap();
+    started_rx.recv().unwrap();
+    match sdlh.handle_ui_events_timeout(Box::new(|_sdl, _windows, _event| false), Duration::from_millis(10)) {
+        Err(Sdl2MtError::Timeout) => {},
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+    tx.send(()).unwrap();
+
+    for _ in 0..10 {
+        sdlh.handle_ui_events_timeout(Box::new(|_sdl, _windows, _event| false), Duration::from_secs(5)).unwrap();
+    }
+
+    let clone = sdlh.clone();
+    clone.handle_ui_events(Box::new(|_sdl, _windows, _event| false)).unwrap();
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Priority, Sdl2MtBuilder};
+
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::channel;
+
+/// A batch runs in order, and even high priority work waits until the whole batch is done
+#[test]
+fn batch_runs_in_one_turn() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let ran = Arc::new(Mutex::new(vec![]));
+
+    let (started_tx, started_rx) = channel();
+    let (release_tx, release_rx) = channel::<()>();
+    let first = ran.clone();
+    let second = ran.clone();
+    let third = ran.clone();
+    sdlh.run_batch(vec![
+        Box::new(move |_sdl, _windows| {
+            first.lock().unwrap().push("first");
+            started_tx.send(()).unwrap();
+            let _ = release_rx.recv();
+        }),
+        Box::new(move |_sdl, _windows| second.lock().unwrap().push("second")),
+        Box::new(move |_sdl, _windows| third.lock().unwrap().push("third")),
+    ]).unwrap();
+
+    started_rx.recv().unwrap();
+    let high = ran.clone();
+    sdlh.run_on_ui_thread_with_priority(Priority::High, Box::new(move |_sdl, _windows| high.lock().unwrap().push("high"))).unwrap();
+    release_tx.send(()).unwrap();
+
+    sdlh.flush().unwrap();
+    assert_eq!(*ran.lock().unwrap(), vec!["first", "second", "third", "high"]);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, TaskState};
+
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::channel;
+
+/// A lambda cancelled while it waits in the queue never runs, and one that has started,
+/// or already ran, can't be cancelled anymore
+#[test]
+fn cancel_before_and_after_running() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let ran = Arc::new(Mutex::new(vec![]));
+
+    // keep the UI thread busy so that the cancelable lambdas wait in the queue
+    let (started_tx, started_rx) = channel();
+    let (release_tx, release_rx) = channel::<()>();
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+        started_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+    })).unwrap();
+    started_rx.recv().unwrap();
+
+    let cancelled_ran = ran.clone();
+    let cancelled = sdlh.run_cancelable(Box::new(move |_sdl, _windows| cancelled_ran.lock().unwrap().push("cancelled"))).unwrap();
+    let kept_ran = ran.clone();
+    let kept = sdlh.run_cancelable(Box::new(move |_sdl, _windows| kept_ran.lock().unwrap().push("kept"))).unwrap();
+    assert_eq!(cancelled.state(), TaskState::Pending);
+    assert!(cancelled.cancel());
+    assert_eq!(cancelled.state(), TaskState::Cancelled);
+    assert!(!cancelled.cancel());
+
+    release_tx.send(()).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(*ran.lock().unwrap(), vec!["kept"]);
+    assert!(kept.has_run());
+    assert!(!cancelled.has_run());
+    assert!(!kept.cancel());
+    assert_eq!(kept.state(), TaskState::Finished);
+
+    // a lambda that is already running finishes
+    let (running_tx, running_rx) = channel();
+    let (finish_tx, finish_rx) = channel::<()>();
+    let running = sdlh.run_cancelable(Box::new(move |_sdl, _windows| {
+        running_tx.send(()).unwrap();
+        let _ = finish_rx.recv();
+    })).unwrap();
+    running_rx.recv().unwrap();
+    assert_eq!(running.state(), TaskState::Running);
+    assert!(!running.cancel());
+    finish_tx.send(()).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(running.state(), TaskState::Finished);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+
+/// Runs lambdas that are small enough to be stored inline and ones that are not
+#[test]
+fn run_on_ui_thread_once() {
+    let sdlh = sdl2_mt::init();
+    let (tx, rx) = channel();
+
+    let small_tx = tx.clone();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| {
+        small_tx.send(1).unwrap();
+    }).unwrap();
+
+    let large = [7u64; 16];
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| {
+        tx.send(large.iter().sum::<u64>()).unwrap();
+    }).unwrap();
+
+    assert_eq!(rx.recv().unwrap(), 1);
+    assert_eq!(rx.recv().unwrap(), 112);
+}
+
+/// Captured values are dropped exactly once, whether the lambda ran or not
+#[test]
+fn captures_dropped_once() {
+    let sdlh = sdl2_mt::init();
+    let captured = Arc::new(());
+
+    let moved = captured.clone();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| drop(moved)).unwrap();
+    let kept = captured.clone();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| { let _ = &kept; }).unwrap();
+
+    sdlh.flush().unwrap();
+    assert_eq!(Arc::strong_count(&captured), 1);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{LoopControl, Sdl2MtBuilder};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use std::sync::{Arc, Mutex};
+
+/// The loop stops when the handler says so, and when a quit event arrives
+#[test]
+fn run_until_quit() {
+    let sdlh = Sdl2MtBuilder::new().headless().synchronous().build();
+    let window = sdlh.create_simple_window("run until quit", 32, 32).unwrap();
+    let seen = Arc::new(Mutex::new(vec![]));
+
+    sdlh.inject_key(window, Keycode::A, true).unwrap();
+    sdlh.inject_key(window, Keycode::Escape, true).unwrap();
+    let keys = seen.clone();
+    sdlh.run_until_quit(move |_sdl, _windows, event| {
+        match *event {
+            Event::KeyDown { keycode: Some(Keycode::Escape), .. } => LoopControl::Stop,
+            Event::KeyDown { keycode: Some(keycode), .. } => {
+                keys.lock().unwrap().push(keycode);
+                LoopControl::Handled
+            },
+            _ => LoopControl::Unhandled,
+        }
+    }).unwrap();
+    assert_eq!(*seen.lock().unwrap(), vec![Keycode::A]);
+
+    sdlh.inject_quit().unwrap();
+    let quits = seen.clone();
+    sdlh.run_until_quit(move |_sdl, _windows, event| {
+        if let Event::Quit { .. } = *event {
+            quits.lock().unwrap().push(Keycode::Q);
+        }
+        LoopControl::Handled
+    }).unwrap();
+    assert_eq!(*seen.lock().unwrap(), vec![Keycode::A, Keycode::Q]);
+}
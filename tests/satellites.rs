@@ -0,0 +1,13 @@
+#![cfg(all(feature = "sdl", feature = "image", feature = "ttf"))]
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+
+/// The satellite libraries are ready to use once the UI thread is up
+#[test]
+fn satellites_initialized() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    assert_eq!(sdlh.image_initialized(), Ok(true));
+    let version = sdlh.with_ttf(|_ttf, _windows| sdl2_mt::ttf::get_linked_version().major).unwrap();
+    assert_eq!(version, 2);
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Scene, SceneAction, Script, Sdl2MtBuilder, TestClock};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+use sdl2_mt::render::WindowCanvas;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+struct Recorder {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+    overlay: bool,
+}
+
+impl Scene for Recorder {
+    fn event(&mut self, event: &Event) -> SceneAction {
+        match *event {
+            Event::KeyDown { keycode: Some(Keycode::Escape), .. } => SceneAction::Pop,
+            _ => SceneAction::None,
+        }
+    }
+
+    fn update(&mut self, elapsed: Duration) -> SceneAction {
+        self.log.lock().unwrap().push(format!("{} update {}ms", self.name, elapsed.as_secs() * 1000 + elapsed.subsec_nanos() as u64 / 1_000_000));
+        SceneAction::None
+    }
+
+    fn render(&mut self, _canvas: &mut WindowCanvas) {
+        self.log.lock().unwrap().push(format!("{} render", self.name));
+    }
+
+    fn is_overlay(&self) -> bool {
+        self.overlay
+    }
+}
+
+/// Only the top scene is updated, overlays are drawn over the scene below, and scenes pop themselves
+#[test]
+fn scene_stack() {
+    let clock = TestClock::new();
+    let sdlh = Sdl2MtBuilder::new().headless().test_clock(clock.clone()).build();
+    let window = sdlh.create_simple_window("scenes", 32, 32).unwrap();
+    let log = Arc::new(Mutex::new(vec![]));
+
+    sdlh.set_scene_frame_interval(window, Duration::from_millis(10)).unwrap();
+    sdlh.push_scene(window, Box::new(Recorder { name: "game", log: log.clone(), overlay: false })).unwrap();
+    sdlh.advance_clock(Duration::from_millis(10)).unwrap();
+    assert_eq!(*log.lock().unwrap(), vec!["game update 10ms", "game render"]);
+
+    log.lock().unwrap().clear();
+    sdlh.push_scene(window, Box::new(Recorder { name: "pause", log: log.clone(), overlay: true })).unwrap();
+    sdlh.advance_clock(Duration::from_millis(10)).unwrap();
+    assert_eq!(*log.lock().unwrap(), vec!["pause update 10ms", "game render", "pause render"]);
+    assert_eq!(sdlh.scene_count(window), Ok(2));
+
+    Script::new().key_down(Keycode::Escape).run(&sdlh).unwrap();
+    assert_eq!(sdlh.scene_count(window), Ok(1));
+
+    sdlh.pop_scene(window).unwrap();
+    assert_eq!(sdlh.scene_count(window), Ok(0));
+    assert_eq!(sdlh.dump_state().unwrap().windows[0].frames_presented, 2);
+}
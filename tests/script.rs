@@ -0,0 +1,54 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Script, Sdl2MtBuilder};
+use sdl2_mt::event::{Event, WindowEvent};
+use sdl2_mt::keyboard::Keycode;
+use sdl2_mt::mouse::MouseButton;
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// A script's input is handled in order, and waits for frames in between
+#[test]
+fn script_plays_back_input() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("script", 320, 240).unwrap();
+
+    let renderer = sdlh.clone();
+    let render_thread = thread::spawn(move || {
+        for _ in 0..10 {
+            renderer.present(window).unwrap();
+            thread::sleep(Duration::from_millis(5));
+        }
+    });
+
+    Script::new()
+        .key_press(Keycode::Escape)
+        .wait_frames(2)
+        .click(MouseButton::Left, 10, 10)
+        .resize(800, 600)
+        .run(&sdlh)
+        .unwrap();
+    render_thread.join().unwrap();
+    assert_eq!(sdlh.window_size(window).unwrap(), (800, 600));
+
+    let seen = Arc::new(Mutex::new(vec![]));
+    let handler_seen = seen.clone();
+    sdlh.handle_ui_events(Box::new(move |_sdl, _windows, event| {
+        let description = match *event {
+            Event::KeyDown { keycode: Some(Keycode::Escape), .. } => "escape down",
+            Event::KeyUp { keycode: Some(Keycode::Escape), .. } => "escape up",
+            Event::MouseButtonDown { .. } => "mouse down",
+            Event::MouseButtonUp { .. } => "mouse up",
+            Event::Window { win_event: WindowEvent::SizeChanged(800, 600), .. } => "resized",
+            _ => return false,
+        };
+        handler_seen.lock().unwrap().push(description);
+        true
+    })).unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), vec!["escape down", "escape up", "mouse down", "mouse up", "resized"]);
+}
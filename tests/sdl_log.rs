@@ -0,0 +1,45 @@
+#![cfg(all(feature = "sdl", feature = "log"))]
+
+extern crate log;
+extern crate sdl2_mt;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::sys::log::SDL_Log;
+
+use std::os::raw::c_char;
+use std::sync::Mutex;
+
+/// Keeps every record, so that the test can look for the ones that came from SDL
+struct Recorder(Mutex<Vec<(String, Level, String)>>);
+
+impl Log for Recorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push((record.target().to_string(), record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+/// SDL_Log on the UI thread ends up with the logger instead of on stderr
+#[test]
+fn sdl_log_reaches_logger() {
+    log::set_logger(&RECORDER).unwrap();
+    log::set_max_level(LevelFilter::Info);
+
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    sdlh.redirect_sdl_log().unwrap();
+    sdlh.call(|_sdl, _windows| unsafe {
+        SDL_Log(b"%s %d\0".as_ptr() as *const c_char, b"hello from SDL\0".as_ptr() as *const c_char, 42);
+    }).unwrap();
+
+    let records = RECORDER.0.lock().unwrap();
+    let expected = ("sdl2::application".to_string(), Level::Info, "hello from SDL 42".to_string());
+    assert!(records.contains(&expected), "{:?}", *records);
+}
@@ -0,0 +1,25 @@
+#![cfg(all(feature = "sdl", feature = "signals", unix))]
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::event::Event;
+use std::process::{self, Command};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// SIGINT notifies every handle, and reaches event forwarders as a quit event
+#[test]
+fn sigint_quits() {
+    let sdlh = Sdl2MtBuilder::new().headless().quit_on_signals().build();
+    let shutdown = sdlh.clone().shutdown_signals();
+    let (events_tx, events_rx) = channel();
+    sdlh.forward_events(events_tx).unwrap();
+
+    let status = Command::new("kill").arg("-INT").arg(process::id().to_string()).status().unwrap();
+    assert!(status.success());
+    shutdown.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(sdlh.shutdown_requested());
+
+    let quit = events_rx.iter().find(|event| matches!(**event, Event::Quit { .. }));
+    assert!(quit.is_some());
+}
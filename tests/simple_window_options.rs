@@ -0,0 +1,28 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, SimpleWindowOptions};
+
+/// Windows made from `SimpleWindowOptions` get the title, size and flags they asked for
+#[test]
+fn simple_window_options() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window_with(SimpleWindowOptions {
+        title: "from a config file".to_string(),
+        size: (64, 48),
+        position: Some((10, 20)),
+        resizable: false,
+        hidden: true,
+        ..SimpleWindowOptions::default()
+    }).unwrap();
+
+    let (title, size, resizable) = sdlh.with_canvas(window, |canvas| {
+        let window = canvas.window();
+        let resizable = window.window_flags() & sdl2_mt::sys::video::SDL_WindowFlags::SDL_WINDOW_RESIZABLE as u32 != 0;
+        (window.title().to_string(), window.size(), resizable)
+    }).unwrap();
+    assert_eq!(title, "from a config file");
+    assert_eq!(size, (64, 48));
+    assert!(!resizable);
+}
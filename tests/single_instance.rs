@@ -0,0 +1,25 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use std::process;
+use std::time::Duration;
+
+/// A second instance activates the first, and the first can be replaced once it is dropped
+#[test]
+fn single_instance() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let app_id = format!("sdl2_mt-test-{}", process::id());
+
+    let first = sdlh.single_instance(&app_id).unwrap().expect("the first instance");
+    let window = sdlh.create_simple_window("single instance", 32, 32).unwrap();
+    first.set_main_window(window);
+    let activations = first.activations();
+
+    assert!(sdlh.single_instance(&app_id).unwrap().is_none());
+    activations.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    drop(first);
+    assert!(sdlh.single_instance(&app_id).unwrap().is_some());
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2Mt, Sdl2MtBuilder, Sdl2MtError, TestClock};
+
+use std::time::Duration;
+
+/// A 24 bit BMP file of a single color
+fn bmp(width: u32, height: u32, (r, g, b): (u8, u8, u8)) -> Vec<u8> {
+    let row = (width * 3).div_ceil(4) * 4;
+    let size = 54 + row * height;
+    let mut data = vec![];
+    data.extend_from_slice(b"BM");
+    for &value in &[size, 0, 54, 40, width, height] {
+        data.extend_from_slice(&[value as u8, (value >> 8) as u8, (value >> 16) as u8, (value >> 24) as u8]);
+    }
+    data.extend_from_slice(&[1, 0, 24, 0]);
+    data.extend_from_slice(&[0; 24]);
+    for _ in 0..height {
+        for _ in 0..width {
+            data.extend_from_slice(&[b, g, r]);
+        }
+        data.resize(data.len() + (row - width * 3) as usize, 0);
+    }
+    data
+}
+
+fn window_count(sdlh: &Sdl2Mt) -> usize {
+    sdlh.call(|_sdl, windows| windows.len()).unwrap()
+}
+
+/// The splash screen shows its image, and stays up for its minimum duration
+#[test]
+fn splash_screen() {
+    let clock = TestClock::new();
+    let sdlh = Sdl2MtBuilder::new().headless().test_clock(clock).build();
+
+    let splash = sdlh.show_splash(bmp(5, 3, (200, 100, 50)), Duration::from_secs(1)).unwrap();
+    let pixels = sdlh.read_window_pixels(splash.window_id()).unwrap();
+    assert_eq!((pixels.width, pixels.height), (5, 3));
+    assert_eq!(pixels.pixel(4, 2), (200, 100, 50));
+
+    splash.close().unwrap();
+    sdlh.advance_clock(Duration::from_millis(999)).unwrap();
+    assert_eq!(window_count(&sdlh), 1);
+    sdlh.advance_clock(Duration::from_millis(250)).unwrap();
+    assert_eq!(window_count(&sdlh), 0);
+
+    match sdlh.show_splash(b"not a bmp".to_vec(), Duration::from_secs(1)) {
+        Err(Sdl2MtError::SdlError(message)) => assert!(message.starts_with("SDL_LoadBMP_RW")),
+        _ => panic!("expected an SDL error"),
+    }
+}
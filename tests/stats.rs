@@ -0,0 +1,26 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Counts messages and measures how long they took
+#[test]
+fn stats() {
+    let sdlh = sdl2_mt::init();
+    sdlh.flush().unwrap();
+    sdlh.stats();
+
+    sdlh.run_on_ui_thread(Box::new(|_sdl, _windows| sleep(Duration::from_millis(20)))).unwrap();
+    sdlh.flush().unwrap();
+
+    let stats = sdlh.stats();
+    assert_eq!(stats.queue_depth, 0);
+    assert!(stats.messages_processed >= 2);
+    assert!(stats.max_execution_time >= Duration::from_millis(20));
+    assert!(stats.max_reply_latency >= Duration::from_millis(20));
+
+    // each call starts a new interval
+    assert_eq!(sdlh.stats().max_execution_time, Duration::from_secs(0));
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtError;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Submits more lambdas than fit in the ring buffer from another thread
+#[test]
+fn submit_in_order() {
+    let sdlh = sdl2_mt::init();
+    let ran = Arc::new(Mutex::new(vec![]));
+
+    let mut submitter = sdlh.submitter(16).unwrap();
+    let producer_ran = ran.clone();
+    thread::spawn(move || {
+        for i in 0..1000 {
+            let ran = producer_ran.clone();
+            submitter.submit(move |_sdl, _windows| ran.lock().unwrap().push(i)).unwrap();
+        }
+    }).join().unwrap();
+
+    // the dropped submitter's lambdas still run, and draining them is a high priority message
+    sdlh.flush().unwrap();
+    assert_eq!(*ran.lock().unwrap(), (0..1000).collect::<Vec<_>>());
+}
+
+/// A ring buffer with no room for any lambda is refused
+#[test]
+fn zero_capacity() {
+    let sdlh = sdl2_mt::init();
+    match sdlh.submitter(0) {
+        Err(Sdl2MtError::InvalidArgument(_)) => {},
+        _ => panic!("expected a capacity of zero to be refused"),
+    }
+}
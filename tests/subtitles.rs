@@ -0,0 +1,51 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{AvClock, Cue, CuePosition, CueStyle, Sdl2MtBuilder, Subtitles};
+use sdl2_mt::pixels::Color;
+
+use std::time::Duration;
+
+/// Cues are active between their start and end, and drawn with the built-in font
+#[test]
+fn subtitles_follow_the_clock() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("subtitles", 64, 32).unwrap();
+
+    let clock = AvClock::new();
+    clock.pause();
+    let subtitles = Subtitles::new(&clock);
+    subtitles.set_style(CueStyle {
+        color: Color::RGB(255, 255, 255),
+        background: None,
+        scale: 1,
+        position: CuePosition::Top,
+        margin: 0,
+    });
+    subtitles.push(Cue::new("I", Duration::from_secs(1), Duration::from_secs(2)));
+    assert!(subtitles.active().is_empty());
+    clock.seek(Duration::from_secs(1));
+    assert_eq!(subtitles.active(), vec!["I".to_string()]);
+
+    let track = subtitles.clone();
+    sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+        let canvas = windows.get_mut(&window).unwrap();
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+        track.draw(canvas).unwrap();
+    }).unwrap();
+    // "I" is centered at the top, and its first row is 4 pixels wide
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(28, 0), (0, 0, 0));
+    assert_eq!(pixels.pixel(29, 0), (255, 255, 255));
+    assert_eq!(pixels.pixel(32, 0), (255, 255, 255));
+    assert_eq!(pixels.pixel(33, 0), (0, 0, 0));
+
+    clock.seek(Duration::from_secs(2));
+    assert!(subtitles.active().is_empty());
+    sdlh.show_subtitles(window, &subtitles).unwrap();
+    sdlh.present(window).unwrap();
+    sdlh.hide_subtitles(window).unwrap();
+    sdlh.flush().unwrap();
+}
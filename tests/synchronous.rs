@@ -0,0 +1,43 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Without a UI thread, work only happens when the test turns the loop
+#[test]
+fn synchronous_turns() {
+    let sdlh = Sdl2MtBuilder::new().headless().synchronous().build();
+
+    // synchronous calls turn the loop by themselves
+    let window = sdlh.create_simple_window("synchronous", 64, 64).unwrap();
+    assert_eq!(sdlh.window_size(window).unwrap(), (64, 64));
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let lambda_runs = runs.clone();
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+        lambda_runs.fetch_add(1, Ordering::SeqCst);
+    })).unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+    sdlh.turn().unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+    // lambdas can't make synchronous calls while the loop is turning
+    let nested = sdlh.clone();
+    let nested_result = Arc::new(std::sync::Mutex::new(None));
+    let lambda_result = nested_result.clone();
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+        *lambda_result.lock().unwrap() = Some(nested.flush());
+    })).unwrap();
+    sdlh.turn().unwrap();
+    assert_eq!(*nested_result.lock().unwrap(), Some(Err(Sdl2MtError::WouldDeadlock)));
+
+    // exiting takes effect on the next turn, without waiting for another thread
+    sdlh.clone().exit().unwrap();
+    assert_eq!(sdlh.turn(), Err(Sdl2MtError::UiThreadExited));
+    assert_eq!(sdlh.flush(), Err(Sdl2MtError::UiThreadExited));
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+/// Unhandled events can be taken off the UI thread, after which they are gone
+#[test]
+fn take_unhandled_events() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("take", 16, 16).unwrap();
+
+    sdlh.inject_key(window, Keycode::A, true).unwrap();
+    sdlh.inject_key(window, Keycode::B, true).unwrap();
+    sdlh.flush().unwrap();
+
+    let keys: Vec<Keycode> = sdlh.take_unhandled_events().unwrap().into_iter().filter_map(|event| match event {
+        Event::KeyDown { keycode, .. } => keycode,
+        _ => None,
+    }).collect();
+    assert_eq!(keys, vec![Keycode::A, Keycode::B]);
+
+    sdlh.handle_ui_events(Box::new(|_sdl, _windows, event| match *event {
+        Event::KeyDown { .. } => panic!("taken events must not be handled again"),
+        _ => true,
+    })).unwrap();
+}
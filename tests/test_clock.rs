@@ -0,0 +1,52 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, TestClock};
+
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Timers follow the test clock instead of real time
+#[test]
+fn timers_follow_test_clock() {
+    let clock = TestClock::new();
+    let sdlh = Sdl2MtBuilder::new().headless().test_clock(clock.clone()).build();
+
+    let once = Arc::new(AtomicUsize::new(0));
+    let repeating = Arc::new(AtomicUsize::new(0));
+    let once_count = once.clone();
+    let repeating_count = repeating.clone();
+    sdlh.schedule(Duration::from_secs(60), Box::new(move |_sdl, _windows| {
+        once_count.fetch_add(1, Ordering::SeqCst);
+    })).unwrap();
+    let timer = sdlh.schedule_repeating(Duration::from_secs(1), Box::new(move |_sdl, _windows| {
+        repeating_count.fetch_add(1, Ordering::SeqCst);
+    })).unwrap();
+
+    sdlh.advance_clock(Duration::from_millis(999)).unwrap();
+    assert_eq!(repeating.load(Ordering::SeqCst), 0);
+
+    for _ in 0..59 {
+        sdlh.advance_clock(Duration::from_secs(1)).unwrap();
+    }
+    assert_eq!(repeating.load(Ordering::SeqCst), 59);
+    assert_eq!(once.load(Ordering::SeqCst), 0);
+
+    sdlh.cancel_timer(timer).unwrap();
+    sdlh.advance_clock(Duration::from_millis(1)).unwrap();
+    assert_eq!(once.load(Ordering::SeqCst), 1);
+    assert_eq!(repeating.load(Ordering::SeqCst), 59);
+    assert_eq!(clock.elapsed(), Duration::from_secs(60));
+
+    // advancing the clock on its own wakes the UI thread, which then runs the due timers
+    let (tx, rx) = channel();
+    sdlh.schedule(Duration::from_secs(1), Box::new(move |_sdl, _windows| {
+        let _ = tx.send(());
+    })).unwrap();
+    sdlh.flush().unwrap();
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(()));
+}
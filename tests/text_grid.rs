@@ -0,0 +1,47 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{GridCell, Sdl2MtBuilder, TextGrid};
+use sdl2_mt::pixels::Color;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Cells set from another thread show up in the window
+#[test]
+fn text_grid_window() {
+    let grid = TextGrid::new(2, 4);
+    grid.print(0, 2, "Hello", Color::RGB(255, 255, 255));
+    assert_eq!(grid.get(0, 3).unwrap().character, 'e');
+    assert_eq!(grid.get(0, 4), None);
+    grid.scroll_up(1);
+    assert_eq!(grid.get(1, 2), Some(GridCell::default()));
+    assert_eq!(grid.get(0, 2).unwrap().character, 'H');
+
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_text_grid_window("grid", &grid, 1).unwrap();
+
+    let writer = grid.clone();
+    thread::spawn(move || {
+        writer.set_cell(1, 0, GridCell {
+            character: 'I',
+            foreground: Color::RGB(255, 0, 0),
+            background: Color::RGB(0, 0, 255),
+        });
+    }).join().unwrap();
+
+    // "I" starts with a row 4 pixels wide, one pixel from the left of its cell
+    let deadline = Instant::now() + Duration::from_secs(2);
+    loop {
+        let pixels = sdlh.read_window_pixels(window).unwrap();
+        assert_eq!((pixels.width, pixels.height), (32, 16));
+        if pixels.pixel(1, 8) == (255, 0, 0) {
+            assert_eq!(pixels.pixel(0, 8), (0, 0, 255));
+            break;
+        }
+        assert!(Instant::now() < deadline, "the grid wasn't redrawn");
+        thread::sleep(Duration::from_millis(10));
+    }
+    sdlh.hide_text_grid(window).unwrap();
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+
+use std::thread;
+use std::time::Duration;
+
+/// Ticks and the performance counter move forward, and presenting counts frames
+#[test]
+fn ticks_and_frame_number() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("ticks", 8, 8).unwrap();
+
+    let ticks = sdlh.ticks();
+    let counter = sdlh.performance_counter();
+    thread::sleep(Duration::from_millis(20));
+    assert!(sdlh.ticks() >= ticks + 20);
+    assert!(sdlh.performance_elapsed(counter) >= Duration::from_millis(20));
+
+    let frame = sdlh.frame_number();
+    sdlh.present(window).unwrap();
+    sdlh.present(window).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(sdlh.frame_number(), frame + 2);
+}
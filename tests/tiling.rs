@@ -0,0 +1,46 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Layout, Sdl2Mt, Sdl2MtBuilder, WindowId, layout_cells};
+use sdl2_mt::rect::Rect;
+
+fn window_rect(sdlh: &Sdl2Mt, id: WindowId) -> Rect {
+    sdlh.call(move |_sdl, windows| {
+        let window = windows[&id].window();
+        let (x, y) = window.position();
+        let (width, height) = window.size();
+        Rect::new(x, y, width, height)
+    }).unwrap()
+}
+
+/// Grids are as square as they can be, and columns and rows split one way
+#[test]
+fn layout_cells_split_the_display() {
+    let bounds = Rect::new(0, 0, 900, 600);
+    assert_eq!(layout_cells(Layout::Grid, bounds, 5), vec![
+        Rect::new(0, 0, 300, 300), Rect::new(300, 0, 300, 300), Rect::new(600, 0, 300, 300),
+        Rect::new(0, 300, 300, 300), Rect::new(300, 300, 300, 300),
+    ]);
+    assert_eq!(layout_cells(Layout::Columns, bounds, 2), vec![Rect::new(0, 0, 450, 600), Rect::new(450, 0, 450, 600)]);
+    assert_eq!(layout_cells(Layout::Rows, bounds, 2), vec![Rect::new(0, 0, 900, 300), Rect::new(0, 300, 900, 300)]);
+}
+
+/// Tiled windows fill the display, and close the gap when one of them is closed
+#[test]
+fn tile_windows() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let first = sdlh.create_simple_window("first", 10, 10).unwrap();
+    let second = sdlh.create_simple_window("second", 10, 10).unwrap();
+    let bounds = sdlh.call(|sdl, _windows| sdl.video().unwrap().display_bounds(0).unwrap()).unwrap();
+
+    sdlh.tile_windows(&[first, second], Layout::Columns, 0).unwrap();
+    assert_eq!(window_rect(&sdlh, first), layout_cells(Layout::Columns, bounds, 2)[0]);
+    assert_eq!(window_rect(&sdlh, second), layout_cells(Layout::Columns, bounds, 2)[1]);
+
+    sdlh.run_on_ui_thread_once(move |_sdl, windows| {
+        windows.remove(&second);
+    }).unwrap();
+    sdlh.handle_ui_events(Box::new(|_sdl, _windows, _event| false)).unwrap();
+    assert_eq!(window_rect(&sdlh, first), bounds);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Schedules a lambda and waits for it to run
+#[test]
+fn schedule_once() {
+    let sdlh = sdl2_mt::init();
+
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+    sdlh.schedule(Duration::from_millis(100), Box::new(move |_sdl, _windows| {
+        tx.send(Instant::now()).unwrap();
+    })).unwrap();
+
+    let ran_at = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(ran_at - start >= Duration::from_millis(100));
+
+    // a one-shot timer only runs once
+    assert!(rx.recv_timeout(Duration::from_millis(250)).is_err());
+}
+
+/// Runs a repeating timer a few times, then cancels it
+#[test]
+fn schedule_repeating() {
+    let sdlh = sdl2_mt::init();
+
+    let (tx, rx) = mpsc::channel();
+    let timer = sdlh.schedule_repeating(Duration::from_millis(20), Box::new(move |_sdl, _windows| {
+        let _ = tx.send(());
+    })).unwrap();
+
+    for _ in 0..3 {
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    sdlh.cancel_timer(timer).unwrap();
+
+    // drain anything that ran before the cancellation was processed
+    sdlh.flush().unwrap();
+    while rx.try_recv().is_ok() {}
+
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+}
+
+/// Only the last of a burst of debounced lambdas runs
+#[test]
+fn run_debounced() {
+    let sdlh = sdl2_mt::init();
+
+    let (tx, rx) = mpsc::channel();
+    for i in 0..5 {
+        let tx = tx.clone();
+        sdlh.run_debounced("resize", Duration::from_millis(50), Box::new(move |_sdl, _windows| {
+            tx.send(i).unwrap();
+        })).unwrap();
+    }
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), 4);
+    assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+/// Synchronous calls can give up on a busy UI thread
+#[test]
+fn call_timeout() {
+    let sdlh = sdl2_mt::init();
+
+    sdlh.run_on_ui_thread(Box::new(|_sdl, _windows| ::std::thread::sleep(Duration::from_millis(200)))).unwrap();
+
+    let result = sdlh.call_timeout(|_sdl, _windows| (), Duration::from_millis(10));
+    assert_eq!(result, Err(sdl2_mt::Sdl2MtError::Timeout));
+}
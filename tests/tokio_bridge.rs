@@ -0,0 +1,50 @@
+#![cfg(all(feature = "sdl", feature = "tokio"))]
+
+extern crate sdl2_mt;
+extern crate smol;
+extern crate tokio;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+use tokio::sync::broadcast::error::TryRecvError;
+
+/// The oneshot receiver completes with the function's return value
+#[test]
+fn run_on_ui_thread_oneshot() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+
+    let answer = smol::block_on(sdlh.run_on_ui_thread_oneshot(|_sdl, windows| windows.len() + 42));
+    assert_eq!(answer.unwrap(), 42);
+}
+
+/// Broadcast receivers see new events, and a zero capacity is refused without
+/// bothering the UI thread
+#[test]
+fn broadcast_events() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    match sdlh.broadcast_events(0) {
+        Err(Sdl2MtError::InvalidArgument(_)) => {},
+        other => panic!("expected InvalidArgument, got {:?}", other.map(|_| ())),
+    }
+
+    let mut receiver = sdlh.broadcast_events(64).unwrap();
+    let window = sdlh.create_simple_window("broadcast", 16, 16).unwrap();
+    sdlh.inject_key(window, Keycode::A, true).unwrap();
+    // events are pumped after each batch of messages, so the second flush comes after the pump
+    sdlh.flush().unwrap();
+    sdlh.flush().unwrap();
+
+    let mut keys = vec![];
+    loop {
+        match receiver.try_recv() {
+            Ok(event) => if let Event::KeyDown { keycode: Some(keycode), .. } = *event {
+                keys.push(keycode);
+            },
+            Err(TryRecvError::Lagged(_)) => {},
+            Err(_) => break,
+        }
+    }
+    assert_eq!(keys, vec![Keycode::A]);
+}
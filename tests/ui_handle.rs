@@ -0,0 +1,42 @@
+#![cfg(feature = "sdl")]
+
+#[macro_use]
+extern crate sdl2_mt;
+
+use sdl2_mt::{Backpressure, Sdl2MtBuilder, Sdl2MtError};
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Work queued through the context's handle runs later, and a full queue is an error
+/// instead of a deadlock
+#[test]
+fn queue_from_ui_thread() {
+    let sdlh = Sdl2MtBuilder::new()
+        .headless()
+        .queue_capacity(2).unwrap()
+        .backpressure(Backpressure::Block)
+        .build();
+
+    let (tx, rx) = mpsc::channel();
+    ui!(sdlh, move |ctx| {
+        let timer_tx = tx.clone();
+        ctx.handle.schedule(Duration::from_millis(1), Box::new(move |_sdl, _windows| {
+            timer_tx.send("timer").unwrap();
+        })).unwrap();
+        let lambda_tx = tx.clone();
+        ctx.handle.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+            lambda_tx.send("lambda").unwrap();
+        })).unwrap();
+
+        // an `Sdl2Mt` would wait for the UI thread to make room here
+        match ctx.handle.run_on_ui_thread(Box::new(|_sdl, _windows| {})) {
+            Err(Sdl2MtError::QueueFull) => tx.send("full").unwrap(),
+            _ => tx.send("not full").unwrap(),
+        }
+    }).unwrap();
+
+    let mut received: Vec<&str> = rx.iter().take(3).collect();
+    received.sort();
+    assert_eq!(received, vec!["full", "lambda", "timer"]);
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "sdl")]
+
+#[macro_use]
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError, WindowId};
+use sdl2_mt::pixels::Color;
+
+use std::sync::mpsc;
+
+/// `ui!` runs its block with the windows, and reports errors it returns
+#[test]
+fn ui_macro() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("ui!", 4, 4).unwrap();
+    let errors = sdlh.errors();
+
+    let (tx, rx) = mpsc::channel();
+    ui!(sdlh, move |ctx| {
+        let canvas = ctx.canvas(window).unwrap();
+        canvas.set_draw_color(Color::RGB(255, 0, 0));
+        canvas.clear();
+        tx.send(canvas.output_size().unwrap()).unwrap();
+    }).unwrap();
+    assert_eq!(rx.recv().unwrap(), (4, 4));
+
+    ui!(sdlh, move |ctx| {
+        ctx.canvas(WindowId::from_raw(window.raw() + 1)).ok_or("no such window")?;
+        Ok(())
+    }).unwrap();
+    sdlh.flush().unwrap();
+    match errors.try_recv() {
+        Ok(Sdl2MtError::SdlError(message)) => assert!(message.contains("no such window")),
+        other => panic!("expected an SdlError, got {:?}", other),
+    }
+
+    on_event!(sdlh, move |ctx, _event| ctx.windows.contains_key(&window)).unwrap();
+}
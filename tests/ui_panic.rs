@@ -0,0 +1,29 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError};
+use sdl2_mt::event::Event;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// A panicking lambda ends the UI thread, and everyone finds out about it
+#[test]
+fn ui_thread_panic() {
+    let sdlh = Sdl2MtBuilder::new().panic_hook(true).build();
+    let errors = sdlh.errors();
+    let (events_tx, events_rx) = channel();
+    sdlh.forward_events(events_tx).unwrap();
+
+    sdlh.run_on_ui_thread_once(|_sdl, _windows| panic!("lambda failed")).unwrap();
+
+    let quit = events_rx.iter().find(|event| matches!(**event, Event::Quit { .. }));
+    assert!(quit.is_some());
+
+    match errors.recv_timeout(Duration::from_secs(5)) {
+        Ok(Sdl2MtError::UiThreadPanicked(message)) => assert!(message.contains("lambda failed")),
+        other => panic!("expected a panic to be reported, got {:?}", other),
+    }
+    assert!(sdlh.ui_thread_panic().unwrap().contains("lambda failed"));
+    assert_eq!(sdlh.call(|_sdl, _windows| ()), Err(Sdl2MtError::UiThreadExited));
+}
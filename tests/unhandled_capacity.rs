@@ -0,0 +1,36 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2Mt, Sdl2MtBuilder};
+use sdl2_mt::event::Event;
+use sdl2_mt::keyboard::Keycode;
+
+fn take_keys(sdlh: &Sdl2Mt) -> Vec<Keycode> {
+    sdlh.take_unhandled_events().unwrap().into_iter().filter_map(|event| match event {
+        Event::KeyDown { keycode, .. } => keycode,
+        _ => None,
+    }).collect()
+}
+
+/// Once more events are waiting than the capacity allows, the oldest are dropped
+#[test]
+fn oldest_unhandled_events_are_dropped() {
+    let sdlh = Sdl2MtBuilder::new().headless().unhandled_event_capacity(2).build();
+    let window = sdlh.create_simple_window("capacity", 16, 16).unwrap();
+
+    // let the window's own events come and go first
+    sdlh.flush().unwrap();
+    take_keys(&sdlh);
+
+    for &key in &[Keycode::A, Keycode::B, Keycode::C, Keycode::D] {
+        sdlh.inject_key(window, key, true).unwrap();
+    }
+    sdlh.flush().unwrap();
+    assert_eq!(take_keys(&sdlh), vec![Keycode::C, Keycode::D]);
+
+    // room is made again once they are taken
+    sdlh.inject_key(window, Keycode::E, true).unwrap();
+    sdlh.flush().unwrap();
+    assert_eq!(take_keys(&sdlh), vec![Keycode::E]);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+use sdl2_mt::pixels::Color;
+use sdl2_mt::rect::Rect;
+
+use std::sync::{Arc, Mutex};
+
+/// Drawing in a viewport is moved and clipped to it, and the canvas is back to normal afterwards
+#[test]
+fn draw_in_viewport() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("viewport", 16, 16).unwrap();
+    let (full, clip) = sdlh.call(move |_sdl, windows| {
+        let canvas = &windows[&window];
+        (canvas.viewport(), canvas.clip_rect())
+    }).unwrap();
+
+    sdlh.clear(window, Color::RGB(0, 0, 0)).unwrap();
+    sdlh.define_viewport(window, "panel", Rect::new(4, 4, 8, 8)).unwrap();
+
+    let inside = Arc::new(Mutex::new(None));
+    let seen = inside.clone();
+    sdlh.draw_in_viewport(window, "panel", move |canvas| {
+        *seen.lock().unwrap() = Some((canvas.viewport(), canvas.clip_rect()));
+        canvas.set_draw_color(Color::RGB(200, 0, 0));
+        canvas.fill_rect(Rect::new(-10, -10, 100, 100)).unwrap();
+    }).unwrap();
+    sdlh.flush().unwrap();
+
+    assert_eq!(*inside.lock().unwrap(), Some((Rect::new(4, 4, 8, 8), Some(Rect::new(0, 0, 8, 8)))));
+    let pixels = sdlh.read_window_pixels(window).unwrap();
+    assert_eq!(pixels.pixel(4, 4), (200, 0, 0));
+    assert_eq!(pixels.pixel(11, 11), (200, 0, 0));
+    assert_eq!(pixels.pixel(3, 3), (0, 0, 0));
+    assert_eq!(pixels.pixel(12, 12), (0, 0, 0));
+
+    let after = sdlh.call(move |_sdl, windows| {
+        let canvas = &windows[&window];
+        (canvas.viewport(), canvas.clip_rect())
+    }).unwrap();
+    assert_eq!(after, (full, clip));
+
+    // a removed viewport can't be drawn into any more
+    sdlh.remove_viewport(window, "panel").unwrap();
+    sdlh.draw_in_viewport(window, "panel", |_canvas| panic!("drew into a removed viewport")).unwrap();
+    sdlh.flush().unwrap();
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2Mt, Sdl2MtBuilder, Sdl2MtError, WindowId};
+use sdl2_mt::sys::render::SDL_RENDERER_PRESENTVSYNC;
+
+fn vsync(sdlh: &Sdl2Mt, window: WindowId) -> bool {
+    sdlh.renderer_info(window).unwrap().flags & SDL_RENDERER_PRESENTVSYNC != 0
+}
+
+/// The headless software renderer supports switching vsync on SDL 2.0.18 and newer,
+/// and older versions of SDL report that they can't
+#[test]
+fn set_vsync() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("vsync", 8, 8).unwrap();
+    let version = sdlh.call(|_sdl, _windows| sdl2_mt::version::version()).unwrap();
+
+    if (version.major, version.minor, version.patch) < (2, 0, 18) {
+        match sdlh.set_vsync(window, true) {
+            Err(Sdl2MtError::SdlError(_)) => {},
+            other => panic!("expected an SdlError on SDL {}, got {:?}", version, other),
+        }
+        return;
+    }
+
+    assert_eq!(sdlh.set_vsync(window, true), Ok(()));
+    assert!(vsync(&sdlh, window));
+    assert_eq!(sdlh.set_vsync(window, false), Ok(()));
+    assert!(!vsync(&sdlh, window));
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::WorkKind;
+use std::sync::mpsc::channel;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Reports a lambda that keeps the UI thread busy for too long, while it is still running
+#[test]
+fn slow_lambda_reported() {
+    let sdlh = sdl2_mt::init();
+
+    let (tx, rx) = channel();
+    sdlh.set_watchdog(Duration::from_millis(20), Box::new(move |slow| {
+        let _ = tx.send(*slow);
+    }));
+
+    let (done_tx, done_rx) = channel();
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, _windows| {
+        sleep(Duration::from_millis(300));
+        done_tx.send(()).unwrap();
+    })).unwrap();
+
+    let slow = rx.recv_timeout(Duration::from_millis(250)).unwrap();
+    assert_eq!(slow.kind, WorkKind::Lambda);
+    assert!(slow.running_for >= Duration::from_millis(20));
+    assert!(done_rx.try_recv().is_err());
+
+    done_rx.recv().unwrap();
+    sdlh.clear_watchdog();
+}
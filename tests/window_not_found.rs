@@ -0,0 +1,55 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError};
+use sdl2_mt::pixels::Color;
+use sdl2_mt::rect::Rect;
+
+/// Work queued for a window that is gone is reported instead of panicking
+#[test]
+fn window_not_found() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("gone", 8, 8).unwrap();
+    let errors = sdlh.errors();
+    sdlh.define_viewport(window, "panel", Rect::new(0, 0, 4, 4)).unwrap();
+
+    sdlh.run_on_ui_thread(Box::new(move |_sdl, windows| {
+        windows.remove(&window);
+    })).unwrap();
+    sdlh.present_color(window, Color::RGB(0, 0, 0)).unwrap();
+    sdlh.set_camera(window, (0.0, 0.0), 2.0).unwrap();
+    sdlh.draw_in_viewport(window, "panel", |_canvas| panic!("the window is gone")).unwrap();
+    sdlh.flush().unwrap();
+
+    for _ in 0..3 {
+        assert_eq!(errors.try_recv(), Ok(Sdl2MtError::WindowNotFound(window)));
+    }
+
+    let not_found = Some(Sdl2MtError::WindowNotFound(window));
+    assert_eq!(sdlh.create_render_target(window, 4, 4).err(), not_found);
+    assert_eq!(sdlh.window_size(window).err(), not_found);
+    assert_eq!(sdlh.drawable_size(window).err(), not_found);
+    assert_eq!(sdlh.scale_factor(window).err(), not_found);
+    assert_eq!(sdlh.renderer_info(window).err(), not_found);
+    assert_eq!(sdlh.set_vsync(window, true).err(), not_found);
+    assert_eq!(sdlh.native_handle(window).err(), not_found);
+    assert_eq!(sdlh.frame_times(window).err(), not_found);
+    assert_eq!(sdlh.read_window_pixels(window).err(), not_found);
+}
+
+/// Drawing into a viewport that was never defined is reported as well
+#[test]
+fn viewport_not_found() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("no viewport", 8, 8).unwrap();
+    let errors = sdlh.errors();
+
+    sdlh.draw_in_viewport(window, "missing", |_canvas| panic!("there is no such viewport")).unwrap();
+    sdlh.flush().unwrap();
+
+    match errors.try_recv() {
+        Ok(Sdl2MtError::InvalidArgument(_)) => {},
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+}
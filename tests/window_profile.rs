@@ -0,0 +1,26 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, WindowId, WindowProfile};
+
+/// Simple windows take their title prefix, size and flags from the default profile
+#[test]
+fn default_window_profile() {
+    let profile = WindowProfile::new().title_prefix("Tool - ").size(64, 48).resizable(false);
+    let sdlh = Sdl2MtBuilder::new().headless().default_window_profile(profile).build();
+
+    let sized = sdlh.create_simple_window("Sized", 0, 0).unwrap();
+    let explicit = sdlh.create_simple_window("Explicit", 32, 16).unwrap();
+    let windows = sdlh.call(move |_sdl, windows| {
+        let describe = |id: &WindowId| {
+            let window = windows[id].window();
+            let resizable = window.window_flags() & sdl2_mt::sys::video::SDL_WindowFlags::SDL_WINDOW_RESIZABLE as u32 != 0;
+            (window.title().to_owned(), window.size(), resizable)
+        };
+        (describe(&sized), describe(&explicit))
+    }).unwrap();
+
+    assert_eq!(windows.0, ("Tool - Sized".to_owned(), (64, 48), false));
+    assert_eq!(windows.1, ("Tool - Explicit".to_owned(), (32, 16), false));
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtError, WindowId};
+
+/// Queries the size of a window and changes its logical size
+#[test]
+fn window_queries() {
+    //sdlh is "sdl handle"
+    let sdlh = sdl2_mt::init();
+
+    let window = sdlh.create_simple_window("2D plot", 720, 480).unwrap();
+
+    assert_eq!(sdlh.window_size(window).unwrap(), (720, 480));
+    assert!(sdlh.drawable_size(window).is_ok());
+    assert!(sdlh.scale_factor(window).unwrap() >= 1.0);
+    assert!(sdlh.renderer_info(window).is_ok());
+
+    sdlh.set_logical_size(window, Some((160, 120))).unwrap();
+    sdlh.set_integer_scale(window, true).unwrap();
+    let logical_size = sdlh.call(move |_sdl, windows| windows[&window].logical_size()).unwrap();
+    assert_eq!(logical_size, (160, 120));
+
+    // there is no window with ID 0
+    assert_eq!(sdlh.window_size(WindowId::from_raw(0)), Err(Sdl2MtError::WindowNotFound(WindowId::from_raw(0))));
+
+    sdlh.exit().unwrap();
+}
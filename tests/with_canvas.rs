@@ -0,0 +1,20 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl2MtBuilder, Sdl2MtError, WindowId};
+
+/// `with_canvas` hands back what the function returns, or an error for a missing window
+#[test]
+fn with_canvas() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let window = sdlh.create_simple_window("with_canvas", 12, 34).unwrap();
+
+    let size = sdlh.with_canvas(window, |canvas| canvas.output_size().unwrap()).unwrap();
+    assert_eq!(size, (12, 34));
+
+    match sdlh.with_canvas(WindowId::from_raw(window.raw() + 1), |_canvas| ()) {
+        Err(Sdl2MtError::WindowNotFound(id)) => assert_eq!(id, WindowId::from_raw(window.raw() + 1)),
+        other => panic!("expected WindowNotFound, got {:?}", other),
+    }
+}
@@ -0,0 +1,13 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtBuilder;
+
+/// `with_sdl` hands back what the function returns
+#[test]
+fn with_sdl() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let driver = sdlh.with_sdl(|sdl| sdl.video().map(|video| video.current_video_driver().to_string())).unwrap();
+    assert!(driver.is_ok());
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::{Sdl, Sdl2MtBuilder, TaskState, WindowId};
+use sdl2_mt::render::WindowCanvas;
+
+use std::collections::HashMap;
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+/// Jobs run off the UI thread, their results are applied on it, and cancelled jobs are dropped
+#[test]
+fn spawn_background() {
+    let sdlh = Sdl2MtBuilder::new().headless().build();
+    let ui_thread = sdlh.call(|_sdl, _windows| thread::current().id()).unwrap();
+    let (tx, rx) = channel();
+
+    let job = sdlh.spawn_background(move || {
+        let sum: u64 = (1..101).sum();
+        let worker = thread::current().id();
+        move |_sdl: &mut Sdl, _windows: &mut HashMap<WindowId, WindowCanvas>| {
+            tx.send((sum, worker, thread::current().id())).unwrap();
+        }
+    });
+    let (sum, worker, applied_on) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(sum, 5050);
+    assert!(worker != ui_thread);
+    assert_eq!(applied_on, ui_thread);
+    sdlh.flush().unwrap();
+    assert_eq!(job.state(), TaskState::Finished);
+    assert!(!job.cancel());
+
+    let (gate_tx, gate_rx) = channel::<()>();
+    let blocked = sdlh.spawn_background(move || {
+        gate_rx.recv().unwrap();
+        |_sdl: &mut Sdl, _windows: &mut HashMap<WindowId, WindowCanvas>| panic!("a cancelled job was applied")
+    });
+    assert!(blocked.cancel());
+    let _ = gate_tx.send(());
+    sdlh.flush().unwrap();
+    assert_eq!(blocked.state(), TaskState::Cancelled);
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "sdl")]
+
+extern crate sdl2_mt;
+
+use sdl2_mt::Sdl2MtError;
+use std::sync::mpsc::channel;
+
+/// Synchronous calls from the UI thread fail instead of waiting for themselves
+#[test]
+fn synchronous_call_from_ui_thread() {
+    let sdlh = sdl2_mt::init();
+    let (tx, rx) = channel();
+
+    let inner = sdlh.clone();
+    sdlh.run_on_ui_thread_once(move |_sdl, _windows| {
+        tx.send(inner.call(|_sdl, _windows| ())).unwrap();
+        tx.send(inner.handle_ui_events(Box::new(|_sdl, _windows, _event| false))).unwrap();
+    }).unwrap();
+
+    assert_eq!(rx.recv().unwrap(), Err(Sdl2MtError::WouldDeadlock));
+    assert_eq!(rx.recv().unwrap(), Err(Sdl2MtError::WouldDeadlock));
+
+    // other threads are unaffected
+    sdlh.call(|_sdl, _windows| ()).unwrap();
+}